@@ -0,0 +1,213 @@
+//! Provides the [`anneal`] function: the search loop
+//! shared by [`SA`](crate::SA) and [`DynSA`](crate::DynSA)
+
+use itertools::izip;
+use num::Float;
+use numeric_literals::replace_float_literals;
+use rand::prelude::*;
+use rand_distr::{uniform::SampleUniform, Uniform};
+
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::ops::Range;
+
+use crate::{Converged, Reheat, Schedule, VarKind, APF};
+
+/// Settings of the search which don't depend on the storage of the points
+pub(crate) struct Settings<'a, F, R>
+where
+    F: Float + SampleUniform + Debug,
+    R: Rng,
+{
+    /// Initial temperature
+    pub(crate) t_0: F,
+    /// Minimum temperature
+    pub(crate) t_min: F,
+    /// Bounds of the parameter space
+    pub(crate) bounds: &'a [Range<F>],
+    /// Acceptance probability function
+    pub(crate) apf: &'a APF<F, R>,
+    /// Kinds of the coordinates
+    pub(crate) kinds: &'a [VarKind<F>],
+    /// Annealing schedule
+    pub(crate) schedule: &'a Schedule<F>,
+    /// Reheating policy (if any)
+    pub(crate) reheat: Option<Reheat<F>>,
+    /// Convergence criterion (if any)
+    pub(crate) stop: Option<Converged<F>>,
+}
+
+/// State of the search after an iteration
+pub(crate) struct Step<'p, F, P> {
+    /// Iteration
+    pub(crate) k: usize,
+    /// Temperature
+    pub(crate) t: F,
+    /// Value of the current solution
+    pub(crate) f: F,
+    /// Point of the current solution
+    pub(crate) p: &'p P,
+    /// Value of the best solution
+    pub(crate) best_f: F,
+    /// Point of the best solution
+    pub(crate) best_p: &'p P,
+    /// Number of the accepted neighbours
+    pub(crate) accepted_k: usize,
+    /// Recent acceptance rate
+    pub(crate) rate: F,
+}
+
+/// Observer of the search (the methods do nothing by default)
+pub(crate) trait Observer<F, P> {
+    /// Observe an accepted neighbour
+    fn accepted(&mut self, _p: &P) {}
+    /// Observe the state of the search after an iteration
+    fn step(&mut self, _step: &Step<F, P>) {}
+}
+
+impl<F, P> Observer<F, P> for () {}
+
+/// Run the anneal from the specified initial point, return the value and
+/// the point of the best solution, and the number of the iterations
+///
+/// The points are stored in any type which can be viewed as a slice, so
+/// the arrays of [`SA`](crate::SA) don't pay for the vectors of [`DynSA`](crate::DynSA)
+///
+/// Arguments:
+/// * `settings` --- Settings of the search;
+/// * `p_0` --- Initial point;
+/// * `f` --- Objective function (including the penalty, if any);
+/// * `neighbour` --- Function to get a random neighbour of a point;
+/// * `observer` --- Observer of the search;
+/// * `rng` --- Random number generator.
+#[replace_float_literals(F::from(literal).unwrap())]
+pub(crate) fn anneal<F, R, P>(
+    settings: &Settings<F, R>,
+    p_0: P,
+    mut f: impl FnMut(&P) -> F,
+    mut neighbour: impl FnMut(&P, &mut R) -> P,
+    observer: &mut impl Observer<F, P>,
+    rng: &mut R,
+) -> (F, P, usize)
+where
+    F: Float + SampleUniform + Debug,
+    R: Rng,
+    P: AsRef<[F]> + AsMut<[F]> + Clone,
+{
+    let Settings {
+        t_0,
+        t_min,
+        bounds,
+        apf,
+        kinds,
+        schedule,
+        reheat,
+        stop,
+    } = *settings;
+    // Snap the initial point to the allowed values
+    let mut p = p_0;
+    izip!(p.as_mut(), kinds, bounds).for_each(|(p, kind, r)| *p = kind.snap(*p, r));
+    // Evaluate the objective function at the initial point and
+    // save the initial values as the current working solution
+    let mut f_p = f(&p);
+    // Save the current working solution as the current best
+    let mut best_p = p.clone();
+    let mut best_f = f_p;
+    // Save the initial temperature as the current one
+    let mut t = t_0;
+    // Prepare the iterations counter
+    let mut k = 1;
+    // Prepare the index of the iteration in the schedule, the temperature
+    // the schedule starts from, and the counters used for reheating
+    let mut k_schedule = 1;
+    let mut t_start = t_0;
+    let mut stall = 0;
+    let mut reheats = 0;
+    // Prepare the buffer of the recent best solutions
+    let mut recent = VecDeque::new();
+    // Prepare the number of the accepted neighbours
+    // and the recent acceptance rate
+    let mut accepted_k = 0;
+    let mut rate = 0.;
+    // Prepare a Uniform[0, 1] distribution for the APF
+    let uni = Uniform::new(0., 1.);
+    // Search for the minimum of the objective function
+    while t > t_min {
+        // Get a neighbor
+        let mut neighbour_p = neighbour(&p, rng);
+        // Turn its coordinates into the allowed values
+        izip!(neighbour_p.as_mut(), kinds, bounds)
+            .for_each(|(p, kind, r)| *p = kind.propose(*p, r, rng));
+        // Evaluate the objective function
+        let neighbour_f = f(&neighbour_p);
+        // Compute the difference between the new and the current solutions
+        let diff = neighbour_f - f_p;
+        // If the new solution is accepted by the acceptance probability function,
+        let accepted = apf.accept(diff, t, &uni, rng);
+        // Update the recent acceptance rate (this is the exact rate during
+        // the first 100 iterations and a moving average afterwards)
+        let window = F::from(k.min(100)).unwrap();
+        rate = rate + (if accepted { 1. } else { 0. } - rate) / window;
+        // If the new solution is the new best,
+        if neighbour_f < best_f {
+            // Save it as the new best
+            best_p.clone_from(&neighbour_p);
+            best_f = neighbour_f;
+            stall = 0;
+        } else {
+            stall += 1;
+        }
+        if accepted {
+            accepted_k += 1;
+            // Save it as the current solution
+            p = neighbour_p;
+            f_p = neighbour_f;
+            observer.accepted(&p);
+        }
+        // Lower the temperature
+        t = schedule.cool(k_schedule, t, t_start, t_min, rate);
+        k_schedule += 1;
+        // Reheat if the search has stalled (the number of reheats
+        // is capped, so the minimum temperature is reached eventually)
+        if let Some(reheat) = reheat {
+            if stall >= reheat.stall_k && reheats < reheat.max {
+                p.clone_from(&best_p);
+                f_p = best_f;
+                t_start = reheat.factor * t_0;
+                t = t_start;
+                k_schedule = 1;
+                stall = 0;
+                reheats += 1;
+                recent.clear();
+            }
+        }
+        // Check if the search has converged (giving
+        // the remaining reheats a chance first)
+        let mut converged = false;
+        if let Some(stop) = stop {
+            recent.push_back(best_f);
+            if recent.len() > stop.window {
+                let oldest = recent.pop_front().unwrap();
+                let reheating = matches!(reheat, Some(reheat) if reheats < reheat.max);
+                converged = !reheating && oldest - best_f <= stop.tol;
+            }
+        }
+        observer.step(&Step {
+            k,
+            t,
+            f: f_p,
+            p: &p,
+            best_f,
+            best_p: &best_p,
+            accepted_k,
+            rate,
+        });
+        // Update the iterations counter
+        k += 1;
+        // Stop if the search has converged
+        if converged {
+            break;
+        }
+    }
+    (best_f, best_p, k - 1)
+}
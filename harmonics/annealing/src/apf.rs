@@ -22,6 +22,42 @@ where
     /// \end{cases}
     /// $
     Metropolis,
+    /// Cauchy (Lorentzian) criterion (heavier-tailed than the Metropolis one):
+    ///
+    /// $
+    /// P(\Delta f, t) = \begin{cases}
+    /// 1, & if \\; \Delta f \leqslant 0; \\\\
+    /// 1 / (1 + \Delta f / t), & if \\; \Delta f \gt 0
+    /// \end{cases}
+    /// $
+    Cauchy,
+    /// Barker (logistic) criterion (smoother than the Metropolis one):
+    ///
+    /// $ P(\Delta f, t) = 1 / (1 + e^{\Delta f / t}) $
+    Barker,
+    /// Tsallis criterion (generalized simulated annealing; reduces to the Metropolis
+    /// criterion when $ q \to 1 $ and to the Cauchy one when $ q = 2 $):
+    ///
+    /// $
+    /// P(\Delta f, t) = \begin{cases}
+    /// 1, & if \\; \Delta f \leqslant 0; \\\\
+    /// \[1 - (1 - q) \Delta f / t\]_+^{1 / (1 - q)}, & if \\; \Delta f \gt 0
+    /// \end{cases}
+    /// $
+    Tsallis {
+        /// Parameter $ q $
+        q: F,
+    },
+    /// Threshold accepting (deterministic, the uniform
+    /// distribution and the generator are not used):
+    ///
+    /// $
+    /// P(\Delta f, t) = \begin{cases}
+    /// 1, & if \\; \Delta f \lt t; \\\\
+    /// 0, & if \\; \Delta f \geqslant t
+    /// \end{cases}
+    /// $
+    Threshold,
     /// Custom: choose your own!
     Custom {
         /// Custom function
@@ -45,7 +81,133 @@ where
     pub fn accept(&self, diff: F, t: F, uni: &Uniform<F>, rng: &mut R) -> bool {
         match self {
             APF::Metropolis => diff <= 0. || uni.sample(rng) < F::min(F::exp(-diff / t), 1.),
+            // The probability goes to zero (but doesn't become
+            // a NaN) if the temperature is very small or zero
+            APF::Cauchy => diff <= 0. || uni.sample(rng) < 1. / (1. + diff / t),
+            APF::Barker => uni.sample(rng) < barker(diff, t),
+            APF::Tsallis { q } => diff <= 0. || uni.sample(rng) < tsallis(diff, t, *q),
+            APF::Threshold => diff < t,
             APF::Custom { f } => f(diff, t, uni, rng),
         }
     }
 }
+
+/// Compute the probability of acceptance of the Barker criterion
+///
+/// Arguments:
+/// * `diff` --- Difference in the objective;
+/// * `t` --- Temperature.
+#[replace_float_literals(F::from(literal).unwrap())]
+fn barker<F: Float>(diff: F, t: F) -> F {
+    1. / (1. + F::exp(diff / t))
+}
+
+/// Compute the probability of acceptance of an uphill move of the Tsallis criterion
+///
+/// Arguments:
+/// * `diff` --- Difference in the objective;
+/// * `t` --- Temperature;
+/// * `q` --- Parameter of the criterion.
+#[replace_float_literals(F::from(literal).unwrap())]
+fn tsallis<F: Float>(diff: F, t: F, q: F) -> F {
+    // Use the limit in the Metropolis case
+    if q == 1. {
+        return F::exp(-diff / t);
+    }
+    let base = 1. - (1. - q) * diff / t;
+    if base <= 0. {
+        0.
+    } else {
+        base.powf(1. / (1. - q))
+    }
+}
+
+#[test]
+fn test_cauchy() -> anyhow::Result<()> {
+    let apf = APF::Cauchy;
+    let uni = Uniform::new(0., 1.);
+    let mut rng = rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1);
+    // Check that the downhill moves are always accepted
+    if !(0..1000)
+        .all(|_| apf.accept(-1., 1e-300, &uni, &mut rng) && apf.accept(0., 0., &uni, &mut rng))
+    {
+        return Err(anyhow::anyhow!("A downhill move is rejected"));
+    }
+    // Check that the uphill moves are rejected at zero temperature
+    if (0..1000).any(|_| apf.accept(1e-300, 0., &uni, &mut rng)) {
+        return Err(anyhow::anyhow!(
+            "An uphill move is accepted at zero temperature"
+        ));
+    }
+    // Check the acceptance rate of the uphill moves (the
+    // Metropolis criterion would give `e^(-3) ≈ 0.05`)
+    let n = 100_000;
+    let accepted = (0..n)
+        .filter(|_| apf.accept(3., 1., &uni, &mut rng))
+        .count();
+    #[allow(clippy::cast_precision_loss)]
+    let rate = accepted as f64 / f64::from(n);
+    if (rate - 0.25).abs() >= 0.01 {
+        return Err(anyhow::anyhow!(
+            "The acceptance rate is incorrect: {} vs. {rate}",
+            0.25
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_barker() -> anyhow::Result<()> {
+    // Check the probabilities
+    if barker(0., 1.) != 0.5 {
+        return Err(anyhow::anyhow!(
+            "The probability at zero difference is incorrect: 0.5 vs. {}",
+            barker(0., 1.)
+        ));
+    }
+    if barker(-100., 1.) < 1. - 1e-12 || barker(100., 1.) > 1e-12 {
+        return Err(anyhow::anyhow!(
+            "The probabilities of the steep moves are incorrect: {} and {}",
+            barker(-100., 1.),
+            barker(100., 1.)
+        ));
+    }
+    // Check that the steep downhill moves are accepted
+    let apf = APF::Barker;
+    let uni = Uniform::new(0., 1.);
+    let mut rng = rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1);
+    if !(0..1000).all(|_| apf.accept(-1., 1e-3, &uni, &mut rng)) {
+        return Err(anyhow::anyhow!("A steep downhill move is rejected"));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_tsallis() -> anyhow::Result<()> {
+    for (diff, t) in [(0.1, 1.), (1., 1.), (3., 0.5)] {
+        // Check that the probability approaches the Metropolis one
+        let metropolis = f64::exp(-diff / t);
+        for q in [1. - 1e-6, 1., 1. + 1e-6] {
+            if (tsallis(diff, t, q) - metropolis).abs() >= 1e-5 {
+                return Err(anyhow::anyhow!(
+                    "The probability at q = {q} is not close to the Metropolis one: {metropolis} vs. {}",
+                    tsallis(diff, t, q)
+                ));
+            }
+        }
+        // Check that the probability is the Cauchy one at q = 2
+        let cauchy = 1. / (1. + diff / t);
+        if (tsallis(diff, t, 2.) - cauchy).abs() >= 1e-12 {
+            return Err(anyhow::anyhow!(
+                "The probability at q = 2 is not the Cauchy one: {cauchy} vs. {}",
+                tsallis(diff, t, 2.)
+            ));
+        }
+    }
+    // Check the cutoff when `q < 1`
+    if tsallis(3., 1., 0.5) > 0. {
+        return Err(anyhow::anyhow!("The probability is not cut off"));
+    }
+    Ok(())
+}
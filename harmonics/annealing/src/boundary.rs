@@ -0,0 +1,108 @@
+//! Provides the [`BoundaryPolicy`](crate::BoundaryPolicy) enum
+
+use num::Float;
+
+use std::ops::Range;
+
+/// Policy of handling a coordinate of a neighbour which is out of the bounds
+#[derive(Clone, Copy, Debug)]
+pub enum Policy {
+    /// Rejection: sample the coordinate again
+    Reject,
+    /// Reflection: bounce the coordinate off the bounds
+    Reflect,
+    /// Clamping: saturate the coordinate at the bounds
+    Clamp,
+    /// Wrapping: treat the coordinate as periodic, with the period
+    /// equal to the length of the range (e.g., an azimuthal angle)
+    Wrap,
+}
+
+impl Policy {
+    /// Map the coordinate into the range, return `None` if it should be
+    /// sampled again (always the case if the coordinate is not finite)
+    ///
+    /// Arguments:
+    /// * `x` --- Coordinate;
+    /// * `r` --- Range of the coordinate.
+    pub fn apply<F: Float>(self, x: F, r: &Range<F>) -> Option<F> {
+        if !x.is_finite() {
+            return None;
+        }
+        if r.contains(&x) {
+            return Some(x);
+        }
+        let width = r.end - r.start;
+        let x = match self {
+            Policy::Reject => return None,
+            Policy::Reflect => {
+                // Reflecting is wrapping with the doubled period
+                let m = modulo(x - r.start, width + width);
+                r.start + if m > width { width + width - m } else { m }
+            }
+            Policy::Clamp => x.max(r.start),
+            Policy::Wrap => r.start + modulo(x - r.start, width),
+        };
+        // The upper bound is excluded, so take the
+        // closest representable number below it
+        Some(if x >= r.end { below(r.end) } else { x })
+    }
+}
+
+/// Get the non-negative remainder of the division
+///
+/// Arguments:
+/// * `x` --- Dividend;
+/// * `y` --- Divisor (positive).
+fn modulo<F: Float>(x: F, y: F) -> F {
+    let m = x % y;
+    if m < F::zero() {
+        m + y
+    } else {
+        m
+    }
+}
+
+/// Get a number slightly below the passed one (by its relative
+/// epsilon, so it's within a couple of representable numbers)
+///
+/// Arguments:
+/// * `x` --- Number.
+pub(crate) fn below<F: Float>(x: F) -> F {
+    x - x.abs().max(F::min_positive_value()) * F::epsilon()
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test() -> anyhow::Result<()> {
+    use std::f64::consts::PI;
+
+    let r = 0.0..2. * PI;
+    for (policy, x, expected) in [
+        (Policy::Reject, 1., Some(1.)),
+        (Policy::Reject, -1., None),
+        (Policy::Reject, f64::NAN, None),
+        (Policy::Wrap, 2. * PI + 0.1, Some(0.1)),
+        (Policy::Wrap, -0.1, Some(2. * PI - 0.1)),
+        (Policy::Wrap, 6. * PI + 0.1, Some(0.1)),
+        (Policy::Wrap, f64::INFINITY, None),
+        (Policy::Reflect, 2. * PI + 0.1, Some(2. * PI - 0.1)),
+        (Policy::Reflect, -0.1, Some(0.1)),
+        (Policy::Reflect, 4. * PI + 0.1, Some(0.1)),
+        (Policy::Clamp, -0.1, Some(0.)),
+        (Policy::Clamp, 2. * PI + 0.1, Some(below(2. * PI))),
+    ] {
+        let result = policy.apply(x, &r);
+        let close = match (result, expected) {
+            (Some(a), Some(b)) => (a - b).abs() < 1e-12 && r.contains(&a),
+            (None, None) => true,
+            _ => false,
+        };
+        if !close {
+            return Err(anyhow::anyhow!(
+                "The coordinate {x} is handled incorrectly by {policy:?}: {expected:?} vs. {result:?}"
+            ));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,51 @@
+//! Provides the [`check`] function
+
+use anyhow::{anyhow, Result};
+use itertools::izip;
+use num::Float;
+
+use std::fmt::Debug;
+use std::ops::Range;
+
+/// Check if the bounds and the initial point are valid and if the point
+/// is within the bounds, return `true` if it's not, but that's allowed
+/// (so the caller is expected to put the point in the bounds)
+///
+/// Arguments:
+/// * `p_0` --- Initial point;
+/// * `bounds` --- Bounds of the parameter space;
+/// * `allow_out` --- Allow the initial point out of the bounds?
+pub(crate) fn check<F: Float + Debug>(
+    p_0: &[F],
+    bounds: &[Range<F>],
+    allow_out: bool,
+) -> Result<bool> {
+    // Check if the ranges are not empty
+    if let Some((i, r)) = bounds.iter().enumerate().find(|(_, r)| r.is_empty()) {
+        return Err(anyhow!(
+            "The range of the coordinate #{} is empty or reversed: {:?}",
+            i,
+            r
+        ));
+    }
+    // Check if the coordinates of the initial point are finite
+    if let Some((i, p)) = p_0.iter().enumerate().find(|(_, p)| !p.is_finite()) {
+        return Err(anyhow!(
+            "The coordinate #{} of the initial point is not finite: {:?}",
+            i,
+            p
+        ));
+    }
+    // Check if the initial point is within the bounds
+    if izip!(p_0, bounds).all(|(p, r)| r.contains(p)) {
+        return Ok(false);
+    }
+    if !allow_out {
+        return Err(anyhow!(
+            "The initial point {:?} is out of the bounds {:?}",
+            p_0,
+            bounds
+        ));
+    }
+    Ok(true)
+}
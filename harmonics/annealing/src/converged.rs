@@ -0,0 +1,14 @@
+//! Provides the [`Converged`](crate::Converged) struct
+
+use num::Float;
+
+/// Convergence criterion: the search stops early if the best solution
+/// hasn't been improved by more than `tol` over the last `window` iterations
+/// (if reheating is enabled, only after all reheats are used up)
+#[derive(Clone, Copy, Debug)]
+pub struct Converged<F: Float> {
+    /// Tolerance of the improvement
+    pub tol: F,
+    /// Number of iterations
+    pub window: usize,
+}
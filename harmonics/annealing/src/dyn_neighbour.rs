@@ -0,0 +1,95 @@
+//! Provides the [`DynNeighbourMethod`](crate::DynNeighbourMethod) enum
+
+use itertools::izip;
+use num::Float;
+use rand::prelude::*;
+use rand_distr::{uniform::SampleUniform, StandardNormal};
+
+use std::fmt::Debug;
+use std::ops::Range;
+
+use crate::neighbour::{cauchy, normal, uniform_box};
+use crate::BoundaryPolicy;
+
+/// Method of getting a random neighbour in the
+/// parameter space of the runtime dimension
+///
+/// The methods are the same as the ones of [`NeighbourMethod`](crate::NeighbourMethod)
+pub enum Method<F, R>
+where
+    F: Float,
+    StandardNormal: Distribution<F>,
+    R: Rng,
+{
+    /// Get a neighbour in the vicinity of the current point
+    /// by sampling a random normal distribution with the mean
+    /// in that point and with the provided standard deviation
+    Normal {
+        /// Standard deviation
+        sd: F,
+    },
+    /// Get a neighbour in the vicinity of the current point
+    /// by sampling a Cauchy distribution with the median in
+    /// that point and with the provided scale
+    Cauchy {
+        /// Scale
+        scale: F,
+    },
+    /// Get a neighbour by sampling each coordinate uniformly within
+    /// the box with the provided half-widths around the current point
+    UniformBox {
+        /// Half-widths of the box
+        width: Vec<F>,
+    },
+    /// Custom: choose your own!
+    Custom {
+        /// Custom function
+        #[allow(clippy::type_complexity)]
+        f: fn(p: &[F], bounds: &[Range<F>], rng: &mut R) -> Vec<F>,
+    },
+}
+
+impl<F, R> Method<F, R>
+where
+    F: Float + SampleUniform + Debug,
+    StandardNormal: Distribution<F>,
+    R: Rng,
+{
+    /// Get a neighbour of the current point
+    ///
+    /// Arguments:
+    /// * `p` --- Current point;
+    /// * `bounds` --- Bounds of the parameter space;
+    /// * `boundary` --- Policies of handling the coordinates out of the bounds
+    ///   (a custom method is expected to handle them by itself);
+    /// * `rng` --- Random number generator.
+    pub fn neighbour(
+        &self,
+        p: &[F],
+        bounds: &[Range<F>],
+        boundary: &[BoundaryPolicy],
+        rng: &mut R,
+    ) -> Vec<F> {
+        match self {
+            Method::Normal { sd } => izip!(p, bounds, boundary)
+                .map(|(&p, r, &policy)| normal(p, *sd, r, policy, rng))
+                .collect(),
+            Method::Cauchy { scale } => izip!(p, bounds, boundary)
+                .map(|(&p, r, &policy)| cauchy(p, *scale, r, policy, rng))
+                .collect(),
+            Method::UniformBox { width } => izip!(p, width, bounds, boundary)
+                .map(|(&p, &w, r, &policy)| uniform_box(p, w, r, policy, rng))
+                .collect(),
+            Method::Custom { f } => f(p, bounds, rng),
+        }
+    }
+
+    /// Get the number of coordinates the method is parametrized
+    /// for (if it is, i.e. for the box of the half-widths)
+    pub(crate) fn dim(&self) -> Option<usize> {
+        match self {
+            Method::UniformBox { width } => Some(width.len()),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,207 @@
+//! Provides the [`DynSA`](crate::DynSA) struct and the
+//! [`findmin`](crate::DynSA#method.findmin) method
+
+use anyhow::{anyhow, Result};
+use num::Float;
+use rand::prelude::*;
+use rand_distr::{uniform::SampleUniform, Distribution, StandardNormal};
+
+use std::fmt::Debug;
+use std::ops::Range;
+
+use crate::anneal::{anneal, Settings};
+use crate::check::check;
+use crate::{
+    BoundaryPolicy, Converged, DynNeighbourMethod, DynPenalty, Reheat, Schedule, VarKind, APF,
+};
+
+/// Simulated annealing in the parameter space of the runtime dimension
+///
+/// This is a counterpart of [`SA`](crate::SA) for the cases when the number of
+/// the coordinates is unknown at compile time: the points are [`Vec`]'s and
+/// the bounds are slices, so the lengths are checked before the search
+///
+/// Both share the same search loop, but there is no status
+/// function and no history here, since those store arrays
+pub struct DynSA<'a, F, R, FN>
+where
+    F: Float + SampleUniform + Debug,
+    StandardNormal: Distribution<F>,
+    R: Rng,
+    FN: FnMut(&[F]) -> F,
+{
+    /// Objective function
+    pub f: FN,
+    /// Penalty added to the objective function (if any; then the neighbours
+    /// are allowed out of the bounds, so the constraints become soft)
+    pub penalty: Option<DynPenalty<F>>,
+    /// Initial point
+    pub p_0: &'a [F],
+    /// Initial temperature
+    pub t_0: F,
+    /// Minimum temperature
+    pub t_min: F,
+    /// Bounds of the parameter space
+    pub bounds: &'a [Range<F>],
+    /// Acceptance probability function
+    pub apf: &'a APF<F, R>,
+    /// Method of getting a random neighbour
+    pub neighbour: &'a DynNeighbourMethod<F, R>,
+    /// Policies of handling the coordinates of a neighbour out of the bounds
+    pub boundary: &'a [BoundaryPolicy],
+    /// Kinds of the coordinates (the initial points and the
+    /// neighbours are snapped to the allowed values)
+    pub kinds: &'a [VarKind<F>],
+    /// Annealing schedule
+    pub schedule: &'a Schedule<F>,
+    /// Reheating policy (if any)
+    pub reheat: Option<Reheat<F>>,
+    /// Convergence criterion (if any)
+    pub stop: Option<Converged<F>>,
+    /// Random number generator
+    pub rng: &'a mut R,
+}
+
+impl<F, R, FN> DynSA<'_, F, R, FN>
+where
+    F: Float + SampleUniform + Debug,
+    StandardNormal: Distribution<F>,
+    R: Rng,
+    FN: FnMut(&[F]) -> F,
+{
+    /// Find the global minimum (and the corresponding point) of the objective function
+    pub fn findmin(&mut self) -> Result<(F, Vec<F>)> {
+        self.check()?;
+        let settings = Settings {
+            t_0: self.t_0,
+            t_min: self.t_min,
+            bounds: self.bounds,
+            apf: self.apf,
+            kinds: self.kinds,
+            schedule: self.schedule,
+            reheat: self.reheat,
+            stop: self.stop,
+        };
+        // Allow the neighbours out of the bounds if the constraints are soft
+        let unbounded = vec![F::neg_infinity()..F::infinity(); self.p_0.len()];
+        let bounds = if self.penalty.is_some() {
+            &unbounded
+        } else {
+            self.bounds
+        };
+        let (best_f, best_p, _) = anneal(
+            &settings,
+            self.p_0.to_vec(),
+            |p: &Vec<F>| (self.f)(p) + self.penalty.map_or(F::zero(), |g| g(p, self.bounds)),
+            |p, rng| self.neighbour.neighbour(p, bounds, self.boundary, rng),
+            &mut (),
+            &mut *self.rng,
+        );
+        Ok((best_f, best_p))
+    }
+
+    /// Check if the lengths of the slices match, if the bounds and
+    /// the initial point are valid and if the point is within the bounds
+    fn check(&self) -> Result<()> {
+        // Check if the lengths match the dimension of the initial point
+        let n = self.p_0.len();
+        for (name, len) in [
+            ("bounds", self.bounds.len()),
+            ("boundary policies", self.boundary.len()),
+            ("kinds", self.kinds.len()),
+        ]
+        .into_iter()
+        .chain(
+            self.neighbour
+                .dim()
+                .map(|len| ("half-widths of the box", len)),
+        ) {
+            if len != n {
+                return Err(anyhow!(
+                    "The number of {} doesn't match the dimension of the initial point: {} vs. {}",
+                    name,
+                    len,
+                    n
+                ));
+            }
+        }
+        // Check the bounds and the initial point
+        check(self.p_0, self.bounds, false)?;
+        Ok(())
+    }
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test() -> Result<()> {
+    use crate::{NeighbourMethod, Point, Status, SA};
+    // Find the minimum of the same function in the array and the runtime
+    // dimension (the generators are seeded equally, and both searches
+    // may reheat and stop early, since they share the same loop)
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn f(p: &Point<f64, 2>) -> f64 {
+        let (x, y) = (p[0], p[1]);
+        f64::ln(x) * (f64::sin(x) + f64::cos(x)) + (y - 3.).powi(2)
+    }
+    let reheat = Some(Reheat {
+        stall_k: 500,
+        factor: 0.5,
+        max: 2,
+    });
+    let stop = Some(Converged {
+        tol: 1e-9,
+        window: 2000,
+    });
+    let (m, p) = SA {
+        f,
+        penalty: None,
+        p_0: &[2., 0.],
+        t_0: 100_000.0,
+        t_min: 1.0,
+        bounds: &[1.0..27.8, -5.0..5.0],
+        apf: &APF::Metropolis,
+        neighbour: &NeighbourMethod::Normal { sd: 5. },
+        boundary: &[BoundaryPolicy::Reject, BoundaryPolicy::Reflect],
+        kinds: &[VarKind::Continuous; 2],
+        schedule: &Schedule::Fast,
+        reheat,
+        stop,
+        status: &mut Status::None,
+        rng: &mut rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1),
+        clamp_start: false,
+    }
+    .findmin()?;
+    let mut dyn_sa = DynSA {
+        f: |p: &[f64]| f(&[p[0], p[1]]),
+        penalty: None,
+        p_0: &[2., 0.],
+        t_0: 100_000.0,
+        t_min: 1.0,
+        bounds: &[1.0..27.8, -5.0..5.0],
+        apf: &APF::Metropolis,
+        neighbour: &DynNeighbourMethod::Normal { sd: 5. },
+        boundary: &[BoundaryPolicy::Reject, BoundaryPolicy::Reflect],
+        kinds: &[VarKind::Continuous; 2],
+        schedule: &Schedule::Fast,
+        reheat,
+        stop,
+        rng: &mut rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1),
+    };
+    let (dyn_m, dyn_p) = dyn_sa.findmin()?;
+    // Check that the results are the same
+    if dyn_m != m || dyn_p != p {
+        return Err(anyhow!(
+            "The results are different: {} at {:?} vs. {} at {:?}",
+            m,
+            p,
+            dyn_m,
+            dyn_p
+        ));
+    }
+    // Check that the mismatched lengths are reported
+    dyn_sa.kinds = &[VarKind::Continuous];
+    if dyn_sa.findmin().is_ok() {
+        return Err(anyhow!("The mismatched lengths are not reported"));
+    }
+    Ok(())
+}
@@ -0,0 +1,77 @@
+//! Provides the [`History`](crate::History) struct
+
+use num::Float;
+
+use crate::Point;
+
+/// History of the search (the samples are recorded at the first
+/// iteration and at every iteration divisible by the chosen number)
+#[derive(Clone, Debug)]
+pub struct History<F: Float, const N: usize> {
+    /// Iterations
+    pub k: Vec<usize>,
+    /// Temperatures
+    pub t: Vec<F>,
+    /// Current solutions
+    pub f: Vec<F>,
+    /// Current points
+    pub p: Vec<Point<F, N>>,
+    /// Current best solutions
+    pub best_f: Vec<F>,
+    /// Current points of the best solutions
+    pub best_p: Vec<Point<F, N>>,
+    /// Numbers of the accepted neighbours (the
+    /// numbers of the proposed ones are the iterations)
+    pub accepted: Vec<usize>,
+    /// Recent acceptance rates
+    pub rate: Vec<F>,
+}
+
+impl<F: Float, const N: usize> History<F, N> {
+    /// Create an empty history
+    pub(crate) fn new() -> Self {
+        Self {
+            k: Vec::new(),
+            t: Vec::new(),
+            f: Vec::new(),
+            p: Vec::new(),
+            best_f: Vec::new(),
+            best_p: Vec::new(),
+            accepted: Vec::new(),
+            rate: Vec::new(),
+        }
+    }
+
+    /// Record a sample
+    ///
+    /// Arguments:
+    /// * `k` --- Current iteration;
+    /// * `t` --- Current temperature;
+    /// * `f` --- Current solution;
+    /// * `p` --- Current point;
+    /// * `best_f` --- Current best solution;
+    /// * `best_p` --- Current point of the best solution;
+    /// * `accepted` --- Number of the accepted neighbours;
+    /// * `rate` --- Recent acceptance rate.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn record(
+        &mut self,
+        k: usize,
+        t: F,
+        f: F,
+        p: Point<F, N>,
+        best_f: F,
+        best_p: Point<F, N>,
+        accepted: usize,
+        rate: F,
+    ) {
+        self.k.push(k);
+        self.t.push(t);
+        self.f.push(f);
+        self.p.push(p);
+        self.best_f.push(best_f);
+        self.best_p.push(best_p);
+        self.accepted.push(accepted);
+        self.rate.push(rate);
+    }
+}
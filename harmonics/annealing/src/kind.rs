@@ -0,0 +1,103 @@
+//! Provides the [`VarKind`](crate::VarKind) enum
+
+use num::Float;
+use rand::prelude::*;
+
+use std::ops::Range;
+
+/// Kind of a coordinate of the parameter space
+#[derive(Clone, Copy, Debug)]
+pub enum Kind<F: Float> {
+    /// Continuous: any value within the range
+    Continuous,
+    /// Integer: a value on the grid with the provided step starting from the
+    /// lower bound (a range narrower than the step pins the coordinate to it)
+    Integer {
+        /// Step of the grid
+        step: F,
+    },
+    /// Categorical: an index of a level (`0, 1, ..., levels - 1`; the range
+    /// is ignored), a new one is chosen uniformly regardless of the distance
+    Categorical {
+        /// Number of levels
+        levels: usize,
+    },
+}
+
+impl<F: Float> Kind<F> {
+    /// Snap the coordinate to the closest allowed value
+    ///
+    /// Arguments:
+    /// * `x` --- Coordinate;
+    /// * `r` --- Range of the coordinate.
+    pub(crate) fn snap(&self, x: F, r: &Range<F>) -> F {
+        match self {
+            Kind::Continuous => x,
+            Kind::Integer { step } => {
+                // Find the index of the last node of the grid within
+                // the range (the upper bound is excluded)
+                let j_max = (((r.end - r.start) / *step).ceil() - F::one()).max(F::zero());
+                let j = ((x - r.start) / *step).round().max(F::zero()).min(j_max);
+                r.start + j * *step
+            }
+            Kind::Categorical { levels } => {
+                let j_max = F::from(levels.saturating_sub(1)).unwrap();
+                x.round().max(F::zero()).min(j_max)
+            }
+        }
+    }
+
+    /// Turn the proposed coordinate into an allowed one
+    ///
+    /// Arguments:
+    /// * `x` --- Proposed coordinate;
+    /// * `r` --- Range of the coordinate;
+    /// * `rng` --- Random number generator.
+    pub(crate) fn propose<R: Rng>(&self, x: F, r: &Range<F>, rng: &mut R) -> F {
+        match self {
+            Kind::Categorical { levels } => F::from(rng.gen_range(0..*levels)).unwrap(),
+            _ => self.snap(x, r),
+        }
+    }
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test() -> anyhow::Result<()> {
+    // Check the snapping of the integer coordinates
+    let integer = Kind::Integer { step: 2. };
+    for (x, r, expected) in [
+        (3.2, 0.0..10.0, 4.),
+        (-1., 0.0..10.0, 0.),
+        (9.9, 0.0..10.0, 8.),
+        (11., 1.0..11.0, 9.),
+        (5., 0.0..1.5, 0.),
+    ] {
+        let snapped = integer.snap(x, &r);
+        if snapped != expected {
+            return Err(anyhow::anyhow!(
+                "The coordinate {x} is snapped incorrectly in {r:?}: {expected} vs. {snapped}"
+            ));
+        }
+    }
+    // Check the proposals of the categorical coordinates
+    let categorical = Kind::Categorical { levels: 3 };
+    let mut rng = rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1);
+    let mut counts = [0; 3];
+    for _ in 0..300 {
+        let x: f64 = categorical.propose(0., &(0.0..1.0), &mut rng);
+        if x.fract() != 0. || !(0. ..3.).contains(&x) {
+            return Err(anyhow::anyhow!("The level {x} is out of range"));
+        }
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        {
+            counts[x as usize] += 1;
+        }
+    }
+    if counts.contains(&0) {
+        return Err(anyhow::anyhow!(
+            "Some levels are never proposed: {counts:?}"
+        ));
+    }
+    Ok(())
+}
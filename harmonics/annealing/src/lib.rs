@@ -11,23 +11,57 @@
 //! - Mykel J. Kochenderfer, Tim A. Wheeler, 2019, “[Algorithms for Optimization](https://www.amazon.com/dp/0262039427)”
 //! - Jonathan Woollett-Light, [`simple_optimization`](https://docs.rs/simple_optimization) crate
 
+#[doc(hidden)]
+mod anneal;
 #[doc(hidden)]
 mod apf;
 #[doc(hidden)]
+mod boundary;
+#[doc(hidden)]
+mod check;
+#[doc(hidden)]
+mod converged;
+#[doc(hidden)]
+mod dyn_neighbour;
+#[doc(hidden)]
+mod dyn_sa;
+#[doc(hidden)]
+mod history;
+#[doc(hidden)]
+mod kind;
+#[doc(hidden)]
 mod neighbour;
 #[doc(hidden)]
+mod qa;
+#[doc(hidden)]
+mod reheat;
+#[doc(hidden)]
+mod restart;
+#[doc(hidden)]
 mod sa;
 #[doc(hidden)]
 mod schedule;
 #[doc(hidden)]
+mod solution;
+#[doc(hidden)]
 mod status;
 
 use std::ops::Range;
 
 pub use apf::APF;
+pub use boundary::Policy as BoundaryPolicy;
+pub use converged::Converged;
+pub use dyn_neighbour::Method as DynNeighbourMethod;
+pub use dyn_sa::DynSA;
+pub use history::History;
+pub use kind::Kind as VarKind;
 pub use neighbour::Method as NeighbourMethod;
+pub use qa::QA;
+pub use reheat::Reheat;
+pub use restart::Policy as RestartPolicy;
 pub use sa::SA;
 pub use schedule::Schedule;
+pub use solution::Solution;
 pub use status::{Custom as CustomStatus, Status};
 
 /// Point in the parameter space
@@ -35,3 +69,9 @@ pub type Point<F, const N: usize> = [F; N];
 
 /// Bounds of the parameter space
 pub type Bounds<F, const N: usize> = [Range<F>; N];
+
+/// Penalty for leaving the bounds of the parameter space
+pub type Penalty<F, const N: usize> = fn(&Point<F, N>, &Bounds<F, N>) -> F;
+
+/// Penalty for leaving the bounds of the parameter space of the runtime dimension
+pub type DynPenalty<F> = fn(&[F], &[Range<F>]) -> F;
@@ -3,11 +3,12 @@
 use itertools::izip;
 use num::Float;
 use rand::prelude::*;
-use rand_distr::{Normal, StandardNormal};
+use rand_distr::{uniform::SampleUniform, Normal, StandardNormal, Uniform};
 
 use std::fmt::Debug;
+use std::ops::Range;
 
-use crate::{Bounds, Point};
+use crate::{BoundaryPolicy, Bounds, Point};
 
 /// Method of getting a random neighbour
 pub enum Method<F, R, const N: usize>
@@ -23,6 +24,22 @@ where
         /// Standard deviation
         sd: F,
     },
+    /// Get a neighbour in the vicinity of the current point
+    /// by sampling a Cauchy distribution with the median in that
+    /// point and with the provided scale (the heavy tails make
+    /// large jumps more likely than in the normal case)
+    Cauchy {
+        /// Scale
+        scale: F,
+    },
+    /// Get a neighbour by sampling each coordinate uniformly within the box
+    /// with the provided half-widths around the current point (clipped to
+    /// the bounds unless the coordinate is wrapped; the half-widths must
+    /// be positive)
+    UniformBox {
+        /// Half-widths of the box
+        width: Point<F, N>,
+    },
     /// Custom: choose your own!
     Custom {
         /// Custom function
@@ -32,7 +49,7 @@ where
 
 impl<F, R, const N: usize> Method<F, R, N>
 where
-    F: Float + Debug,
+    F: Float + SampleUniform + Debug,
     StandardNormal: Distribution<F>,
     R: Rng,
 {
@@ -41,28 +58,158 @@ where
     /// Arguments:
     /// * `p` --- Current point;
     /// * `bounds` --- Bounds of the parameter space;
+    /// * `boundary` --- Policies of handling the coordinates out of the bounds
+    ///   (a custom method is expected to handle them by itself);
     /// * `distribution` --- Distribution to sample from;
     /// * `rng` --- Random number generator.
-    pub fn neighbour(&self, p: &Point<F, N>, bounds: &Bounds<F, N>, rng: &mut R) -> Point<F, N> {
+    pub fn neighbour(
+        &self,
+        p: &Point<F, N>,
+        bounds: &Bounds<F, N>,
+        boundary: &[BoundaryPolicy; N],
+        rng: &mut R,
+    ) -> Point<F, N> {
         match self {
             Method::Normal { sd } => {
                 let mut new_p = [F::zero(); N];
                 // Generate a new point
-                izip!(&mut new_p, p, bounds).for_each(|(np, &p, r)| {
-                    // Create a normal distribution around the current coordinate
-                    let d = Normal::new(p, *sd).unwrap();
-                    // Sample from this distribution
-                    let mut p = d.sample(rng);
-                    // If the result is not in the range, repeat until it is
-                    while !r.contains(&p) {
-                        p = d.sample(rng);
-                    }
-                    // Save the new coordinate
-                    *np = F::from(p).unwrap();
+                izip!(&mut new_p, p, bounds, boundary).for_each(|(np, &p, r, &policy)| {
+                    *np = normal(p, *sd, r, policy, rng);
                 });
                 new_p
             }
+            Method::Cauchy { scale } => {
+                let mut new_p = [F::zero(); N];
+                // Generate a new point
+                izip!(&mut new_p, p, bounds, boundary).for_each(|(np, &p, r, &policy)| {
+                    *np = cauchy(p, *scale, r, policy, rng);
+                });
+                new_p
+            }
+            Method::UniformBox { width } => {
+                let mut new_p = [F::zero(); N];
+                // Generate a new point
+                izip!(&mut new_p, p, width, bounds, boundary).for_each(
+                    |(np, &p, &w, r, &policy)| {
+                        *np = uniform_box(p, w, r, policy, rng);
+                    },
+                );
+                new_p
+            }
             Method::Custom { f } => f(p, bounds, rng),
         }
     }
 }
+
+/// Sample a coordinate of a neighbour from the normal distribution around the current one
+///
+/// Arguments:
+/// * `p` --- Current coordinate;
+/// * `sd` --- Standard deviation;
+/// * `r` --- Range of the coordinate;
+/// * `policy` --- Policy of handling the coordinate out of the range;
+/// * `rng` --- Random number generator.
+pub(crate) fn normal<F, R>(p: F, sd: F, r: &Range<F>, policy: BoundaryPolicy, rng: &mut R) -> F
+where
+    F: Float,
+    StandardNormal: Distribution<F>,
+    R: Rng,
+{
+    // Create a normal distribution around the current coordinate
+    let d = Normal::new(p, sd).unwrap();
+    // Sample from this distribution until the
+    // result is in the range or mapped into it
+    resample(r, policy, || d.sample(rng))
+}
+
+/// Sample a coordinate of a neighbour from the Cauchy distribution around the current one
+///
+/// Arguments:
+/// * `p` --- Current coordinate;
+/// * `scale` --- Scale parameter;
+/// * `r` --- Range of the coordinate;
+/// * `policy` --- Policy of handling the coordinate out of the range;
+/// * `rng` --- Random number generator.
+pub(crate) fn cauchy<F, R>(p: F, scale: F, r: &Range<F>, policy: BoundaryPolicy, rng: &mut R) -> F
+where
+    F: Float,
+    StandardNormal: Distribution<F>,
+    R: Rng,
+{
+    // Sample the distribution as the ratio of two independent standard
+    // normal variables (so no more bounds are required) until the
+    // result is in the range or mapped into it
+    resample(r, policy, || {
+        let (z_1, z_2): (F, F) = (StandardNormal.sample(rng), StandardNormal.sample(rng));
+        p + scale * z_1 / z_2
+    })
+}
+
+/// Sample a coordinate of a neighbour uniformly from the box around the current one
+///
+/// Arguments:
+/// * `p` --- Current coordinate;
+/// * `w` --- Half-width of the box;
+/// * `r` --- Range of the coordinate;
+/// * `policy` --- Policy of handling the coordinate out of the range;
+/// * `rng` --- Random number generator.
+pub(crate) fn uniform_box<F, R>(p: F, w: F, r: &Range<F>, policy: BoundaryPolicy, rng: &mut R) -> F
+where
+    F: Float + SampleUniform + Debug,
+    R: Rng,
+{
+    // Sample from the whole interval if the coordinate is wrapped
+    if let BoundaryPolicy::Wrap = policy {
+        return resample(r, policy, || Uniform::new(p - w, p + w).sample(rng));
+    }
+    // Otherwise, clip the interval to the range
+    let start = F::max(p - w, r.start);
+    let end = F::min(p + w, r.end);
+    assert!(
+        start < end,
+        "The interval [{:?}, {:?}) is empty after clipping it to {:?}",
+        p - w,
+        p + w,
+        r
+    );
+    // Sample from the interval
+    Uniform::new(start, end).sample(rng)
+}
+
+/// Sample the coordinate until the policy maps it into the range
+///
+/// Arguments:
+/// * `r` --- Range of the coordinate;
+/// * `policy` --- Policy of handling the coordinate out of the range;
+/// * `sample` --- Function to sample the coordinate.
+fn resample<F: Float>(r: &Range<F>, policy: BoundaryPolicy, mut sample: impl FnMut() -> F) -> F {
+    loop {
+        if let Some(x) = policy.apply(sample(), r) {
+            return x;
+        }
+    }
+}
+
+#[test]
+fn test_uniform_box() -> anyhow::Result<()> {
+    let width = [0.1, 1.];
+    let method = Method::<f64, rand_xoshiro::Xoshiro256PlusPlus, 2>::UniformBox { width };
+    let bounds = [0.0..std::f64::consts::PI, 0.0..2. * std::f64::consts::PI];
+    let mut rng = rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1);
+    // Check that the neighbours are in the clipped boxes
+    for p in [[0., 0.], [1.5, 3.], [3.1, 6.]] {
+        for _ in 0..1000 {
+            let new_p = method.neighbour(&p, &bounds, &[BoundaryPolicy::Reject; 2], &mut rng);
+            if !izip!(&new_p, &p, &width, &bounds)
+                .all(|(np, p, w, r)| (np - p).abs() <= *w && r.contains(np))
+            {
+                return Err(anyhow::anyhow!(
+                    "The neighbour {:?} of {:?} is out of the box",
+                    new_p,
+                    p
+                ));
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,234 @@
+//! Provides the [`QA`](crate::QA) struct and the
+//! [`findmin`](crate::QA#method.findmin) method
+
+use anyhow::{anyhow, Result};
+use itertools::izip;
+use num::Float;
+use numeric_literals::replace_float_literals;
+use rand::prelude::*;
+use rand_distr::{uniform::SampleUniform, Distribution, StandardNormal, Uniform};
+
+use std::fmt::Debug;
+
+use crate::check::check;
+use crate::{BoundaryPolicy, Bounds, NeighbourMethod, Point, Schedule, APF};
+
+/// Simulated quantum annealing (path-integral Monte Carlo)
+///
+/// The point is represented by $ P $ Trotter replicas $ p^{(1)}, \dots, p^{(P)} $
+/// evolving at a fixed temperature $ t $ and coupled in a ring by springs
+/// (for $ P = 1 $, there are no springs and it's a classical random walk):
+///
+/// $ E = \sum_{j=1}^P \left\[ f(p^{(j)}) / P + J \\, \lVert p^{(j)} - p^{(j+1)} \rVert^2 \right\], \\; p^{(P+1)} = p^{(1)} $,
+///
+/// where the stiffness of the springs is derived from the transverse field $ \Gamma $:
+///
+/// $ J = \frac{P t}{2} \ln \coth \frac{\Gamma}{P t} $
+///
+/// The field is lowered by the schedule, so the replicas, free to
+/// tunnel through the barriers at first, get pulled together eventually
+///
+/// References:
+/// - Roman Martoňák, Giuseppe E. Santoro, Erio Tosatti, 2002, “[Quantum annealing by the path-integral Monte Carlo method: The two-dimensional random Ising model](https://doi.org/10.1103/PhysRevB.66.094203)”
+pub struct QA<'a, F, R, FN, const N: usize>
+where
+    F: Float + SampleUniform + Debug,
+    StandardNormal: Distribution<F>,
+    R: Rng,
+    FN: FnMut(&Point<F, N>) -> F,
+{
+    /// Objective function
+    pub f: FN,
+    /// Initial point (of all replicas)
+    pub p_0: &'a Point<F, N>,
+    /// Number of the Trotter replicas $ P $
+    pub replicas: usize,
+    /// Temperature (fixed)
+    pub t: F,
+    /// Initial transverse field
+    pub gamma_0: F,
+    /// Minimum transverse field
+    pub gamma_min: F,
+    /// Bounds of the parameter space
+    pub bounds: &'a Bounds<F, N>,
+    /// Acceptance probability function (applied to the
+    /// difference in the energy of the replicas)
+    pub apf: &'a APF<F, R>,
+    /// Method of getting a random neighbour (of a replica)
+    pub neighbour: &'a NeighbourMethod<F, R, N>,
+    /// Policies of handling the coordinates of a neighbour out of the bounds
+    pub boundary: &'a [BoundaryPolicy; N],
+    /// Schedule of lowering the transverse field
+    pub schedule: &'a Schedule<F>,
+    /// Random number generator
+    pub rng: &'a mut R,
+}
+
+impl<F, R, FN, const N: usize> QA<'_, F, R, FN, N>
+where
+    F: Float + SampleUniform + Debug,
+    StandardNormal: Distribution<F>,
+    R: Rng,
+    FN: FnMut(&Point<F, N>) -> F,
+{
+    /// Find the global minimum (and the corresponding point) of the
+    /// objective function among all points visited by the replicas
+    #[replace_float_literals(F::from(literal).unwrap())]
+    pub fn findmin(&mut self) -> Result<(F, Point<F, N>)> {
+        if self.replicas == 0 {
+            return Err(anyhow!("The number of replicas must be positive"));
+        }
+        check(self.p_0, self.bounds, false)?;
+        let n = self.replicas;
+        let n_f = F::from(n).unwrap();
+        // Start all replicas from the initial point
+        let mut ps = vec![*self.p_0; n];
+        let mut fs = vec![(self.f)(self.p_0); n];
+        // Save the initial point as the current best
+        let mut best_p = *self.p_0;
+        let mut best_f = fs[0];
+        // Save the initial transverse field as the current one
+        let mut gamma = self.gamma_0;
+        // Prepare the sweeps counter
+        let mut k = 1;
+        // Prepare a Uniform[0, 1] distribution for the APF
+        let uni = Uniform::new(0., 1.);
+        // Search for the minimum of the objective function
+        while gamma > self.gamma_min {
+            let j_perp = coupling(gamma, self.t, n);
+            let mut accepted = 0;
+            // Sweep through the replicas
+            for i in 0..n {
+                // Get a neighbour of the replica
+                let neighbour_p =
+                    self.neighbour
+                        .neighbour(&ps[i], self.bounds, self.boundary, self.rng);
+                // Evaluate the objective function
+                let neighbour_f = (self.f)(&neighbour_p);
+                // Compute the difference in the energy (only the
+                // springs to the adjacent replicas are changed)
+                let mut diff = (neighbour_f - fs[i]) / n_f;
+                if n > 1 {
+                    let (prev, next) = (&ps[(i + n - 1) % n], &ps[(i + 1) % n]);
+                    diff = diff
+                        + j_perp
+                            * (distance(&neighbour_p, prev) + distance(&neighbour_p, next)
+                                - distance(&ps[i], prev)
+                                - distance(&ps[i], next));
+                }
+                // If the move is accepted by the acceptance probability function,
+                if self.apf.accept(diff, self.t, &uni, self.rng) {
+                    // Save it as the current state of the replica
+                    ps[i] = neighbour_p;
+                    fs[i] = neighbour_f;
+                    accepted += 1;
+                }
+                // If the new solution is the new best, save it as the new best
+                if neighbour_f < best_f {
+                    best_p = neighbour_p;
+                    best_f = neighbour_f;
+                }
+            }
+            // Lower the transverse field (the acceptance
+            // rate of the sweep is passed to the schedule)
+            let rate = F::from(accepted).unwrap() / n_f;
+            gamma = self
+                .schedule
+                .cool(k, gamma, self.gamma_0, self.gamma_min, rate);
+            // Update the sweeps counter
+            k += 1;
+        }
+        Ok((best_f, best_p))
+    }
+}
+
+/// Compute the stiffness of the springs between the replicas
+///
+/// Arguments:
+/// * `gamma` --- Transverse field;
+/// * `t` --- Temperature;
+/// * `n` --- Number of the replicas.
+#[replace_float_literals(F::from(literal).unwrap())]
+fn coupling<F: Float>(gamma: F, t: F, n: usize) -> F {
+    let pt = F::from(n).unwrap() * t;
+    pt / 2. * F::ln(F::tanh(gamma / pt).recip())
+}
+
+/// Compute the squared Euclidean distance between the points
+///
+/// Arguments:
+/// * `a` --- First point;
+/// * `b` --- Second point.
+fn distance<F: Float, const N: usize>(a: &Point<F, N>, b: &Point<F, N>) -> F {
+    izip!(a, b).fold(F::zero(), |acc, (&a, &b)| acc + (a - b).powi(2))
+}
+
+#[test]
+fn test() -> Result<()> {
+    // Define the objective function
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn f(p: &Point<f64, 1>) -> f64 {
+        let x = p[0];
+        f64::ln(x) * (f64::sin(x) + f64::cos(x))
+    }
+    // Check that the springs get stiffer as the field weakens
+    if coupling(1e-3, 1., 8) <= coupling(1., 1., 8) {
+        return Err(anyhow!(
+            "The springs don't get stiffer as the field weakens"
+        ));
+    }
+    // Get the minimum
+    let (m, p) = QA {
+        f,
+        p_0: &[2.],
+        replicas: 8,
+        t: 0.05,
+        gamma_0: 3.,
+        gamma_min: 1e-3,
+        bounds: &[1.0..27.8],
+        apf: &APF::Metropolis,
+        neighbour: &NeighbourMethod::Normal { sd: 5. },
+        boundary: &[BoundaryPolicy::Reject],
+        schedule: &Schedule::Linear { k_max: 2000 },
+        rng: &mut rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1),
+    }
+    .findmin()?;
+    // Check that the zero number of replicas is rejected
+    let rejected = QA {
+        f,
+        p_0: &[2.],
+        replicas: 0,
+        t: 0.05,
+        gamma_0: 3.,
+        gamma_min: 1e-3,
+        bounds: &[1.0..27.8],
+        apf: &APF::Metropolis,
+        neighbour: &NeighbourMethod::Normal { sd: 5. },
+        boundary: &[BoundaryPolicy::Reject],
+        schedule: &Schedule::Linear { k_max: 2000 },
+        rng: &mut rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1),
+    }
+    .findmin()
+    .is_err();
+    if !rejected {
+        return Err(anyhow!("The zero number of replicas is not rejected"));
+    }
+    // Compare the result with the actual minimum
+    let actual_p = [22.790_580_66];
+    let actual_m = f(&actual_p);
+    if (p[0] - actual_p[0]).abs() >= 1e-2 {
+        return Err(anyhow!(
+            "The minimum point is incorrect: {} vs. {}",
+            actual_p[0],
+            p[0]
+        ));
+    }
+    if (m - actual_m).abs() >= 1e-4 {
+        return Err(anyhow!(
+            "The minimum value is incorrect: {} vs. {}",
+            actual_m,
+            m
+        ));
+    }
+    Ok(())
+}
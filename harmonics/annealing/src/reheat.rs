@@ -0,0 +1,16 @@
+//! Provides the [`Reheat`](crate::Reheat) struct
+
+use num::Float;
+
+/// Reheating policy: if the best solution hasn't been improved for
+/// `stall_k` iterations, the search restarts from the best point, and the
+/// schedule restarts from the temperature `factor * t_0` (at most `max` times)
+#[derive(Clone, Copy, Debug)]
+pub struct Reheat<F: Float> {
+    /// Number of iterations without improvement before reheating
+    pub stall_k: usize,
+    /// Factor of the initial temperature to reheat to
+    pub factor: F,
+    /// Maximum number of reheats
+    pub max: usize,
+}
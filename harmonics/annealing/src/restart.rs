@@ -0,0 +1,47 @@
+//! Provides the [`RestartPolicy`](crate::RestartPolicy) enum
+
+use itertools::izip;
+use num::Float;
+use rand::prelude::*;
+use rand_distr::{uniform::SampleUniform, Uniform};
+
+use crate::{Bounds, Point};
+
+/// Policy of choosing the initial point of a restart
+pub enum Policy {
+    /// Intensification: start from the best point found by the previous restarts
+    Intensify,
+    /// Diversification: start from a random point sampled uniformly within the bounds
+    Diversify,
+}
+
+impl Policy {
+    /// Get the initial point of the next restart
+    ///
+    /// Arguments:
+    /// * `best_p` --- Best point found by the previous restarts;
+    /// * `bounds` --- Bounds of the parameter space;
+    /// * `rng` --- Random number generator.
+    pub fn start<F, R, const N: usize>(
+        &self,
+        best_p: &Point<F, N>,
+        bounds: &Bounds<F, N>,
+        rng: &mut R,
+    ) -> Point<F, N>
+    where
+        F: Float + SampleUniform,
+        R: Rng,
+    {
+        match self {
+            Policy::Intensify => *best_p,
+            Policy::Diversify => {
+                let mut p = [F::zero(); N];
+                // Sample each coordinate uniformly within its range
+                izip!(&mut p, bounds).for_each(|(p, r)| {
+                    *p = Uniform::new(r.start, r.end).sample(rng);
+                });
+                p
+            }
+        }
+    }
+}
@@ -1,14 +1,21 @@
 //! Provides the [`SA`](crate::SA) struct and the
-//! [`minimum`](crate::SA#method.minimum) method
+//! [`findmin`](crate::SA#method.findmin) method
 
+use anyhow::{anyhow, Result};
+use itertools::izip;
 use num::Float;
-use numeric_literals::replace_float_literals;
 use rand::prelude::*;
-use rand_distr::{uniform::SampleUniform, Distribution, StandardNormal, Uniform};
+use rand_distr::{uniform::SampleUniform, Distribution, StandardNormal};
 
 use std::fmt::Debug;
 
-use crate::{Bounds, NeighbourMethod, Point, Schedule, Status, APF};
+use crate::anneal::{self, anneal, Settings, Step};
+use crate::boundary::below;
+use crate::check::check;
+use crate::{
+    BoundaryPolicy, Bounds, Converged, History, NeighbourMethod, Penalty, Point, Reheat,
+    RestartPolicy, Schedule, Solution, Status, VarKind, APF,
+};
 
 /// Simulated annealing
 pub struct SA<'a, 'b, F, R, FN, const N: usize>
@@ -20,6 +27,9 @@ where
 {
     /// Objective function
     pub f: FN,
+    /// Penalty added to the objective function (if any; then the neighbours
+    /// are allowed out of the bounds, so the constraints become soft)
+    pub penalty: Option<Penalty<F, N>>,
     /// Initial point
     pub p_0: &'a Point<F, N>,
     /// Initial temperature
@@ -32,93 +42,426 @@ where
     pub apf: &'a APF<F, R>,
     /// Method of getting a random neighbour
     pub neighbour: &'a NeighbourMethod<F, R, N>,
+    /// Policies of handling the coordinates of a neighbour out of the bounds
+    pub boundary: &'a [BoundaryPolicy; N],
+    /// Kinds of the coordinates (the initial points and the
+    /// neighbours are snapped to the allowed values)
+    pub kinds: &'a [VarKind<F>; N],
     /// Annealing schedule
     pub schedule: &'a Schedule<F>,
+    /// Reheating policy (if any)
+    pub reheat: Option<Reheat<F>>,
+    /// Convergence criterion (if any)
+    pub stop: Option<Converged<F>>,
     /// Status function
     pub status: &'a mut Status<'b, F, N>,
     /// Random number generator
     pub rng: &'a mut R,
+    /// Clamp the initial point into the bounds (instead of returning an error)
+    /// if it's out of them? (See [`initial_point`](SA#method.initial_point)
+    /// for checking whether that happened)
+    pub clamp_start: bool,
 }
 
-impl<F, R, FN, const N: usize> SA<'_, '_, F, R, FN, N>
+impl<'a, 'b, F, R, FN, const N: usize> SA<'a, 'b, F, R, FN, N>
 where
     F: Float + SampleUniform + Debug,
     StandardNormal: Distribution<F>,
-    R: Rng + SeedableRng,
+    R: Rng,
     FN: FnMut(&Point<F, N>) -> F,
 {
+    /// Initialize the search with the default optional settings: no penalty,
+    /// rejecting the neighbours out of the bounds, continuous coordinates,
+    /// no reheating, no early stopping, and no clamping of the initial point
+    /// (override them with the struct update syntax if needed)
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        f: FN,
+        p_0: &'a Point<F, N>,
+        t_0: F,
+        t_min: F,
+        bounds: &'a Bounds<F, N>,
+        apf: &'a APF<F, R>,
+        neighbour: &'a NeighbourMethod<F, R, N>,
+        schedule: &'a Schedule<F>,
+        status: &'a mut Status<'b, F, N>,
+        rng: &'a mut R,
+    ) -> Self {
+        SA {
+            f,
+            penalty: None,
+            p_0,
+            t_0,
+            t_min,
+            bounds,
+            apf,
+            neighbour,
+            boundary: &[BoundaryPolicy::Reject; N],
+            kinds: &[VarKind::Continuous; N],
+            schedule,
+            reheat: None,
+            stop: None,
+            status,
+            rng,
+            clamp_start: false,
+        }
+    }
+
     /// Find the global minimum (and the corresponding point) of the objective function
-    #[replace_float_literals(F::from(literal).unwrap())]
-    pub fn findmin(&mut self) -> (F, Point<F, N>) {
-        // Evaluate the objective function at the initial point and
-        // save the initial values as the current working solution
-        let mut p = *self.p_0;
-        let mut f = (self.f)(self.p_0);
-        // Save the current working solution as the current best
-        let mut best_p = p;
-        let mut best_f = f;
-        // Save the initial temperature as the current one
-        let mut t = self.t_0;
-        // Prepare the iterations counter
-        let mut k = 1;
-        // Prepare a Uniform[0, 1] distribution for the APF
-        let uni = Uniform::new(0., 1.);
-        // Search for the minimum of the objective function
-        while t > self.t_min {
-            // Get a neighbor
-            let neighbour_p = self.neighbour.neighbour(&p, self.bounds, self.rng);
-            // Evaluate the objective function
-            let neighbour_f = (self.f)(&neighbour_p);
-            // Compute the difference between the new and the current solutions
-            let diff = neighbour_f - f;
-            // If the new solution is accepted by the acceptance probability function,
-            if self.apf.accept(diff, t, &uni, self.rng) {
-                // Save it as the current solution
-                p = neighbour_p;
-                f = neighbour_f;
+    pub fn findmin(&mut self) -> Result<(F, Point<F, N>)> {
+        let solution = self.findmin_full()?;
+        Ok((solution.value, solution.point))
+    }
+
+    /// Find the global minimum (and the corresponding point) of the objective
+    /// function, return it together with the statistics of the search
+    pub fn findmin_full(&mut self) -> Result<Solution<F, N>> {
+        let (p_0, _) = self.initial_point()?;
+        Ok(self.anneal(p_0, None, None))
+    }
+
+    /// Find the global minimum (and the corresponding point) of the objective
+    /// function, return it together with the statistics of the search and
+    /// all accepted points (in the order of acceptance)
+    pub fn findmin_cloud(&mut self) -> Result<(Solution<F, N>, Vec<Point<F, N>>)> {
+        let (p_0, _) = self.initial_point()?;
+        let mut cloud = Vec::new();
+        let solution = self.anneal(p_0, Some(&mut cloud), None);
+        Ok((solution, cloud))
+    }
+
+    /// Find the global minimum (and the corresponding point) of the objective
+    /// function, return it together with the history of the search
+    ///
+    /// Arguments:
+    /// * `every` --- Number of iterations between the samples of the history.
+    pub fn findmin_history(&mut self, every: usize) -> Result<(F, Point<F, N>, History<F, N>)> {
+        if every == 0 {
+            return Err(anyhow!(
+                "The number of iterations between the samples must be positive"
+            ));
+        }
+        let (p_0, _) = self.initial_point()?;
+        let mut history = History::new();
+        let solution = self.anneal(p_0, None, Some((&mut history, every)));
+        Ok((solution.value, solution.point, history))
+    }
+
+    /// Find the global minimum (and the corresponding point) of the objective
+    /// function by running the full anneal several times, return the best
+    /// solution together with the total statistics of the search
+    ///
+    /// The first run starts from the initial point, the initial points
+    /// of the next ones are chosen according to the restart policy
+    ///
+    /// Arguments:
+    /// * `starts` --- Number of runs;
+    /// * `policy` --- Restart policy.
+    pub fn findmin_multistart(
+        &mut self,
+        starts: usize,
+        policy: &RestartPolicy,
+    ) -> Result<Solution<F, N>> {
+        if starts == 0 {
+            return Err(anyhow!("The number of starts must be positive"));
+        }
+        // Run the first anneal from the initial point
+        let (p_0, _) = self.initial_point()?;
+        let mut best = self.anneal(p_0, None, None);
+        for _ in 1..starts {
+            // Choose the initial point of the restart
+            let p_0 = policy.start(&best.point, self.bounds, self.rng);
+            // Run the anneal from it
+            let solution = self.anneal(p_0, None, None);
+            // Update the statistics and the best solution
+            best.merge(&solution);
+        }
+        Ok(best)
+    }
+
+    /// Find the global minimum (and the corresponding point) of the objective
+    /// function by running the full anneal from several random initial points
+    /// (sampled uniformly within the bounds), return the best solution together
+    /// with the total statistics of the search
+    ///
+    /// Each run uses its own random number generator seeded by the main one,
+    /// so the result is reproducible (and the same as in the parallel version)
+    ///
+    /// Arguments:
+    /// * `starts` --- Number of runs.
+    pub fn findmin_best_of(&mut self, starts: usize) -> Result<Solution<F, N>>
+    where
+        R: SeedableRng,
+    {
+        let seeds = self.seeds(starts)?;
+        let mut best = self.anneal_seeded(seeds[0]);
+        for &seed in &seeds[1..] {
+            let solution = self.anneal_seeded(seed);
+            best.merge(&solution);
+        }
+        Ok(best)
+    }
+
+    /// Same as [`findmin_best_of`](SA#method.findmin_best_of),
+    /// but the runs are done in parallel (the status is not printed)
+    ///
+    /// Arguments:
+    /// * `starts` --- Number of runs.
+    #[cfg(feature = "parallel")]
+    pub fn findmin_best_of_par(&mut self, starts: usize) -> Result<Solution<F, N>>
+    where
+        F: Send + Sync,
+        R: SeedableRng,
+        FN: Fn(&Point<F, N>) -> F + Sync,
+    {
+        use rayon::prelude::*;
+
+        let seeds = self.seeds(starts)?;
+        // Share only the thread-safe parts of the settings
+        let (f, penalty, p_0, bounds) = (&self.f, self.penalty, self.p_0, self.bounds);
+        let (apf, neighbour) = (self.apf, self.neighbour);
+        let (boundary, kinds) = (self.boundary, self.kinds);
+        let (schedule, reheat, stop) = (self.schedule, self.reheat, self.stop);
+        let (t_0, t_min) = (self.t_0, self.t_min);
+        let solutions: Vec<Solution<F, N>> = seeds
+            .par_iter()
+            .map(|&seed| {
+                let mut rng = R::seed_from_u64(seed);
+                let p_0 = RestartPolicy::Diversify.start(p_0, bounds, &mut rng);
+                SA {
+                    f,
+                    penalty,
+                    p_0: &p_0,
+                    t_0,
+                    t_min,
+                    bounds,
+                    apf,
+                    neighbour,
+                    boundary,
+                    kinds,
+                    schedule,
+                    reheat,
+                    stop,
+                    status: &mut Status::None,
+                    rng: &mut rng,
+                    clamp_start: false,
+                }
+                .anneal(p_0, None, None)
+            })
+            .collect();
+        let mut best = solutions[0];
+        for solution in &solutions[1..] {
+            best.merge(solution);
+        }
+        Ok(best)
+    }
+
+    /// Check the settings, return the seeds of the random
+    /// number generators of the independent runs
+    ///
+    /// Arguments:
+    /// * `starts` --- Number of runs.
+    fn seeds(&mut self, starts: usize) -> Result<Vec<u64>> {
+        if starts == 0 {
+            return Err(anyhow!("The number of starts must be positive"));
+        }
+        self.initial_point()?;
+        Ok((0..starts).map(|_| self.rng.next_u64()).collect())
+    }
+
+    /// Run the anneal from a random initial point (sampled uniformly within
+    /// the bounds) using a random number generator with the specified seed
+    ///
+    /// Arguments:
+    /// * `seed` --- Seed of the random number generator.
+    fn anneal_seeded(&mut self, seed: u64) -> Solution<F, N>
+    where
+        R: SeedableRng,
+    {
+        let mut rng = R::seed_from_u64(seed);
+        let p_0 = RestartPolicy::Diversify.start(self.p_0, self.bounds, &mut rng);
+        SA {
+            f: &mut self.f,
+            penalty: self.penalty,
+            p_0: &p_0,
+            t_0: self.t_0,
+            t_min: self.t_min,
+            bounds: self.bounds,
+            apf: self.apf,
+            neighbour: self.neighbour,
+            boundary: self.boundary,
+            kinds: self.kinds,
+            schedule: self.schedule,
+            reheat: self.reheat,
+            stop: self.stop,
+            status: &mut *self.status,
+            rng: &mut rng,
+            clamp_start: false,
+        }
+        .anneal(p_0, None, None)
+    }
+
+    /// Check if the bounds and the initial point are valid and if the point
+    /// is within the bounds, clamp it into them if it's not and it's allowed
+    ///
+    /// Return the point the search starts from and whether it was clamped
+    pub fn initial_point(&self) -> Result<(Point<F, N>, bool)> {
+        // If the initial point is within the bounds, use it
+        if !check(self.p_0, self.bounds, self.clamp_start)? {
+            return Ok((*self.p_0, false));
+        }
+        // Otherwise, clamp it (that's allowed if the check passed)
+        let mut p_0 = *self.p_0;
+        izip!(&mut p_0, self.bounds).for_each(|(p, r)| {
+            if *p < r.start {
+                *p = r.start;
+            } else if *p >= r.end {
+                // The upper bound is excluded, so take a number
+                // slightly below it (by its relative epsilon)
+                *p = below(r.end);
             }
-            // If the new solution is the new best,
-            if neighbour_f < best_f {
-                // Save it as the new best
-                best_p = neighbour_p;
-                best_f = neighbour_f;
+        });
+        Ok((p_0, true))
+    }
+
+    /// Run the anneal from the specified initial point
+    ///
+    /// Arguments:
+    /// * `p_0` --- Initial point;
+    /// * `cloud` --- Vector to put the accepted points in (if they're collected);
+    /// * `history` --- History to record the samples in and the number
+    ///   of iterations between them (if it's recorded).
+    fn anneal(
+        &mut self,
+        p_0: Point<F, N>,
+        cloud: Option<&mut Vec<Point<F, N>>>,
+        history: Option<(&mut History<F, N>, usize)>,
+    ) -> Solution<F, N> {
+        let settings = Settings {
+            t_0: self.t_0,
+            t_min: self.t_min,
+            bounds: self.bounds,
+            apf: self.apf,
+            kinds: self.kinds,
+            schedule: self.schedule,
+            reheat: self.reheat,
+            stop: self.stop,
+        };
+        // Allow the neighbours out of the bounds if the constraints are soft
+        let unbounded = [(); N].map(|()| F::neg_infinity()..F::infinity());
+        let bounds = if self.penalty.is_some() {
+            &unbounded
+        } else {
+            self.bounds
+        };
+        let (value, point, iterations) = anneal(
+            &settings,
+            p_0,
+            |p| (self.f)(p) + self.penalty.map_or(F::zero(), |g| g(p, self.bounds)),
+            |p, rng| self.neighbour.neighbour(p, bounds, self.boundary, rng),
+            &mut Observer {
+                status: &mut *self.status,
+                cloud,
+                history,
+            },
+            &mut *self.rng,
+        );
+        Solution {
+            value,
+            point,
+            iterations,
+            // The objective function is evaluated once per
+            // iteration, plus once at the initial point
+            evaluations: iterations + 1,
+        }
+    }
+}
+
+/// Observer of the search which prints the status, records
+/// the history and collects the accepted points
+struct Observer<'s, 'b, F: Float + Debug, const N: usize> {
+    /// Status function
+    status: &'s mut Status<'b, F, N>,
+    /// Vector to put the accepted points in (if they're collected)
+    cloud: Option<&'s mut Vec<Point<F, N>>>,
+    /// History to record the samples in and the number
+    /// of iterations between them (if it's recorded)
+    history: Option<(&'s mut History<F, N>, usize)>,
+}
+
+impl<F: Float + Debug, const N: usize> anneal::Observer<F, Point<F, N>> for Observer<'_, '_, F, N> {
+    fn accepted(&mut self, p: &Point<F, N>) {
+        // Collect the point if that's requested
+        if let Some(cloud) = self.cloud.as_mut() {
+            cloud.push(*p);
+        }
+    }
+
+    fn step(&mut self, s: &Step<F, Point<F, N>>) {
+        // Print the status
+        self.status.print(s.k, s.t, s.f, *s.p, s.best_f, *s.best_p);
+        // Record the history if that's requested
+        if let Some((history, every)) = self.history.as_mut() {
+            if s.k == 1 || s.k % *every == 0 {
+                history.record(
+                    s.k,
+                    s.t,
+                    s.f,
+                    *s.p,
+                    s.best_f,
+                    *s.best_p,
+                    s.accepted_k,
+                    s.rate,
+                );
             }
-            // Lower the temperature
-            t = self.schedule.cool(k, t, self.t_0);
-            // Print the status
-            self.status.print(k, t, f, p, best_f, best_p);
-            // Update the iterations counter
-            k += 1;
         }
-        (best_f, best_p)
     }
 }
 
 #[cfg(test)]
-use anyhow::{anyhow, Result};
+impl<'a, 'b, FN> SA<'a, 'b, f64, rand_xoshiro::Xoshiro256PlusPlus, FN, 1>
+where
+    FN: FnMut(&Point<f64, 1>) -> f64,
+{
+    /// Initialize the search used in the tests (the settings
+    /// are overridden with the struct update syntax if needed)
+    fn test(
+        f: FN,
+        status: &'a mut Status<'b, f64, 1>,
+        rng: &'a mut rand_xoshiro::Xoshiro256PlusPlus,
+    ) -> Self {
+        SA::new(
+            f,
+            &[2.],
+            100_000.0,
+            1.0,
+            &[1.0..27.8],
+            &APF::Metropolis,
+            &NeighbourMethod::Normal { sd: 5. },
+            &Schedule::Fast,
+            status,
+            rng,
+        )
+    }
+}
+
+/// Objective function used in the tests (the global minimum is at 22.79058066)
+#[cfg(test)]
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn f(p: &Point<f64, 1>) -> f64 {
+    let x = p[0];
+    f64::ln(x) * (f64::sin(x) + f64::cos(x))
+}
 
 #[test]
 fn test() -> Result<()> {
-    // Define the objective function
-    #[allow(clippy::trivially_copy_pass_by_ref)]
-    fn f(p: &Point<f64, 1>) -> f64 {
-        let x = p[0];
-        f64::ln(x) * (f64::sin(x) + f64::cos(x))
-    }
     // Get the minimum
-    let (m, p) = SA {
+    let (m, p) = SA::test(
         f,
-        p_0: &[2.],
-        t_0: 100_000.0,
-        t_min: 1.0,
-        bounds: &[1.0..27.8],
-        apf: &APF::Metropolis,
-        neighbour: &NeighbourMethod::Normal { sd: 5. },
-        schedule: &Schedule::Fast,
-        status: &mut Status::Periodic { nk: 1000 },
-        rng: &mut rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1),
-    }
-    .findmin();
+        &mut Status::Periodic { nk: 1000 },
+        &mut rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1),
+    )
+    .findmin()?;
     // Compare the result with the actual minimum
     let actual_p = [22.790_580_66];
     let actual_m = f(&actual_p);
@@ -138,3 +481,735 @@ fn test() -> Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn test_non_seedable() -> Result<()> {
+    // Wrap a generator so that it can't be seeded
+    struct Wrapper(rand_xoshiro::Xoshiro256PlusPlus);
+    impl RngCore for Wrapper {
+        fn next_u32(&mut self) -> u32 {
+            self.0.next_u32()
+        }
+        fn next_u64(&mut self) -> u64 {
+            self.0.next_u64()
+        }
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            self.0.fill_bytes(dest);
+        }
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+            self.0.try_fill_bytes(dest)
+        }
+    }
+    // Get the minimum with the specified generator
+    fn run<R: Rng>(rng: &mut R) -> Result<(f64, Point<f64, 1>)> {
+        SA::new(
+            f,
+            &[2.],
+            100_000.0,
+            1.0,
+            &[1.0..27.8],
+            &APF::Metropolis,
+            &NeighbourMethod::Normal { sd: 5. },
+            &Schedule::Fast,
+            &mut Status::None,
+            rng,
+        )
+        .findmin()
+    }
+    // Get the minimum using the wrapper and the wrapped generator
+    let mut rng = rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1);
+    let results = [run(&mut Wrapper(rng.clone()))?, run(&mut rng)?];
+    // Check that the results are the same
+    #[allow(clippy::float_cmp)]
+    if results[0] != results[1] {
+        return Err(anyhow!(
+            "The results are different: {:?} vs. {:?}",
+            results[0],
+            results[1]
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_findmin_full() -> Result<()> {
+    // Get the minimum
+    let solution = SA::test(
+        f,
+        &mut Status::None,
+        &mut rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1),
+    )
+    .findmin_full()?;
+    // Check the statistics
+    if solution.evaluations != solution.iterations + 1 {
+        return Err(anyhow!(
+            "The number of evaluations is incorrect: {} vs. {}",
+            solution.iterations + 1,
+            solution.evaluations
+        ));
+    }
+    // Serialize the solution
+    let json = serde_json::to_value(solution)?;
+    if json["value"].as_f64() != Some(solution.value) {
+        return Err(anyhow!(
+            "The minimum value is not serialized: {} vs. {}",
+            solution.value,
+            json["value"]
+        ));
+    }
+    if json["point"].as_array().map(Vec::len) != Some(1)
+        || json["point"][0].as_f64() != Some(solution.point[0])
+    {
+        return Err(anyhow!(
+            "The point is not serialized: {:?} vs. {}",
+            solution.point,
+            json["point"]
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_linear() -> Result<()> {
+    // Find the minimum using the linear schedule
+    let solution = SA {
+        schedule: &Schedule::Linear { k_max: 12345 },
+        ..SA::test(
+            f,
+            &mut Status::None,
+            &mut rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1),
+        )
+    }
+    .findmin_full()?;
+    // Check that the search stopped exactly at the last iteration
+    if solution.iterations != 12345 {
+        return Err(anyhow!(
+            "The number of iterations is incorrect: {} vs. {}",
+            12345,
+            solution.iterations
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_adaptive() -> Result<()> {
+    // Get the minimum using the adaptive schedule
+    let (m, p) = SA {
+        schedule: &Schedule::Adaptive {
+            target_rate: 0.44,
+            gamma: 0.999,
+        },
+        ..SA::test(
+            f,
+            &mut Status::None,
+            &mut rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1),
+        )
+    }
+    .findmin()?;
+    // Compare the result with the actual minimum
+    let actual_p = [22.790_580_66];
+    let actual_m = f(&actual_p);
+    if (p[0] - actual_p[0]).abs() >= 1e-2 || (m - actual_m).abs() >= 1e-4 {
+        return Err(anyhow!(
+            "The minimum is incorrect: {} at {} vs. {} at {}",
+            actual_m,
+            actual_p[0],
+            m,
+            p[0]
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_threshold() -> Result<()> {
+    // Get the minimum using the specified criterion
+    let findmin = |apf: &APF<f64, rand_xoshiro::Xoshiro256PlusPlus>| {
+        SA {
+            apf,
+            ..SA::test(
+                f,
+                &mut Status::None,
+                &mut rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1),
+            )
+        }
+        .findmin()
+    };
+    let (m, p) = findmin(&APF::Threshold)?;
+    let (m_metropolis, _) = findmin(&APF::Metropolis)?;
+    // Compare the result with the actual minimum
+    // and the result of the Metropolis criterion
+    let actual_p = [22.790_580_66];
+    let actual_m = f(&actual_p);
+    if (p[0] - actual_p[0]).abs() >= 1e-4 || (m - actual_m).abs() >= 1e-8 {
+        return Err(anyhow!(
+            "The minimum is incorrect: {} at {} vs. {} at {}",
+            actual_m,
+            actual_p[0],
+            m,
+            p[0]
+        ));
+    }
+    if (m - m_metropolis).abs() >= 1e-8 {
+        return Err(anyhow!(
+            "The minimum is different from the Metropolis one: {} vs. {}",
+            m_metropolis,
+            m
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_cauchy_neighbour() -> Result<()> {
+    // Define the objective function with a local
+    // minimum at 10 and the global one at 80
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn f(p: &Point<f64, 1>) -> f64 {
+        let x = p[0];
+        -f64::exp(-(x - 10.).powi(2)) - 2. * f64::exp(-(x - 80.).powi(2) / 50.)
+    }
+    // Get the minimum point using the specified neighbour method,
+    // starting from the local minimum at a low temperature
+    let findmin = |neighbour: &NeighbourMethod<f64, rand_xoshiro::Xoshiro256PlusPlus, 1>| {
+        SA {
+            p_0: &[10.],
+            t_0: 0.01,
+            t_min: 1e-6,
+            bounds: &[0.0..100.0],
+            neighbour,
+            schedule: &Schedule::Exponential { gamma: 0.999 },
+            ..SA::test(
+                f,
+                &mut Status::None,
+                &mut rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1),
+            )
+        }
+        .findmin()
+        .map(|(_, p)| p[0])
+    };
+    let p_normal = findmin(&NeighbourMethod::Normal { sd: 1. })?;
+    let p_cauchy = findmin(&NeighbourMethod::Cauchy { scale: 1. })?;
+    // Check that only the Cauchy jumps escape the local minimum
+    if (p_normal - 10.).abs() >= 1. {
+        return Err(anyhow!(
+            "The normal neighbours escaped the local minimum: {}",
+            p_normal
+        ));
+    }
+    if (p_cauchy - 80.).abs() >= 1e-1 {
+        return Err(anyhow!(
+            "The Cauchy neighbours didn't find the global minimum: {}",
+            p_cauchy
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_reheat() -> Result<()> {
+    // Get the solution with the specified reheating policy
+    let findmin = |reheat: Option<Reheat<f64>>| {
+        SA {
+            t_0: 100.0,
+            schedule: &Schedule::Exponential { gamma: 0.99 },
+            reheat,
+            ..SA::test(
+                f,
+                &mut Status::None,
+                &mut rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1),
+            )
+        }
+        .findmin_full()
+    };
+    let solution = findmin(None)?;
+    let solution_reheated = findmin(Some(Reheat {
+        stall_k: 50,
+        factor: 1.,
+        max: 3,
+    }))?;
+    // Check that the search was extended, but not indefinitely
+    let n = solution.iterations;
+    let n_reheated = solution_reheated.iterations;
+    if n_reheated <= n || n_reheated > 4 * n {
+        return Err(anyhow!(
+            "The number of iterations is not in the expected range: ({}, {}] vs. {}",
+            n,
+            4 * n,
+            n_reheated
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_converged() -> Result<()> {
+    // Get the solution with the specified stopping and reheating policies
+    let findmin = |stop: Option<Converged<f64>>, reheat: Option<Reheat<f64>>| {
+        SA {
+            reheat,
+            stop,
+            ..SA::test(
+                f,
+                &mut Status::None,
+                &mut rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1),
+            )
+        }
+        .findmin_full()
+    };
+    let stop = Some(Converged {
+        tol: 1e-9,
+        window: 5000,
+    });
+    let reheat = Some(Reheat {
+        stall_k: 1000,
+        factor: 1.,
+        max: 2,
+    });
+    let solution = findmin(None, None)?;
+    let solution_stopped = findmin(stop, None)?;
+    let solution_reheated = findmin(stop, reheat)?;
+    // Check that the search stopped early, but found the minimum
+    let actual_m = f(&[22.790_580_66]);
+    if solution_stopped.iterations >= solution.iterations / 2
+        || (solution_stopped.value - actual_m).abs() >= 1e-6
+    {
+        return Err(anyhow!(
+            "The search didn't stop early enough or didn't find the minimum: \
+            {} iterations vs. {} ({} vs. {})",
+            solution.iterations,
+            solution_stopped.iterations,
+            actual_m,
+            solution_stopped.value
+        ));
+    }
+    // Check that the reheats are used up before stopping
+    if solution_reheated.iterations <= solution_stopped.iterations {
+        return Err(anyhow!(
+            "The search with reheating stopped before the reheats: {} iterations vs. {}",
+            solution_stopped.iterations,
+            solution_reheated.iterations
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_findmin_history() -> Result<()> {
+    // Find the minimum, recording every 100th iteration
+    let (m, p, history) = SA::test(
+        f,
+        &mut Status::None,
+        &mut rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1),
+    )
+    .findmin_history(100)?;
+    // Check the samples
+    let n = history.k.len();
+    if history.k[0] != 1 || history.k[1] != 100 || history.k[n - 1] != 100_000 {
+        return Err(anyhow!(
+            "The iterations of the samples are incorrect: {:?}",
+            history.k
+        ));
+    }
+    let lens = [
+        history.t.len(),
+        history.f.len(),
+        history.p.len(),
+        history.best_f.len(),
+        history.best_p.len(),
+        history.accepted.len(),
+        history.rate.len(),
+    ];
+    if lens.iter().any(|&len| len != n) {
+        return Err(anyhow!("The lengths of the history vectors are different"));
+    }
+    if history.accepted.windows(2).any(|w| w[1] < w[0])
+        || izip!(&history.accepted, &history.k).any(|(a, k)| a > k)
+    {
+        return Err(anyhow!(
+            "The numbers of the accepted neighbours are incorrect: {:?}",
+            history.accepted
+        ));
+    }
+    if history.rate.iter().any(|r| !(0.0..=1.0).contains(r)) {
+        return Err(anyhow!(
+            "The acceptance rates are out of [0, 1]: {:?}",
+            history.rate
+        ));
+    }
+    if history.best_f.windows(2).any(|w| w[1] > w[0]) {
+        return Err(anyhow!("The best solutions are not monotonic"));
+    }
+    // Compare the last best sample with the result
+    #[allow(clippy::float_cmp)]
+    if history.best_f[n - 1] != m || history.best_p[n - 1] != p {
+        return Err(anyhow!(
+            "The last best sample is not the result: {} at {:?} vs. {} at {:?}",
+            m,
+            p,
+            history.best_f[n - 1],
+            history.best_p[n - 1]
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_kinds() -> Result<()> {
+    // Find the minimum of a function of a continuous and an integer
+    // coordinate, saving the points the function is evaluated at
+    let mut points = Vec::new();
+    let (m, p) = SA {
+        kinds: &[VarKind::Continuous, VarKind::Integer { step: 1. }],
+        ..SA::new(
+            |p: &Point<f64, 2>| {
+                points.push(*p);
+                let (x, l) = (p[0], p[1]);
+                f64::ln(x) * (f64::sin(x) + f64::cos(x)) + (l - 3.).powi(2)
+            },
+            &[2., 0.3],
+            100_000.0,
+            1.0,
+            &[1.0..27.8, 0.0..10.0],
+            &APF::Metropolis,
+            &NeighbourMethod::Normal { sd: 5. },
+            &Schedule::Fast,
+            &mut Status::None,
+            &mut rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1),
+        )
+    }
+    .findmin()?;
+    // Check that the integer coordinate was always on the grid
+    if let Some(p) = points.iter().find(|p| p[1].fract() != 0.) {
+        return Err(anyhow!(
+            "The function is evaluated at a non-integer point: {:?}",
+            p
+        ));
+    }
+    // Compare the result with the actual minimum
+    let actual_p = [22.790_580_66, 3.];
+    if (p[0] - actual_p[0]).abs() >= 1e-2 || (p[1] - actual_p[1]).abs() > 0. {
+        return Err(anyhow!(
+            "The minimum point is incorrect: {:?} vs. {:?} (the value is {})",
+            actual_p,
+            p,
+            m
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_penalty() -> Result<()> {
+    // Define a quadratic penalty for leaving the bounds
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn penalty(p: &Point<f64, 1>, bounds: &Bounds<f64, 1>) -> f64 {
+        let r = &bounds[0];
+        100. * (r.start - p[0]).max(p[0] - r.end).max(0.).powi(2)
+    }
+    // Find the minimum of a function with the minimum out of
+    // the bounds, saving the points it's evaluated at
+    let findmin = |penalty: Option<Penalty<f64, 1>>| {
+        let mut points = Vec::new();
+        let result = SA {
+            penalty,
+            t_0: 1000.0,
+            t_min: 0.01,
+            bounds: &[0.0..20.0],
+            neighbour: &NeighbourMethod::Normal { sd: 1. },
+            ..SA::test(
+                |p: &Point<f64, 1>| {
+                    points.push(p[0]);
+                    (p[0] - 30.).powi(2)
+                },
+                &mut Status::None,
+                &mut rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1),
+            )
+        }
+        .findmin();
+        result.map(|(m, p)| (m, p[0], points.iter().any(|&x| x >= 20.)))
+    };
+    let (_, p, outside) = findmin(None)?;
+    let (m_soft, p_soft, outside_soft) = findmin(Some(penalty))?;
+    // Check that the hard constraints are never violated
+    if outside || p >= 20. {
+        return Err(anyhow!("The function is evaluated out of the bounds"));
+    }
+    // Check that the soft constraints lead to the minimum
+    // of the penalized function (at 2030 / 101)
+    let actual_p = 2030. / 101.;
+    let actual_m = (actual_p - 30_f64).powi(2) + 100. * (actual_p - 20_f64).powi(2);
+    if !outside_soft || (p_soft - actual_p).abs() >= 1e-2 || (m_soft - actual_m).abs() >= 1e-2 {
+        return Err(anyhow!(
+            "The minimum of the penalized function is incorrect: {} at {} vs. {} at {}",
+            actual_m,
+            actual_p,
+            m_soft,
+            p_soft
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_findmin_multistart() -> Result<()> {
+    // Run the multistart search with the specified
+    // policy, return the initial points of the runs
+    fn starts(policy: &RestartPolicy) -> Result<(Vec<f64>, Solution<f64, 1>)> {
+        let mut points = Vec::new();
+        let solution = SA {
+            t_0: 1000.0,
+            ..SA::test(
+                |p: &Point<f64, 1>| {
+                    points.push(p[0]);
+                    let x = p[0];
+                    f64::ln(x) * (f64::sin(x) + f64::cos(x))
+                },
+                &mut Status::None,
+                &mut rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1),
+            )
+        }
+        .findmin_multistart(10, policy)?;
+        // The objective function is evaluated at the initial
+        // point first, then once per iteration of each run
+        let n = solution.evaluations / 10;
+        Ok((points.into_iter().step_by(n).collect(), solution))
+    }
+    // Check the intensification policy
+    let (points, solution) = starts(&RestartPolicy::Intensify)?;
+    if (points[0] - 2.).abs() >= f64::EPSILON {
+        return Err(anyhow!(
+            "The first run didn't start from the initial point: {}",
+            points[0]
+        ));
+    }
+    if points
+        .iter()
+        .skip(1)
+        .any(|&p| (p - solution.point[0]).abs() >= 5.)
+    {
+        return Err(anyhow!(
+            "The restarts didn't start near the best point {}: {:?}",
+            solution.point[0],
+            points
+        ));
+    }
+    // Check the diversification policy
+    let (points, _) = starts(&RestartPolicy::Diversify)?;
+    let min = points.iter().skip(1).copied().fold(f64::INFINITY, f64::min);
+    let max = points
+        .iter()
+        .skip(1)
+        .copied()
+        .fold(f64::NEG_INFINITY, f64::max);
+    if max - min <= 10. {
+        return Err(anyhow!(
+            "The restarts didn't start from points spread across the bounds: {:?}",
+            points
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_findmin_best_of() -> Result<()> {
+    // Find the minimum by several independent runs
+    let findmin = |parallel: bool| {
+        let mut status = Status::None;
+        let mut rng = rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1);
+        let mut sa = SA {
+            t_0: 1000.0,
+            ..SA::test(f, &mut status, &mut rng)
+        };
+        if parallel {
+            #[cfg(feature = "parallel")]
+            return sa.findmin_best_of_par(4);
+        }
+        sa.findmin_best_of(4)
+    };
+    let solution = findmin(false)?;
+    // Compare the result with the actual minimum
+    let actual_p = 22.790_580_66;
+    if (solution.point[0] - actual_p).abs() >= 1e-2 || solution.iterations != 4 * 1000 {
+        return Err(anyhow!(
+            "The solution is incorrect: {} at {} after {} iterations",
+            solution.value,
+            solution.point[0],
+            solution.iterations
+        ));
+    }
+    // Check that the runs are reproducible (in parallel, too)
+    for parallel in [false, cfg!(feature = "parallel")] {
+        let other = findmin(parallel)?;
+        #[allow(clippy::float_cmp)]
+        if other.value != solution.value || other.point != solution.point {
+            return Err(anyhow!(
+                "The solution is not reproducible: {} at {:?} vs. {} at {:?}",
+                solution.value,
+                solution.point,
+                other.value,
+                other.point
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_clamp_start() -> Result<()> {
+    // Search for the minimum starting from the passed point, return
+    // the actual initial point and whether it was reported as clamped
+    fn start(p_0: f64, clamp_start: bool) -> Result<(f64, bool)> {
+        let mut points = Vec::new();
+        let clamped = {
+            let mut status = Status::None;
+            let mut rng = rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1);
+            let mut sa = SA {
+                p_0: &[p_0],
+                t_0: 10.0,
+                bounds: &[-1.0..1.0],
+                neighbour: &NeighbourMethod::Normal { sd: 0.1 },
+                clamp_start,
+                ..SA::test(
+                    |p: &Point<f64, 1>| {
+                        points.push(p[0]);
+                        p[0].powi(2)
+                    },
+                    &mut status,
+                    &mut rng,
+                )
+            };
+            let (_, clamped) = sa.initial_point()?;
+            sa.findmin_full()?;
+            clamped
+        };
+        // The objective function is evaluated at the initial point first
+        Ok((points[0], clamped))
+    }
+    // Check that out-of-bounds initial points are rejected
+    for p_0 in [-2., 1., 2.] {
+        match start(p_0, false) {
+            Ok(_) => {
+                return Err(anyhow!(
+                    "The out-of-bounds initial point {} is not rejected",
+                    p_0
+                ))
+            }
+            Err(e) if e.to_string().contains("out of the bounds") => (),
+            Err(e) => return Err(anyhow!("The error is not descriptive: {}", e)),
+        }
+    }
+    // Check that out-of-bounds initial points are clamped
+    for (p_0, expected) in [(-2., -1.), (1., 1.), (2., 1.)] {
+        let (p, clamped) = start(p_0, true)?;
+        if !clamped {
+            return Err(anyhow!("The clamping of {} is not reported", p_0));
+        }
+        if !(-1.0..1.0).contains(&p) || (p - expected).abs() >= 1e-9 {
+            return Err(anyhow!(
+                "The initial point {} is not clamped to the nearest feasible point: {}",
+                p_0,
+                p
+            ));
+        }
+    }
+    // Check that the initial points within the bounds are kept
+    let (p, clamped) = start(0.5, true)?;
+    if clamped || (p - 0.5).abs() >= f64::EPSILON {
+        return Err(anyhow!("The initial point is changed: 0.5 vs. {}", p));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_invalid_input() -> Result<()> {
+    // Search for the minimum with the passed initial point and bounds
+    fn findmin(p_0: f64, bounds: std::ops::Range<f64>) -> Result<Solution<f64, 1>> {
+        SA {
+            p_0: &[p_0],
+            t_0: 10.0,
+            bounds: &[bounds],
+            neighbour: &NeighbourMethod::Normal { sd: 0.1 },
+            clamp_start: true,
+            ..SA::test(
+                |p: &Point<f64, 1>| p[0].powi(2),
+                &mut Status::None,
+                &mut rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1),
+            )
+        }
+        .findmin_full()
+    }
+    // Check that the invalid input is rejected with a descriptive error
+    for (p_0, bounds, message) in [
+        (2., 5.0..1.0, "empty or reversed"),
+        (f64::NAN, 1.0..5.0, "not finite"),
+    ] {
+        match findmin(p_0, bounds.clone()) {
+            Ok(_) => {
+                return Err(anyhow!(
+                    "The invalid input is not rejected: {:?}, {:?}",
+                    p_0,
+                    bounds
+                ))
+            }
+            Err(e) if e.to_string().contains(message) => (),
+            Err(e) => return Err(anyhow!("The error is not descriptive: {}", e)),
+        }
+    }
+    // Check that the zero numbers of iterations and runs are rejected
+    let mut status = Status::None;
+    let mut rng = rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1);
+    let mut sa = SA::test(f, &mut status, &mut rng);
+    for (name, result) in [
+        ("history", sa.findmin_history(0).map(|_| ())),
+        (
+            "multistart",
+            sa.findmin_multistart(0, &RestartPolicy::Intensify)
+                .map(|_| ()),
+        ),
+        ("best of", sa.findmin_best_of(0).map(|_| ())),
+    ] {
+        match result {
+            Ok(()) => return Err(anyhow!("The zero argument of {} is not rejected", name)),
+            Err(e) if e.to_string().contains("must be positive") => (),
+            Err(e) => return Err(anyhow!("The error is not descriptive: {}", e)),
+        }
+    }
+    Ok(())
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_findmin_cloud() -> Result<()> {
+    // Count the accepted moves by the changes of the current point
+    // (the points are compared exactly, since they're copied)
+    let mut accepted = 0;
+    let mut p_prev = [2.];
+    // Get the minimum and the cloud
+    let (_, cloud) = SA {
+        t_0: 1000.0,
+        ..SA::test(
+            f,
+            &mut Status::Custom {
+                f: Box::new(|_, _, _, p: [f64; 1], _, _| {
+                    if p != p_prev {
+                        accepted += 1;
+                        p_prev = p;
+                    }
+                }),
+            },
+            &mut rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(1),
+        )
+    }
+    .findmin_cloud()?;
+    // Compare the number of the collected points with the number of accepted moves
+    if cloud.len() != accepted {
+        return Err(anyhow!(
+            "The number of points in the cloud is incorrect: {} vs. {}",
+            accepted,
+            cloud.len()
+        ));
+    }
+    Ok(())
+}
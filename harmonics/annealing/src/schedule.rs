@@ -7,9 +7,11 @@ use std::fmt::Debug;
 
 /// Annealing schedule
 pub enum Schedule<F: Float> {
-    /// Logarithmic:
+    /// Logarithmic (Boltzmann):
     ///
     /// $ t^{(k)} = t^{(1)} \ln(2) / \ln(k + 1) $
+    ///
+    /// (the index is clamped to be at least 1, so $ t^{(0)} = t^{(1)} $)
     Logarithmic,
     /// Exponential:
     ///
@@ -22,6 +24,27 @@ pub enum Schedule<F: Float> {
     ///
     /// $ t^{(k)} = t^{(1)} / k $
     Fast,
+    /// Linear:
+    ///
+    /// $ t^{(k)} = t^{(0)} - k (t^{(0)} - t_{min}) / K \\; \text{for} \\; k \leq K $
+    /// (the minimum temperature is reached exactly at the last iteration)
+    Linear {
+        /// Number of iterations $ K $
+        k_max: usize,
+    },
+    /// Adaptive:
+    ///
+    /// $ t^{(k+1)} = \gamma^{\min(\max(\rho / \rho^*, 1/2), 2)} t^{(k)} $,
+    ///
+    /// where $ \rho $ is the recent acceptance rate and $ \rho^* $
+    /// is the target one (cools faster when the rate is higher than
+    /// the target and slower when it's lower, but never stalls)
+    Adaptive {
+        /// Target acceptance rate $ \rho^* \in (0, 1] $ (e.g., 0.44)
+        target_rate: F,
+        /// Exponential parameter $ \gamma \in (0, 1) $
+        gamma: F,
+    },
     /// Custom: choose your own!
     Custom {
         /// Custom function
@@ -35,14 +58,77 @@ impl<F: Float + Debug> Schedule<F> {
     /// Arguments:
     /// * `k` --- Index of the iteration;
     /// * `t` --- Temperature,
-    /// * `t_0` --- Initial temperature.
+    /// * `t_0` --- Initial temperature;
+    /// * `t_min` --- Minimum temperature;
+    /// * `rate` --- Recent acceptance rate.
     #[replace_float_literals(F::from(literal).unwrap())]
-    pub fn cool(&self, k: usize, t: F, t_0: F) -> F {
+    pub fn cool(&self, k: usize, t: F, t_0: F, t_min: F, rate: F) -> F {
         match self {
-            Schedule::Logarithmic => t_0 * F::ln(2.) / F::ln(F::from(k + 1).unwrap()),
+            Schedule::Logarithmic => t_0 * F::ln(2.) / F::ln(F::from(k.max(1) + 1).unwrap()),
             Schedule::Exponential { gamma } => *gamma * t,
             Schedule::Fast => t_0 / F::from(k).unwrap(),
+            Schedule::Linear { k_max } => {
+                // Avoid the rounding errors at the last iteration
+                if k >= *k_max {
+                    t_min
+                } else {
+                    t_min + (t_0 - t_min) * F::from(k_max - k).unwrap() / F::from(*k_max).unwrap()
+                }
+            }
+            Schedule::Adaptive { target_rate, gamma } => {
+                gamma.powf((rate / *target_rate).max(0.5).min(2.)) * t
+            }
             Schedule::Custom { f } => f(k, t, t_0),
         }
     }
 }
+
+#[test]
+fn test_logarithmic() -> anyhow::Result<()> {
+    let schedule = Schedule::Logarithmic;
+    let t_0 = 100.;
+    // Check that the first iterations don't divide by zero
+    for k in [0, 1] {
+        let t = schedule.cool(k, t_0, t_0, 1., 0.);
+        if (t - t_0).abs() >= 1e-12 {
+            return Err(anyhow::anyhow!(
+                "The temperature at k = {k} is incorrect: {t_0} vs. {t}"
+            ));
+        }
+    }
+    // Check that the temperature is halved at k = 3
+    let t = schedule.cool(3, t_0, t_0, 1., 0.);
+    if (t - t_0 / 2.).abs() >= 1e-12 {
+        return Err(anyhow::anyhow!(
+            "The temperature at k = 3 is incorrect: {} vs. {t}",
+            t_0 / 2.
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_adaptive() -> anyhow::Result<()> {
+    let schedule = Schedule::Adaptive {
+        target_rate: 0.44,
+        gamma: 0.9,
+    };
+    let t = 100.;
+    // Check that the temperature is lowered faster
+    // when the rate is higher than the target
+    let t_low = schedule.cool(1, t, t, 1., 0.1);
+    let t_target = schedule.cool(1, t, t, 1., 0.44);
+    let t_high = schedule.cool(1, t, t, 1., 0.9);
+    if !(t_high < t_target && t_target < t_low && t_low < t) {
+        return Err(anyhow::anyhow!(
+            "The temperatures are not ordered: {t_high}, {t_target}, {t_low}"
+        ));
+    }
+    // Check that the temperature is still lowered when nothing is accepted
+    if (schedule.cool(1, t, t, 1., 0.) - 0.9_f64.sqrt() * t).abs() >= 1e-12 {
+        return Err(anyhow::anyhow!(
+            "The temperature is not lowered with the zero rate"
+        ));
+    }
+    Ok(())
+}
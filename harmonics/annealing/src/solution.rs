@@ -0,0 +1,49 @@
+//! Provides the [`Solution`](crate::Solution) struct
+
+use num::Float;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+use crate::Point;
+
+/// A solution found by the simulated annealing, plus
+/// the statistics of the search which led to it
+#[derive(Clone, Copy, Debug)]
+pub struct Solution<F: Float, const N: usize> {
+    /// Value of the objective function at the point
+    pub value: F,
+    /// Point of the solution
+    pub point: Point<F, N>,
+    /// Number of iterations
+    pub iterations: usize,
+    /// Number of evaluations of the objective function
+    pub evaluations: usize,
+}
+
+impl<F: Float, const N: usize> Solution<F, N> {
+    /// Merge the solution of another run into this one: add up the statistics
+    /// and take the other solution if it's better
+    ///
+    /// Arguments:
+    /// * `other` --- Solution of another run.
+    pub(crate) fn merge(&mut self, other: &Self) {
+        self.iterations += other.iterations;
+        self.evaluations += other.evaluations;
+        if other.value < self.value {
+            self.value = other.value;
+            self.point = other.point;
+        }
+    }
+}
+
+// The point is serialized as a sequence, since `serde`
+// doesn't implement `Serialize` for arrays of arbitrary length
+impl<F: Float + Serialize, const N: usize> Serialize for Solution<F, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Solution", 4)?;
+        state.serialize_field("value", &self.value)?;
+        state.serialize_field("point", &self.point[..])?;
+        state.serialize_field("iterations", &self.iterations)?;
+        state.serialize_field("evaluations", &self.evaluations)?;
+        state.end()
+    }
+}
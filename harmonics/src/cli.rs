@@ -1,7 +1,7 @@
 //! Provides the command-line interface of the program
 
 use anyhow::Result;
-use clap::Parser;
+use clap::{ArgEnum, Parser};
 use num::Float;
 use numeric_literals::replace_float_literals;
 use paste::paste;
@@ -30,6 +30,66 @@ where
     /// Minimum temperature
     #[clap(long = "to", help_heading = "OPTIMIZATION", default_value = "1.0", validator = Self::validate_t_min)]
     pub t_min: F,
+    /// Annealing schedule
+    #[clap(long, arg_enum, help_heading = "OPTIMIZATION", default_value = "fast")]
+    pub schedule: Schedule,
+    /// Number of iterations (for the linear schedule)
+    #[clap(long, help_heading = "OPTIMIZATION", default_value_t = 100_000)]
+    pub iterations: usize,
+    /// Acceptance probability function
+    #[clap(
+        long,
+        arg_enum,
+        help_heading = "OPTIMIZATION",
+        default_value = "metropolis"
+    )]
+    pub apf: APF,
+    /// Method of getting a random neighbour
+    #[clap(
+        long,
+        arg_enum,
+        help_heading = "OPTIMIZATION",
+        default_value = "normal"
+    )]
+    pub neighbour: Neighbour,
+    /// Parameter of the Tsallis acceptance probability function
+    #[clap(long, help_heading = "OPTIMIZATION", default_value = "1.5")]
+    pub q: F,
+}
+
+/// Annealing schedules available from the command line
+#[derive(ArgEnum, Clone, Copy)]
+pub enum Schedule {
+    /// Fast
+    Fast,
+    /// Linear
+    Linear,
+}
+
+/// Acceptance probability functions available from the command line
+#[derive(ArgEnum, Clone, Copy)]
+pub enum APF {
+    /// Metropolis criterion
+    Metropolis,
+    /// Cauchy criterion
+    Cauchy,
+    /// Barker criterion
+    Barker,
+    /// Tsallis criterion
+    Tsallis,
+    /// Threshold accepting
+    Threshold,
+}
+
+/// Neighbour methods available from the command line
+#[derive(ArgEnum, Clone, Copy)]
+pub enum Neighbour {
+    /// Normal distribution
+    Normal,
+    /// Cauchy distribution
+    Cauchy,
+    /// Uniform distribution in a box
+    UniformBox,
 }
 
 /// Create a validator for an argument
@@ -19,7 +19,7 @@
 mod cli;
 mod write;
 
-use annealing::{NeighbourMethod, Point, Schedule, Status, APF, SA};
+use annealing::{BoundaryPolicy, NeighbourMethod, Point, Schedule, Status, APF, SA};
 use anyhow::{Context, Result};
 use rand::prelude::*;
 use rand_distr::Uniform;
@@ -28,7 +28,7 @@ use rgsl::{
     SfLegendreNorm,
 };
 
-use std::f64::consts::{FRAC_PI_8, PI, SQRT_2};
+use std::f64::consts::{FRAC_PI_4, FRAC_PI_8, PI, SQRT_2};
 
 /// Run the program
 #[doc(hidden)]
@@ -84,46 +84,55 @@ fn main() -> Result<()> {
                 .1,
         )
     };
-    // Prepare arrays for tracking the optimization process
-    let mut ts = Vec::<f64>::new();
-    let mut ps = Vec::<Vec<f64>>::new();
-    let mut fs = Vec::<f64>::new();
-    let mut best_ps = Vec::<Vec<f64>>::new();
-    let mut best_fs = Vec::<f64>::new();
-    // Define the status function
-    let mut status = Status::Custom {
-        f: Box::new(
-            |k: usize, t: f64, f: f64, p: [f64; 2], best_f: f64, best_p: [f64; 2]| {
-                if k == 1 || k % 1000 == 0 {
-                    ts.push(t);
-                    ps.push(p.to_vec());
-                    fs.push(-f);
-                    best_ps.push(best_p.to_vec());
-                    best_fs.push(-best_f);
-                }
-            },
-        ),
+    // Choose the annealing schedule
+    let schedule = match args.schedule {
+        cli::Schedule::Fast => Schedule::Fast,
+        cli::Schedule::Linear => Schedule::Linear {
+            k_max: args.iterations,
+        },
+    };
+    // Choose the acceptance probability function
+    let apf = match args.apf {
+        cli::APF::Metropolis => APF::Metropolis,
+        cli::APF::Cauchy => APF::Cauchy,
+        cli::APF::Barker => APF::Barker,
+        cli::APF::Tsallis => APF::Tsallis { q: args.q },
+        cli::APF::Threshold => APF::Threshold,
+    };
+    // Choose the neighbour method
+    let neighbour = match args.neighbour {
+        cli::Neighbour::Normal => NeighbourMethod::Normal { sd: FRAC_PI_8 },
+        cli::Neighbour::Cauchy => NeighbourMethod::Cauchy { scale: FRAC_PI_8 },
+        cli::Neighbour::UniformBox => NeighbourMethod::UniformBox {
+            width: [FRAC_PI_8, FRAC_PI_4],
+        },
     };
     // Define bounds
     let bounds = [0.0..PI, 0.0..2. * PI];
-    // Find the global minimum of the objective
-    // function and the corresponding point
-    let (minimum, point) = SA {
-        f: f.clone(),
-        p_0: &[
-            rng.gen_range(bounds[0].clone()),
-            rng.gen_range(bounds[1].clone()),
-        ],
-        t_0: args.t_0,
-        t_min: args.t_min,
-        bounds: &bounds,
-        apf: &APF::Metropolis,
-        neighbour: &NeighbourMethod::Normal { sd: FRAC_PI_8 },
-        schedule: &Schedule::Fast,
-        status: &mut status,
-        rng: &mut rng,
+    // Find the global minimum of the objective function and the
+    // corresponding point, recording every 1000th iteration
+    let p_0 = [
+        rng.gen_range(bounds[0].clone()),
+        rng.gen_range(bounds[1].clone()),
+    ];
+    let (minimum, point, history) = SA {
+        // The azimuthal angle is periodic
+        boundary: &[BoundaryPolicy::Reject, BoundaryPolicy::Wrap],
+        ..SA::new(
+            f.clone(),
+            &p_0,
+            args.t_0,
+            args.t_min,
+            &bounds,
+            &apf,
+            &neighbour,
+            &schedule,
+            &mut Status::None,
+            &mut rng,
+        )
     }
-    .findmin();
+    .findmin_history(1000)
+    .with_context(|| "Couldn't find the global minimum")?;
     // Convert the minimum to a maximum
     let maximum = -minimum;
     // Print the result
@@ -132,6 +141,18 @@ fn main() -> Result<()> {
         maximum / (2. * PI),
         point.map(|x| x / (2. * PI)),
     );
+    // Print the acceptance rates (to help choosing the initial temperature;
+    // the recent rate is averaged over the last 100 iterations, so the
+    // first sample taken after a full window stands for the start)
+    let n = history.k.len();
+    let i = history.k.iter().position(|&k| k >= 100).unwrap_or(n - 1);
+    println!(
+        "acceptance: {:.3} at k = {}, {:.3} at the end, {:.3} overall\n",
+        history.rate[i],
+        history.k[i],
+        history.rate[n - 1],
+        history.accepted[n - 1] as f64 / history.k[n - 1] as f64,
+    );
     // Prepare a grid
     let h = 1000;
     let theta: Vec<f64> = (0..=h)
@@ -152,8 +173,9 @@ fn main() -> Result<()> {
         })
         .collect::<Vec<Vec<f64>>>()
         .concat();
-    // Relinquish the mutable borrows
-    drop(status);
+    // Convert the solutions in the history to maxima
+    let fs: Vec<f64> = history.f.iter().map(|f| -f).collect();
+    let best_fs: Vec<f64> = history.best_f.iter().map(|f| -f).collect();
     // Write the results
     write::serialize_into(&[maximum], &args.output.join("maximum.bin"))
         .with_context(|| "Couldn't serialize the maximum vector")?;
@@ -165,16 +187,18 @@ fn main() -> Result<()> {
         .with_context(|| "Couldn't serialize the azimuthal angle vector")?;
     write::serialize_into(&obj, &args.output.join("obj.bin"))
         .with_context(|| "Couldn't serialize the objective function vector")?;
-    write::serialize_into(&ts, &args.output.join("ts.bin"))
+    write::serialize_into(&history.t, &args.output.join("ts.bin"))
         .with_context(|| "Couldn't serialize the temperature vector")?;
-    write::serialize_into(&ps.concat(), &args.output.join("ps.bin"))
+    write::serialize_into(&history.p.concat(), &args.output.join("ps.bin"))
         .with_context(|| "Couldn't serialize the current points vector")?;
     write::serialize_into(&fs, &args.output.join("fs.bin"))
         .with_context(|| "Couldn't serialize the current solutions vector")?;
-    write::serialize_into(&best_ps.concat(), &args.output.join("best_ps.bin"))
+    write::serialize_into(&history.best_p.concat(), &args.output.join("best_ps.bin"))
         .with_context(|| "Couldn't serialize the best points vector")?;
     write::serialize_into(&best_fs, &args.output.join("best_fs.bin"))
         .with_context(|| "Couldn't serialize the best solutions vector")?;
+    write::serialize_into(&history.rate, &args.output.join("rates.bin"))
+        .with_context(|| "Couldn't serialize the acceptance rates vector")?;
     Ok(())
 }
 
@@ -0,0 +1,76 @@
+//! Provides the [`bisection`] routine
+
+use anyhow::anyhow;
+
+use crate::Float;
+
+/// Maximum number of iterations
+const MAX_ITER: u16 = 1000;
+
+/// Find a root of a continuous function using the bisection method
+///
+/// Arguments:
+/// * `f` --- Function;
+/// * `a` --- Left end of the interval;
+/// * `b` --- Right end of the interval.
+///
+/// The values of the function at the ends of the interval must be
+/// of different signs (or at least one of them must be zero)
+pub fn bisection<F: Float>(
+    f: impl Fn(F) -> anyhow::Result<F>,
+    mut a: F,
+    mut b: F,
+) -> anyhow::Result<F> {
+    // Compute the values at the ends of the interval
+    let f_a = f(a)?;
+    let f_b = f(b)?;
+    if f_a.is_zero() {
+        return Ok(a);
+    }
+    if f_b.is_zero() {
+        return Ok(b);
+    }
+    if f_a.signum() == f_b.signum() {
+        return Err(anyhow!(
+            "The values at the ends of the interval [{a}, {b}] are of the same sign"
+        ));
+    }
+    // On each iteration
+    for _ in 0..MAX_ITER {
+        // Compute the middle point
+        let m = a + (b - a) / F::from(2.).unwrap();
+        // Stop if the interval can't be halved anymore
+        if m == a || m == b {
+            return Ok(m);
+        }
+        // Choose the half containing the root
+        let f_m = f(m)?;
+        if f_m.is_zero() {
+            return Ok(m);
+        }
+        if f_m.signum() == f_a.signum() {
+            a = m;
+        } else {
+            b = m;
+        }
+    }
+    Ok(a + (b - a) / F::from(2.).unwrap())
+}
+
+#[test]
+fn test() -> anyhow::Result<()> {
+    // Find the root of a cubic polynomial
+    let x = bisection(|x: f64| Ok(x.powi(3) - 2.), 0., 2.)?;
+    if (x - f64::cbrt(2.)).abs() >= 4. * f64::EPSILON {
+        return Err(anyhow!("The root is incorrect: {} vs. {x}", f64::cbrt(2.)));
+    }
+
+    // Check that an interval without a sign change is rejected
+    if bisection(|x: f64| Ok(x.powi(2) + 1.), -1., 1.).is_ok() {
+        return Err(anyhow!(
+            "The interval without a sign change wasn't rejected"
+        ));
+    }
+
+    Ok(())
+}
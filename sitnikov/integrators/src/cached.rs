@@ -0,0 +1,123 @@
+//! Provides the [`CachedIntegrator`](crate::CachedIntegrator) struct
+
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+
+use crate::{Float, SymplecticIntegrator};
+
+/// An entry of the cache: time, state and accelerations
+type Entry<F> = (F, Vec<F>, Vec<F>);
+
+/// A wrapper around a symplectic integrator which memoizes the
+/// last few computed accelerations, delegating to the wrapped
+/// integrator on a miss
+pub struct Integrator<F: Float, T: SymplecticIntegrator<F>> {
+    /// Wrapped integrator
+    inner: T,
+    /// Maximum number of entries in the cache
+    capacity: usize,
+    /// Cached entries (the most recently used first)
+    cache: RefCell<VecDeque<Entry<F>>>,
+    /// Number of cache hits
+    hits: Cell<usize>,
+}
+
+impl<F: Float, T: SymplecticIntegrator<F>> Integrator<F, T> {
+    /// Wrap the integrator
+    ///
+    /// Arguments:
+    /// * `inner` --- Integrator to wrap;
+    /// * `capacity` --- Maximum number of cached entries.
+    pub fn new(inner: T, capacity: usize) -> Self {
+        Self {
+            inner,
+            capacity,
+            cache: RefCell::new(VecDeque::with_capacity(capacity)),
+            hits: Cell::new(0),
+        }
+    }
+    /// Get the wrapped integrator
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+    /// Get the number of cache hits
+    pub fn hits(&self) -> usize {
+        self.hits.get()
+    }
+}
+
+impl<F: Float, T: SymplecticIntegrator<F>> SymplecticIntegrator<F> for Integrator<F, T> {
+    fn accelerations(&self, t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+        let mut cache = self.cache.borrow_mut();
+        // If the accelerations are cached, move the entry to the front and return them
+        if let Some(i) = cache.iter().position(|(c_t, c_x, _)| *c_t == t && c_x == x) {
+            self.hits.set(self.hits.get() + 1);
+            let entry = cache.remove(i).unwrap();
+            let a = entry.2.clone();
+            cache.push_front(entry);
+            return Ok(a);
+        }
+        // Otherwise, compute them and put them in the cache
+        let a = self.inner.accelerations(t, x)?;
+        if self.capacity > 0 {
+            if cache.len() == self.capacity {
+                cache.pop_back();
+            }
+            cache.push_front((t, x.to_vec(), a.clone()));
+        }
+        Ok(a)
+    }
+    fn energy(&self, t: F, x: &[F]) -> Option<F> {
+        self.inner.energy(t, x)
+    }
+    fn dim(&self) -> Option<usize> {
+        self.inner.dim()
+    }
+}
+
+#[test]
+fn test() -> anyhow::Result<()> {
+    use crate::{ResultExt, SymplecticIntegrators};
+
+    // Implement the trait on a test struct
+    struct Test {
+        calls: Cell<usize>,
+    }
+    impl<F: Float> SymplecticIntegrator<F> for Test {
+        fn accelerations(&self, t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            self.calls.set(self.calls.get() + 1);
+            Ok(vec![t - x[0]])
+        }
+    }
+    let test = Test {
+        calls: Cell::new(0),
+    };
+    let cached = Integrator::new(
+        Test {
+            calls: Cell::new(0),
+        },
+        4,
+    );
+
+    // Integrate using both integrators and compare the results
+    let x = [1., 0., -1.];
+    let result = test.integrate(&x, 0., 1e-2, 100, SymplecticIntegrators::Yoshida4th)?;
+    let result_cached = cached.integrate(&x, 0., 1e-2, 100, SymplecticIntegrators::Yoshida4th)?;
+    if result != result_cached {
+        return Err(anyhow::anyhow!(
+            "The cached integrator gives different results"
+        ));
+    }
+
+    // Check that a repeated call hits the cache
+    let calls = cached.inner().calls.get();
+    let hits = cached.hits();
+    let state = result_cached.state(100);
+    let a = cached.accelerations(1., &state)?;
+    let a_repeated = cached.accelerations(1., &state)?;
+    if a != a_repeated || cached.inner().calls.get() != calls + 1 || cached.hits() != hits + 1 {
+        return Err(anyhow::anyhow!("The repeated call didn't hit the cache"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,192 @@
+//! Provides the [`Parameters`] struct and the
+//! [`save_checkpoint`] and [`load_checkpoint`] routines
+
+use anyhow::Context;
+use bincode::Options;
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+use crate::Float;
+
+/// Parameters of an integration (a checkpoint
+/// is loaded only if they're the same)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Parameters<F: Float> {
+    /// Length of the state
+    pub len: usize,
+    /// Initial value of time
+    pub t_0: F,
+    /// Time step
+    pub h: F,
+    /// Number of iterations
+    pub n: usize,
+    /// Name of the integration method
+    pub integrator: String,
+}
+
+/// Stored parameters of an integration: length of the state,
+/// initial value of time, time step, number of iterations
+/// and name of the integration method
+type StoredParameters = (u64, f64, f64, u64, String);
+
+/// A checkpoint: parameters of the integration, index of the step,
+/// time moment and state (the values are stored in double precision,
+/// which is exact for both float types)
+type Checkpoint = (StoredParameters, u64, f64, Vec<f64>);
+
+impl<F: Float> Parameters<F> {
+    /// Convert the parameters to the stored form
+    fn store(&self) -> StoredParameters {
+        (
+            self.len as u64,
+            self.t_0.to_f64().unwrap(),
+            self.h.to_f64().unwrap(),
+            self.n as u64,
+            self.integrator.clone(),
+        )
+    }
+}
+
+/// Get the options of the serialization
+fn options() -> impl Options {
+    bincode::DefaultOptions::new()
+        .with_native_endian()
+        .with_fixint_encoding()
+}
+
+/// Save a checkpoint of an integration into the file
+///
+/// Arguments:
+/// * `path` --- Path to the file;
+/// * `parameters` --- Parameters of the integration;
+/// * `step` --- Index of the step;
+/// * `state` --- State of the system;
+/// * `t` --- Time moment of the state.
+pub fn save_checkpoint<F: Float>(
+    path: &Path,
+    parameters: &Parameters<F>,
+    step: usize,
+    state: &[F],
+    t: F,
+) -> anyhow::Result<()> {
+    let checkpoint: Checkpoint = (
+        parameters.store(),
+        step as u64,
+        t.to_f64().unwrap(),
+        state.iter().map(|x| x.to_f64().unwrap()).collect(),
+    );
+    let file = File::create(path)
+        .with_context(|| format!("Couldn't create the checkpoint file {}", path.display()))?;
+    options()
+        .serialize_into(BufWriter::new(file), &checkpoint)
+        .with_context(|| format!("Couldn't serialize the checkpoint into {}", path.display()))?;
+    Ok(())
+}
+
+/// Load a checkpoint of an integration from the file, check that it was
+/// saved with the same parameters of the integration, return the index
+/// of the step, the state and its time moment
+///
+/// Arguments:
+/// * `path` --- Path to the file;
+/// * `parameters` --- Parameters of the integration.
+pub fn load_checkpoint<F: Float>(
+    path: &Path,
+    parameters: &Parameters<F>,
+) -> anyhow::Result<(usize, Vec<F>, F)> {
+    let file = File::open(path)
+        .with_context(|| format!("Couldn't open the checkpoint file {}", path.display()))?;
+    let (stored, step, t, state): Checkpoint = options()
+        .deserialize_from(BufReader::new(file))
+        .with_context(|| {
+            format!(
+                "Couldn't deserialize the checkpoint from {}",
+                path.display()
+            )
+        })?;
+    let expected = parameters.store();
+    if stored != expected || state.len() != parameters.len {
+        return Err(anyhow::anyhow!(
+            "The checkpoint {} was saved by a different integration: \
+            {stored:?} vs. {expected:?} (the length of the state, the \
+            initial value of time, the time step, the number of iterations \
+            and the integration method)",
+            path.display()
+        ));
+    }
+    Ok((
+        usize::try_from(step)?,
+        state.into_iter().map(|x| F::from(x).unwrap()).collect(),
+        F::from(t).unwrap(),
+    ))
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test() -> anyhow::Result<()> {
+    // Save checkpoints in both precisions and load them back
+    let path = std::env::temp_dir().join("integrators-test-checkpoint.bin");
+    let parameters = Parameters {
+        len: 3,
+        t_0: 0.,
+        h: 1e-2,
+        n: 100,
+        integrator: String::from("Test"),
+    };
+    let state = [1. / 3., -f64::MIN_POSITIVE, 1e300];
+    save_checkpoint(&path, &parameters, 42, &state, 0.1)?;
+    let (step, state_loaded, t) = load_checkpoint::<f64>(&path, &parameters)?;
+    let parameters_single = Parameters {
+        len: 3,
+        t_0: 0.,
+        h: 1e-2,
+        n: 100,
+        integrator: String::from("Test"),
+    };
+    let state_single = [1_f32 / 3., -f32::MIN_POSITIVE, 1e30];
+    save_checkpoint(&path, &parameters_single, 42, &state_single, 0.1)?;
+    let (_, state_single_loaded, t_single) = load_checkpoint::<f32>(&path, &parameters_single)?;
+
+    // Check that the checkpoint isn't loaded with different parameters
+    let mismatched = [
+        Parameters {
+            len: 2,
+            ..parameters_single.clone()
+        },
+        Parameters {
+            h: 2e-2,
+            ..parameters_single.clone()
+        },
+        Parameters {
+            n: 200,
+            ..parameters_single.clone()
+        },
+        Parameters {
+            integrator: String::from("Other"),
+            ..parameters_single
+        },
+    ];
+    let loaded = mismatched
+        .iter()
+        .any(|parameters| load_checkpoint::<f32>(&path, parameters).is_ok());
+    std::fs::remove_file(&path)?;
+    if loaded {
+        return Err(anyhow::anyhow!(
+            "The checkpoint is loaded with different parameters"
+        ));
+    }
+
+    // Compare the values
+    if step != 42 || state_loaded != state || t != 0.1 {
+        return Err(anyhow::anyhow!("The loaded checkpoint is different"));
+    }
+    if state_single_loaded != state_single || t_single != 0.1 {
+        return Err(anyhow::anyhow!(
+            "The loaded checkpoint is different in single precision"
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,125 @@
+//! Provides the [`Coefficients`](crate::Coefficients) struct
+
+use lazy_static::lazy_static;
+use num::Float as NumFloat;
+use numeric_literals::replace_float_literals;
+
+use crate::pairs::{self, Tableau};
+
+lazy_static! {
+    /// Coefficients in single precision
+    pub(crate) static ref F32: Coefficients<f32> = Coefficients::new();
+    /// Coefficients in double precision
+    pub(crate) static ref F64: Coefficients<f64> = Coefficients::new();
+}
+
+/// Coefficients of the integration methods
+///
+/// These are computed in the precision of the floating-point type
+/// itself (rather than computed in the biggest type and then cast),
+/// and cached once per type (see [`Float::coefficients`](crate::Float::coefficients))
+#[derive(Debug)]
+pub struct Coefficients<F> {
+    /// The first coefficient in the 4th-order Yoshida method
+    pub d_1: F,
+    /// The second coefficient in the 4th-order Yoshida method
+    pub d_2: F,
+    /// The sum of the first two coefficients in the 4th-order Yoshida method
+    pub d_3: F,
+    /// The diagonal coefficient in the 2nd-order Rosenbrock method
+    pub gamma: F,
+    /// The coefficients of the substeps in the 6th-order Yoshida
+    /// method (in the order of the substeps: `w_3`, `w_2`, `w_1`,
+    /// `w_0`, `w_1`, `w_2`, `w_3`)
+    pub w: [F; 7],
+    /// The `xi` coefficient in the PEFRL method
+    pub xi: F,
+    /// The `lambda` coefficient in the PEFRL method
+    pub lambda: F,
+    /// The `chi` coefficient in the PEFRL method
+    pub chi: F,
+    /// The Butcher tableau of the Cash-Karp pair
+    pub(crate) cash_karp: Tableau<F>,
+    /// The Butcher tableau of the Dormand-Prince pair
+    pub(crate) dormand_prince: Tableau<F>,
+    /// The Butcher tableau of the Runge-Kutta-Fehlberg pair
+    pub(crate) fehlberg: Tableau<F>,
+}
+
+#[replace_float_literals(F::from(literal).unwrap())]
+impl<F: NumFloat> Coefficients<F> {
+    /// Compute the coefficients
+    fn new() -> Self {
+        let d_1 = 1. / (2. - F::exp(F::ln(2.) / 3.));
+        let d_2 = 1. - 2. * d_1;
+        // See H. Yoshida, 1990 (solution A)
+        let w_1 = -1.177_679_984_178_871;
+        let w_2 = 0.235_573_213_359_358_1;
+        let w_3 = 0.784_513_610_477_557_3;
+        let w_0 = 1. - 2. * (w_1 + w_2 + w_3);
+        Self {
+            d_1,
+            d_2,
+            d_3: d_1 + d_2,
+            gamma: 1. + 1. / F::sqrt(2.),
+            w: [w_3, w_2, w_1, w_0, w_1, w_2, w_3],
+            // See I. P. Omelyan et al., 2002
+            xi: 0.178_617_895_844_809_1,
+            lambda: -0.212_341_831_062_605_4,
+            chi: -0.066_264_582_669_818_5,
+            cash_karp: pairs::CASH_KARP.compute(),
+            dormand_prince: pairs::DORMAND_PRINCE.compute(),
+            fehlberg: pairs::FEHLBERG.compute(),
+        }
+    }
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test() -> anyhow::Result<()> {
+    use crate::Float;
+
+    // Compute the coefficients in double precision the old way
+    let d_1 = 1. / (2. - f64::exp(f64::ln(2.) / 3.));
+    let d_2 = 1. - 2. * d_1;
+    let gamma = 1. + 1. / f64::sqrt(2.);
+
+    // Compare them to the cached ones (including an entry of
+    // the Dormand-Prince tableau, which was stored in `f64`)
+    let c = f64::coefficients();
+    if [
+        c.d_1,
+        c.d_2,
+        c.d_3,
+        c.gamma,
+        c.dormand_prince.weights_low[4],
+    ] != [d_1, d_2, d_1 + d_2, gamma, -92097. / 339_200.]
+    {
+        return Err(anyhow::anyhow!(
+            "The coefficients in double precision are incorrect: {c:?}"
+        ));
+    }
+
+    // Compute the coefficients in single precision
+    let d_1 = 1. / (2. - f32::exp(f32::ln(2.) / 3.));
+    let d_2 = 1. - 2. * d_1;
+    let gamma = 1. + 1. / f32::sqrt(2.);
+
+    // Compare them to the cached ones (the entry of the
+    // Dormand-Prince tableau is computed in `f32`, too)
+    let c = f32::coefficients();
+    if [
+        c.d_1,
+        c.d_2,
+        c.d_3,
+        c.gamma,
+        c.dormand_prince.weights_low[4],
+    ] != [d_1, d_2, d_1 + d_2, gamma, -92097. / 339_200.]
+    {
+        return Err(anyhow::anyhow!(
+            "The coefficients in single precision are incorrect: {c:?}"
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,188 @@
+//! Provides the [`ComplexIntegrator`](crate::ComplexIntegrator) trait
+//! and the [`ComplexSolution`](crate::ComplexSolution) struct
+
+use nalgebra::Dynamic;
+use num::Complex;
+
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use crate::{Float, GeneralIntegrator, GeneralIntegrators, Result};
+
+/// Split the complex values into the pairs of their real and imaginary parts
+fn split<F: Float>(x: &[Complex<F>]) -> Vec<F> {
+    x.iter().flat_map(|z| [z.re, z.im]).collect()
+}
+
+/// Join the pairs of the real and imaginary parts into the complex values
+fn join<F: Float>(x: &[F]) -> Vec<Complex<F>> {
+    x.chunks(2).map(|c| Complex::new(c[0], c[1])).collect()
+}
+
+/// A solution of the system with a complex-valued state: the result
+/// matrix and the time moments of its states
+///
+/// The struct dereferences to the result matrix, so the callers
+/// interested only in the states can use it as such
+#[derive(Clone, Debug, PartialEq)]
+pub struct Solution<F: Float> {
+    /// Result matrix
+    states: Result<Complex<F>>,
+    /// Time moments of the states
+    times: Vec<F>,
+}
+
+impl<F: Float> Solution<F> {
+    /// Join the pairs of the real and imaginary parts in the
+    /// solution of the real-valued view of the system
+    fn from_real(real: &crate::Solution<F>) -> Self {
+        let states = Result::from_fn_generic(
+            Dynamic::new(real.nrows() / 2),
+            Dynamic::new(real.ncols()),
+            |i, j| Complex::new(real[(2 * i, j)], real[(2 * i + 1, j)]),
+        );
+        Self {
+            states,
+            times: real.times().to_vec(),
+        }
+    }
+    /// Get the time moments of the states
+    #[must_use]
+    pub fn times(&self) -> &[F] {
+        &self.times
+    }
+    /// Get the `i`-th state of the system
+    #[must_use]
+    pub fn state(&self, i: usize) -> Vec<Complex<F>> {
+        self.states.column(i).iter().copied().collect()
+    }
+    /// Unwrap the result matrix
+    #[must_use]
+    pub fn into_states(self) -> Result<Complex<F>> {
+        self.states
+    }
+}
+
+impl<F: Float> Deref for Solution<F> {
+    type Target = Result<Complex<F>>;
+    fn deref(&self) -> &Self::Target {
+        &self.states
+    }
+}
+
+/// A real-valued view of a complex-valued system (the real and
+/// imaginary parts of each component of the state go in pairs)
+struct Real<'a, F: Float, T: ?Sized> {
+    /// Complex-valued system
+    inner: &'a T,
+    /// Marker of the floating point type
+    float: PhantomData<F>,
+}
+
+impl<F: Float, T: Integrator<F> + ?Sized> GeneralIntegrator<F> for Real<'_, F, T> {
+    fn update(&self, t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+        Ok(split(&self.inner.update(t, &join(x))?))
+    }
+    fn dim(&self) -> Option<usize> {
+        // Each complex component is split into two real ones
+        self.inner.dim().map(|dim| 2 * dim)
+    }
+}
+
+/// An integrator for a system of 1st-order ODEs with a complex-valued state
+///
+/// The system is integrated as a real-valued one of twice the dimension,
+/// so any of the general integration methods can be used
+pub trait Integrator<F: Float> {
+    /// Update the current state as defined by a
+    /// system of 1st-order ODEs, return the result
+    ///
+    /// Arguments:
+    /// * `t` --- Current time moment;
+    /// * `x` --- Current state of the system.
+    fn update(&self, t: F, x: &[Complex<F>]) -> anyhow::Result<Vec<Complex<F>>>;
+    /// Get the length of the state of the system (if it's defined,
+    /// the vectors of initial values are checked against it)
+    fn dim(&self) -> Option<usize> {
+        None
+    }
+    /// Integrate the system of 1st-order ODEs, return the solution
+    ///
+    /// Arguments:
+    /// * `x` --- Vector of initial values;
+    /// * `t_0` --- Initial value of time;
+    /// * `h` --- Time step;
+    /// * `n` --- Number of iterations;
+    /// * `integrator` --- Integration method.
+    fn integrate(
+        &self,
+        x: &[Complex<F>],
+        t_0: F,
+        h: F,
+        n: usize,
+        integrator: GeneralIntegrators,
+    ) -> anyhow::Result<Solution<F>> {
+        let real = Real {
+            inner: self,
+            float: PhantomData,
+        };
+        let solution = real.integrate(&split(x), t_0, h, n, integrator)?;
+        Ok(Solution::from_real(&solution))
+    }
+}
+
+#[test]
+fn test() -> anyhow::Result<()> {
+    // Implement the trait on a test struct (`y' = i y`)
+    struct Test {}
+    impl<F: Float> Integrator<F> for Test {
+        fn update(&self, _t: F, x: &[Complex<F>]) -> anyhow::Result<Vec<Complex<F>>> {
+            Ok(vec![Complex::<F>::i() * x[0]])
+        }
+        fn dim(&self) -> Option<usize> {
+            Some(1)
+        }
+    }
+    let test = Test {};
+
+    // Check that the dimension is checked (in complex components)
+    if Integrator::<f64>::integrate(
+        &test,
+        &[Complex::new(1., 0.), Complex::new(0., 1.)],
+        0.,
+        1e-2,
+        1,
+        GeneralIntegrators::RungeKutta4th,
+    )
+    .is_ok()
+    {
+        return Err(anyhow::anyhow!(
+            "A state of the wrong dimension was accepted"
+        ));
+    }
+
+    // Integrate, check that the solution traces the unit circle
+    let h = 1e-2;
+    let solution = test.integrate(
+        &[Complex::new(1_f64, 0.)],
+        0.,
+        h,
+        700,
+        GeneralIntegrators::RungeKutta4th,
+    )?;
+    if solution.ncols() != 701
+        || solution.times().len() != 701
+        || solution.times().iter().enumerate().any(|(i, &t)| {
+            let x = solution.state(i)[0];
+            (t - h * f64::from(u32::try_from(i).unwrap())).abs() >= 1e-12
+                || (x.norm() - 1.).abs() >= 1e-8
+                || (x - Complex::new(t.cos(), t.sin())).norm() >= 1e-8
+        })
+    {
+        return Err(anyhow::anyhow!(
+            "The solution doesn't trace the unit circle"
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,129 @@
+//! Provides the [`CountedIntegrator`](crate::CountedIntegrator) struct
+
+use nalgebra::DMatrix;
+
+use std::cell::Cell;
+
+use crate::{Float, GeneralIntegrator, SymplecticIntegrator};
+
+/// A wrapper around an integrator which counts the evaluations of the
+/// right-hand side (or accelerations), delegating to the wrapped integrator
+///
+/// This is the true cost metric of the adaptive and implicit methods.
+/// The counting is opt-in: the unwrapped integrators don't pay for it
+pub struct Integrator<T> {
+    /// Wrapped integrator
+    inner: T,
+    /// Number of evaluations
+    evals: Cell<usize>,
+}
+
+impl<T> Integrator<T> {
+    /// Wrap the integrator
+    ///
+    /// Arguments:
+    /// * `inner` --- Integrator to wrap.
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            evals: Cell::new(0),
+        }
+    }
+    /// Get the wrapped integrator
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+    /// Get the number of evaluations
+    pub fn evals(&self) -> usize {
+        self.evals.get()
+    }
+    /// Reset the number of evaluations
+    pub fn reset(&self) {
+        self.evals.set(0);
+    }
+    /// Increment the number of evaluations
+    fn count(&self) {
+        self.evals.set(self.evals.get() + 1);
+    }
+}
+
+impl<F: Float, T: GeneralIntegrator<F>> GeneralIntegrator<F> for Integrator<T> {
+    fn update(&self, t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+        self.count();
+        self.inner.update(t, x)
+    }
+    fn analytic_jacobian(&self, t: F, x: &[F]) -> Option<DMatrix<F>> {
+        self.inner.analytic_jacobian(t, x)
+    }
+    fn dim(&self) -> Option<usize> {
+        self.inner.dim()
+    }
+}
+
+impl<F: Float, T: SymplecticIntegrator<F>> SymplecticIntegrator<F> for Integrator<T> {
+    fn accelerations(&self, t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+        self.count();
+        self.inner.accelerations(t, x)
+    }
+    fn energy(&self, t: F, x: &[F]) -> Option<F> {
+        self.inner.energy(t, x)
+    }
+    fn dim(&self) -> Option<usize> {
+        self.inner.dim()
+    }
+}
+
+#[test]
+fn test() -> anyhow::Result<()> {
+    use crate::{GeneralIntegrators, SymplecticIntegrators};
+
+    // Implement the traits on test structs
+    struct Test {}
+    impl<F: Float> GeneralIntegrator<F> for Test {
+        fn update(&self, _t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![x[1], -x[0]])
+        }
+    }
+    struct TestSymplectic {}
+    impl<F: Float> SymplecticIntegrator<F> for TestSymplectic {
+        fn accelerations(&self, _t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![-x[0]])
+        }
+    }
+    let (counted, counted_symplectic) =
+        (Integrator::new(Test {}), Integrator::new(TestSymplectic {}));
+
+    // Check the numbers of evaluations of the fixed-step methods
+    let n = 100;
+    let result = counted.integrate(&[1., 0.], 0., 1e-2, n, GeneralIntegrators::RungeKutta4th)?;
+    let result_inner =
+        counted
+            .inner()
+            .integrate(&[1., 0.], 0., 1e-2, n, GeneralIntegrators::RungeKutta4th)?;
+    if result != result_inner {
+        return Err(anyhow::anyhow!(
+            "The counted integrator gives different results"
+        ));
+    }
+    counted_symplectic.integrate(&[1., 0., -1.], 0., 1e-2, n, SymplecticIntegrators::Leapfrog)?;
+    if counted.evals() != 4 * n || counted_symplectic.evals() != n {
+        return Err(anyhow::anyhow!(
+            "The numbers of evaluations are incorrect: {} and {}",
+            counted.evals(),
+            counted_symplectic.evals()
+        ));
+    }
+
+    // Check the number of evaluations of the adaptive method (there
+    // are 6 of them per step, including the rejected ones)
+    counted.reset();
+    let result = counted.integrate_adaptive(&[1., 0.], 0., 10., 1e-2, (1e-8, 1e-8))?;
+    let evals = counted.evals();
+    if evals % 6 != 0 || evals < 6 * (result.ncols() - 1) {
+        return Err(anyhow::anyhow!(
+            "The number of evaluations of the adaptive method is incorrect: {evals}"
+        ));
+    }
+
+    Ok(())
+}
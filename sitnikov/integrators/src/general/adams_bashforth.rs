@@ -0,0 +1,178 @@
+//! Provides the [`adams_bashforth`] macro, plus tests for the method
+
+/// Defines the [`adams_bashforth`](crate::GeneralIntegrator#method.adams_bashforth) method
+macro_rules! adams_bashforth {
+    () => {
+        /// Integrate the system using the explicit Adams-Bashforth method
+        ///
+        /// This is a multistep method: each step reuses the right-hand
+        /// sides computed on the previous `steps - 1` steps, so only one
+        /// new evaluation is needed. The first `steps - 1` steps are made
+        /// using the 4th-order Runge-Kutta method.
+        ///
+        /// Arguments:
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Number of iterations;
+        /// * `steps` --- Number of steps (from 1 to 4, which is also the order);
+        /// * `result` --- Solution;
+        /// * `callback` --- Function called after each step (returns `false` to stop);
+        /// * `token` --- Private token.
+        #[allow(clippy::too_many_arguments)]
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn adams_bashforth(
+            &self,
+            t_0: F,
+            h: F,
+            n: usize,
+            steps: usize,
+            result: &mut Solution<F>,
+            callback: &mut dyn FnMut(usize, F, &[F]) -> bool,
+            token: &Token,
+        ) -> anyhow::Result<()> {
+            // Choose the coefficients (for the latest right-hand side first)
+            let b: Vec<F> = match steps {
+                1 => vec![1.],
+                2 => vec![3. / 2., -1. / 2.],
+                3 => vec![23. / 12., -16. / 12., 5. / 12.],
+                4 => vec![55. / 24., -59. / 24., 37. / 24., -9. / 24.],
+                _ => {
+                    return Err(anyhow::anyhow!(
+                        "The number of steps must be from 1 to 4, got {steps}"
+                    ))
+                }
+            };
+            // Get the initial state
+            let mut x = result.initial_values();
+            // Prepare a buffer for the bootstrap steps
+            let mut buffer = self.prepare(&x, t_0, 1, token)?;
+            // Prepare a buffer for the past right-hand sides (the latest first)
+            let mut history: VecDeque<Vec<F>> = VecDeque::with_capacity(steps);
+            // Integrate
+            for i in 0..n {
+                // Compute the time moment
+                let t = t_0 + F::from(i).unwrap() * h;
+                // Put the right-hand side of the current state in the history
+                if history.len() == steps {
+                    history.pop_back();
+                }
+                history.push_front(
+                    self.update(t, &x)
+                        .with_context(|| "Couldn't compute the right-hand side")?,
+                );
+                // Compute the next state (bootstrapping
+                // until the history is complete)
+                x = if history.len() < steps {
+                    self.step(&mut buffer, t, &x, h, Integrators::RungeKutta4th, token)
+                        .with_context(|| format!("Couldn't make a bootstrap step at t = {t}"))?
+                } else {
+                    (0..x.len())
+                        .map(|j| {
+                            x[j] + h * (0..steps).fold(0., |acc, k| acc + b[k] * history[k][j])
+                        })
+                        .collect()
+                };
+                // Put the new state in the result
+                result.set_state_from_slice(i + 1, &x);
+                result.set_time(i + 1, t_0 + F::from(i + 1).unwrap() * h);
+                if !callback(i + 1, result.times()[i + 1], &x) {
+                    break;
+                }
+            }
+            Ok(())
+        }
+    };
+}
+
+pub(super) use adams_bashforth;
+
+#[test]
+#[allow(clippy::cast_precision_loss)]
+fn test() -> anyhow::Result<()> {
+    use crate::{Float, GeneralIntegrator, GeneralIntegrators, ResultExt};
+
+    // Implement the trait on a test struct
+    type F = f64;
+    struct Test {}
+    impl<F: Float> GeneralIntegrator<F> for Test {
+        fn update(&self, t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![t, x[0] * F::sin(t)])
+        }
+    }
+    let test = Test {};
+
+    // Define the exact solution
+    let exact = |t: F| {
+        vec![
+            t.powi(2) / 2.,
+            -t.powi(2) / 2. * F::cos(t) + t * F::sin(t) + F::cos(t) - 1.,
+        ]
+    };
+
+    // Define the integration parameters
+    let x = [0., 0.];
+    let h = 1e-2;
+    let n = 3000;
+
+    // (the error constant of the 2-step method is too big for the default factor)
+    for (steps, factor) in [(2, 1e2), (4, 10.)] {
+        // Integrate
+        let integrator = GeneralIntegrators::AdamsBashforth { steps };
+        let order = i32::try_from(integrator.order())?;
+        let result = test.integrate(&x, 0., h, n, integrator)?;
+
+        // Compare the bootstrapped states with the ones
+        // computed by the 4th-order Runge-Kutta method
+        let rk4 = test.integrate(&x, 0., h, steps - 1, GeneralIntegrators::RungeKutta4th)?;
+        if (0..steps).any(|i| result.state(i) != rk4.state(i)) {
+            return Err(anyhow::anyhow!(
+                "The bootstrapped states are incorrect ({steps} steps)"
+            ));
+        }
+
+        // Compare the final state with the exact solution
+        let (x, x_0) = (result.state(n), exact(h * n as F));
+        if x.iter()
+            .zip(x_0.iter())
+            .any(|(&x, &x_0)| (x - x_0).abs() >= factor * h.powi(order))
+        {
+            return Err(anyhow::anyhow!(
+                "The result of integration is not the same as expected \
+                ({steps} steps): {x_0:?} vs {x:?}"
+            ));
+        }
+    }
+
+    // Check that fewer iterations than steps are all bootstrapped
+    let result = test.integrate(
+        &x,
+        0.,
+        h,
+        2,
+        GeneralIntegrators::AdamsBashforth { steps: 4 },
+    )?;
+    let rk4 = test.integrate(&x, 0., h, 2, GeneralIntegrators::RungeKutta4th)?;
+    if result != rk4 {
+        return Err(anyhow::anyhow!(
+            "The iterations before the history is complete weren't bootstrapped"
+        ));
+    }
+
+    // Check that an unsupported number of steps is rejected
+    if test
+        .integrate(
+            &x,
+            0.,
+            h,
+            n,
+            GeneralIntegrators::AdamsBashforth { steps: 5 },
+        )
+        .is_ok()
+    {
+        return Err(anyhow::anyhow!(
+            "The unsupported number of steps wasn't rejected"
+        ));
+    }
+
+    Ok(())
+}
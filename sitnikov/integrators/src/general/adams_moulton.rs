@@ -0,0 +1,195 @@
+//! Provides the [`adams_moulton`] macro, plus tests for the method
+
+/// Defines the [`adams_moulton`](crate::GeneralIntegrator#method.adams_moulton) method
+macro_rules! adams_moulton {
+    () => {
+        /// Integrate the system using the Adams-Bashforth-Moulton
+        /// predictor-corrector method
+        ///
+        /// On each iteration, the next state is predicted by the explicit
+        /// Adams-Bashforth method and corrected by the implicit Adams-Moulton
+        /// method of the same order (each correction sweep costs one evaluation
+        /// of the right-hand side). The first `steps - 1` steps are made using
+        /// the 4th-order Runge-Kutta method. The maximum change of the state
+        /// in the last correction sweep is recorded as the corrector residual
+        /// (it's zero in the bootstrapped states).
+        ///
+        /// Arguments:
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Number of iterations;
+        /// * `steps` --- Number of steps of the predictor (from 1 to 4, which is also the order);
+        /// * `corrections` --- Number of correction sweeps (at least one);
+        /// * `result` --- Solution;
+        /// * `callback` --- Function called after each step (returns `false` to stop);
+        /// * `token` --- Private token.
+        #[allow(clippy::too_many_arguments)]
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn adams_moulton(
+            &self,
+            t_0: F,
+            h: F,
+            n: usize,
+            steps: usize,
+            corrections: usize,
+            result: &mut Solution<F>,
+            callback: &mut dyn FnMut(usize, F, &[F]) -> bool,
+            token: &Token,
+        ) -> anyhow::Result<()> {
+            // Choose the coefficients of the predictor and the corrector
+            // (for the latest right-hand side first)
+            let (b, c): (Vec<F>, Vec<F>) = match steps {
+                1 => (vec![1.], vec![1.]),
+                2 => (vec![3. / 2., -1. / 2.], vec![1. / 2., 1. / 2.]),
+                3 => (
+                    vec![23. / 12., -16. / 12., 5. / 12.],
+                    vec![5. / 12., 8. / 12., -1. / 12.],
+                ),
+                4 => (
+                    vec![55. / 24., -59. / 24., 37. / 24., -9. / 24.],
+                    vec![9. / 24., 19. / 24., -5. / 24., 1. / 24.],
+                ),
+                _ => {
+                    return Err(anyhow::anyhow!(
+                        "The number of steps must be from 1 to 4, got {steps}"
+                    ))
+                }
+            };
+            if corrections == 0 {
+                return Err(anyhow::anyhow!("At least one correction sweep is required"));
+            }
+            // Get the initial state
+            let mut x = result.initial_values();
+            // Prepare a buffer for the bootstrap steps
+            let mut buffer = self.prepare(&x, t_0, 1, token)?;
+            // Prepare a buffer for the past right-hand sides (the latest first)
+            let mut history: VecDeque<Vec<F>> = VecDeque::with_capacity(steps);
+            // Prepare a vector of the corrector residuals
+            let mut residuals = Vec::with_capacity(n + 1);
+            residuals.push(0.);
+            // Integrate
+            for i in 0..n {
+                // Compute the time moment
+                let t = t_0 + F::from(i).unwrap() * h;
+                // Put the right-hand side of the current state in the history
+                if history.len() == steps {
+                    history.pop_back();
+                }
+                history.push_front(
+                    self.update(t, &x)
+                        .with_context(|| "Couldn't compute the right-hand side")?,
+                );
+                // Compute the next state (bootstrapping
+                // until the history is complete)
+                if history.len() < steps {
+                    x = self
+                        .step(&mut buffer, t, &x, h, Integrators::RungeKutta4th, token)
+                        .with_context(|| format!("Couldn't make a bootstrap step at t = {t}"))?;
+                    residuals.push(0.);
+                } else {
+                    // Predict the next state
+                    let mut x_c: Vec<F> = (0..x.len())
+                        .map(|j| {
+                            x[j] + h * (0..steps).fold(0., |acc, k| acc + b[k] * history[k][j])
+                        })
+                        .collect();
+                    // Correct it
+                    let mut residual = 0.;
+                    for _ in 0..corrections {
+                        let f = self
+                            .update(t + h, &x_c)
+                            .with_context(|| "Couldn't compute the corrected right-hand side")?;
+                        residual = 0.;
+                        for j in 0..x.len() {
+                            let x_j = x[j]
+                                + h * (c[0] * f[j]
+                                    + (1..steps).fold(0., |acc, k| acc + c[k] * history[k - 1][j]));
+                            residual = F::max(residual, (x_j - x_c[j]).abs());
+                            x_c[j] = x_j;
+                        }
+                    }
+                    x = x_c;
+                    residuals.push(residual);
+                }
+                // Put the new state in the result
+                result.set_state_from_slice(i + 1, &x);
+                result.set_time(i + 1, t_0 + F::from(i + 1).unwrap() * h);
+                if !callback(i + 1, result.times()[i + 1], &x) {
+                    break;
+                }
+            }
+            result.set_residuals(residuals);
+            Ok(())
+        }
+    };
+}
+
+pub(super) use adams_moulton;
+
+#[cfg(test)]
+super::test_method::test_method!(GeneralIntegrators::AdamsMoulton {
+    steps: 4,
+    corrections: 1
+});
+
+#[test]
+fn test_cost() -> anyhow::Result<()> {
+    use crate::{Float, GeneralIntegrator, GeneralIntegrators, ResultExt};
+    use std::cell::Cell;
+
+    // Implement the trait on a test struct, counting the calls
+    struct Test {
+        calls: Cell<usize>,
+    }
+    impl<F: Float> GeneralIntegrator<F> for Test {
+        fn update(&self, t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            self.calls.set(self.calls.get() + 1);
+            Ok(vec![t, x[0] * F::sin(t)])
+        }
+    }
+    let test = Test {
+        calls: Cell::new(0),
+    };
+
+    // Integrate using the 4th-order Runge-Kutta method
+    let (x, h, n) = ([0_f64, 0.], 1e-2, 3000);
+    let rk4 = test.integrate(&x, 0., h, n, GeneralIntegrators::RungeKutta4th)?;
+    let calls_rk4 = test.calls.replace(0);
+
+    // Integrate using the predictor-corrector method with one and two sweeps
+    let integrator = |corrections| GeneralIntegrators::AdamsMoulton {
+        steps: 4,
+        corrections,
+    };
+    let abm = test.integrate(&x, 0., h, n, integrator(1))?;
+    let calls_abm = test.calls.replace(0);
+    let abm_2 = test.integrate(&x, 0., h, n, integrator(2))?;
+
+    // Check that the accuracy is comparable, while the cost is about
+    // half as big (two evaluations per iteration instead of four,
+    // plus the bootstrap)
+    let error = (abm.state(n)[1] - rk4.state(n)[1]).abs();
+    if error >= 1e-6 || calls_abm > calls_rk4 / 2 + 16 {
+        return Err(anyhow::anyhow!(
+            "The predictor-corrector method is not as expected: \
+            error {error}, {calls_abm} vs. {calls_rk4} calls"
+        ));
+    }
+
+    // Check the residuals (more sweeps make them smaller)
+    let (residuals, residuals_2) = (abm.residuals(), abm_2.residuals());
+    let max = |r: &[f64]| r.iter().fold(0., |acc: f64, &r| acc.max(r));
+    if residuals.len() != n + 1
+        || residuals[..4].iter().any(|&r| r > 0.)
+        || max(residuals) >= 1e-6
+        || max(residuals_2) >= max(residuals)
+    {
+        return Err(anyhow::anyhow!(
+            "The residuals are not as expected: {} vs. {}",
+            max(residuals),
+            max(residuals_2)
+        ));
+    }
+
+    Ok(())
+}
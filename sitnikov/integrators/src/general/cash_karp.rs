@@ -0,0 +1,56 @@
+//! Provides the [`cash_karp`] macro, plus tests for the method
+
+/// Defines the [`cash_karp`](crate::GeneralIntegrator#method.cash_karp) method
+macro_rules! cash_karp {
+    () => {
+        /// Integrate the system using the Cash-Karp method with a fixed
+        /// time step, keeping the 5th-order estimates (see
+        /// [`integrate_embedded`](crate::GeneralIntegrator#method.integrate_embedded)
+        /// for the integration with an adaptive time step)
+        ///
+        /// Arguments:
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Number of iterations;
+        /// * `result` --- Solution;
+        /// * `callback` --- Function called after each step (returns `false` to stop);
+        /// * `token` --- Private token.
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn cash_karp(
+            &self,
+            t_0: F,
+            h: F,
+            n: usize,
+            result: &mut Solution<F>,
+            callback: &mut dyn FnMut(usize, F, &[F]) -> bool,
+            token: &Token,
+        ) -> anyhow::Result<()> {
+            // Get the initial state
+            let mut x = result.initial_values();
+            // Get the Butcher tableau
+            let tableau = Pairs::CashKarp.tableau();
+            // Integrate
+            for i in 0..n {
+                // Compute the time moment
+                let t = t_0 + F::from(i).unwrap() * h;
+                // Make a step
+                x = self
+                    .embedded_step(t, h, &x, tableau, None, token)
+                    .with_context(|| format!("Couldn't make a step at t = {t}"))?
+                    .0;
+                // Put the new state in the result
+                result.set_state_from_slice(i + 1, &x);
+                result.set_time(i + 1, t_0 + F::from(i + 1).unwrap() * h);
+                if !callback(i + 1, result.times()[i + 1], &x) {
+                    break;
+                }
+            }
+            Ok(())
+        }
+    };
+}
+
+pub(super) use cash_karp;
+
+#[cfg(test)]
+super::test_method::test_method!(GeneralIntegrators::CashKarp);
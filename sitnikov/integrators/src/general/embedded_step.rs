@@ -0,0 +1,80 @@
+//! Provides the [`embedded_step`] macro
+
+/// Defines the [`embedded_step`](crate::GeneralIntegrator#method.embedded_step) method
+macro_rules! embedded_step {
+    () => {
+        /// Make a step using the embedded Runge-Kutta pair, return the
+        /// higher-order estimate of the next state, its difference with
+        /// the lower-order estimate (which is an estimate of the local error)
+        /// and the last stage (which is the right-hand side in the next state
+        /// if the pair has the First Same As Last property)
+        ///
+        /// Arguments:
+        /// * `t` --- Current time moment;
+        /// * `h` --- Time step;
+        /// * `x` --- Current state of the system;
+        /// * `tableau` --- Butcher tableau of the embedded pair;
+        /// * `k_first` --- Right-hand side in the current state (if it's known);
+        /// * `token` --- Private token.
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn embedded_step(
+            &self,
+            t: F,
+            h: F,
+            x: &[F],
+            tableau: &Tableau<F>,
+            k_first: Option<&[F]>,
+            _: &Token,
+        ) -> anyhow::Result<(Vec<F>, Vec<F>, Vec<F>)> {
+            let (weights_high, weights_low) = (&tableau.weights_high, &tableau.weights_low);
+            // Compute the increments
+            let mut k: Vec<Vec<F>> = Vec::with_capacity(tableau.nodes.len());
+            for (i, (&c, a)) in tableau.nodes.iter().zip(tableau.matrix.iter()).enumerate() {
+                // Reuse the right-hand side in the current state
+                if let (0, Some(k_first)) = (i, k_first) {
+                    k.push(k_first.to_vec());
+                    continue;
+                }
+                // Compute the modified state
+                let x_m: Vec<F> = x
+                    .iter()
+                    .enumerate()
+                    .map(|(j, &x)| {
+                        x + h * k
+                            .iter()
+                            .zip(a.iter())
+                            .fold(0., |acc, (k, &a)| acc + a * k[j])
+                    })
+                    .collect();
+                // Compute the increment
+                k.push(
+                    self.update(t + c * h, &x_m)
+                        .with_context(|| format!("Couldn't compute the increment #{}", i + 1))?,
+                );
+            }
+            // Compute the higher-order estimate of the next state
+            let x_high: Vec<F> = x
+                .iter()
+                .enumerate()
+                .map(|(j, &x)| {
+                    x + h * k
+                        .iter()
+                        .zip(weights_high.iter())
+                        .fold(0., |acc, (k, &b)| acc + b * k[j])
+                })
+                .collect();
+            // Compute the difference with the lower-order estimate
+            let delta: Vec<F> = (0..x.len())
+                .map(|j| {
+                    h * k
+                        .iter()
+                        .zip(weights_high.iter().zip(weights_low.iter()))
+                        .fold(0., |acc, (k, (&b_h, &b_l))| acc + (b_h - b_l) * k[j])
+                })
+                .collect();
+            Ok((x_high, delta, k.pop().unwrap()))
+        }
+    };
+}
+
+pub(super) use embedded_step;
@@ -0,0 +1,53 @@
+//! Provides the [`euler`] macro, plus tests for the method
+
+/// Defines the [`euler`](crate::GeneralIntegrator#method.euler) method
+macro_rules! euler {
+    () => {
+        /// Integrate the system using the (forward) Euler method
+        ///
+        /// Arguments:
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Number of iterations;
+        /// * `result` --- Solution;
+        /// * `callback` --- Function called after each step (returns `false` to stop);
+        /// * `token` --- Private token.
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn euler(
+            &self,
+            t_0: F,
+            h: F,
+            n: usize,
+            result: &mut Solution<F>,
+            callback: &mut dyn FnMut(usize, F, &[F]) -> bool,
+            _: &Token,
+        ) -> anyhow::Result<()> {
+            // Get the initial state
+            let mut x = result.initial_values();
+            // Integrate
+            for i in 0..n {
+                // Compute the time moment
+                let t = t_0 + F::from(i).unwrap() * h;
+                // Compute the increment
+                let k = self
+                    .update(t, &x)
+                    .with_context(|| "Couldn't compute the increment")?;
+                // Compute the next state
+                x = x.iter().zip(k.iter()).map(|(&x, &k)| x + h * k).collect();
+                // Put the new state in the result
+                result.set_state_from_slice(i + 1, &x);
+                result.set_time(i + 1, t_0 + F::from(i + 1).unwrap() * h);
+                if !callback(i + 1, result.times()[i + 1], &x) {
+                    break;
+                }
+            }
+            Ok(())
+        }
+    };
+}
+
+pub(super) use euler;
+
+// The global error of the first-order method is too big for the default factor
+#[cfg(test)]
+super::test_method::test_method!(GeneralIntegrators::Euler, 1e3);
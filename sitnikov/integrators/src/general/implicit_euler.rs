@@ -0,0 +1,119 @@
+//! Provides the [`implicit_euler`] macro, plus tests for the method
+
+/// Defines the [`implicit_euler`](crate::GeneralIntegrator#method.implicit_euler) method
+macro_rules! implicit_euler {
+    () => {
+        /// Integrate the system using the implicit (backward) Euler method
+        ///
+        /// On each iteration, the equation `x_{i+1} = x_i + h f(t_{i+1}, x_{i+1})`
+        /// is solved using Newton's method. This makes the method suitable
+        /// for stiff systems.
+        ///
+        /// Arguments:
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Number of iterations;
+        /// * `result` --- Solution;
+        /// * `callback` --- Function called after each step (returns `false` to stop);
+        /// * `token` --- Private token.
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn implicit_euler(
+            &self,
+            t_0: F,
+            h: F,
+            n: usize,
+            result: &mut Solution<F>,
+            callback: &mut dyn FnMut(usize, F, &[F]) -> bool,
+            token: &Token,
+        ) -> anyhow::Result<()> {
+            // Get the initial state
+            let mut x = result.initial_values();
+            // Integrate
+            for i in 0..n {
+                // Compute the time moment of the next state
+                let t = t_0 + F::from(i + 1).unwrap() * h;
+                // Define the equation for the next state and its Jacobian
+                let g = |y: &[F]| -> anyhow::Result<Vec<F>> {
+                    let f = self.update(t, y)?;
+                    Ok((0..y.len()).map(|j| y[j] - x[j] - h * f[j]).collect())
+                };
+                let jacobian = |y: &[F]| -> anyhow::Result<DMatrix<F>> {
+                    let jacobian = self.jacobian(t, y, token)?;
+                    Ok(DMatrix::from_fn(y.len(), y.len(), |i, j| {
+                        let delta = if i == j { 1. } else { 0. };
+                        delta - h * jacobian[(i, j)]
+                    }))
+                };
+                // Compute the next state
+                x = newton(g, jacobian, &x)
+                    .with_context(|| format!("Couldn't solve for the state at t = {t}"))?;
+                // Put the new state in the result
+                result.set_state_from_slice(i + 1, &x);
+                result.set_time(i + 1, t);
+                if !callback(i + 1, result.times()[i + 1], &x) {
+                    break;
+                }
+            }
+            Ok(())
+        }
+    };
+}
+
+pub(super) use implicit_euler;
+
+// The global error of the first-order method is too big for the default factor
+#[cfg(test)]
+super::test_method::test_method!(GeneralIntegrators::ImplicitEuler, 1e3);
+
+#[test]
+fn test_stiff() -> anyhow::Result<()> {
+    use crate::{Float, GeneralIntegrator, GeneralIntegrators, ResultExt};
+    use nalgebra::DMatrix;
+
+    // Implement the trait on test structs (a stiff decay,
+    // with and without the analytic Jacobian)
+    struct Test {}
+    impl<F: Float> GeneralIntegrator<F> for Test {
+        fn update(&self, _t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![F::from(-15.).unwrap() * x[0]])
+        }
+    }
+    struct TestAnalytic {}
+    impl<F: Float> GeneralIntegrator<F> for TestAnalytic {
+        fn update(&self, _t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![F::from(-15.).unwrap() * x[0]])
+        }
+        fn analytic_jacobian(&self, _t: F, _x: &[F]) -> Option<DMatrix<F>> {
+            Some(DMatrix::from_element(1, 1, F::from(-15.).unwrap()))
+        }
+    }
+
+    // Integrate with a step too big for the explicit method
+    let (h, n) = (0.25, 40);
+    let explicit = Test {}.integrate(&[1_f64], 0., h, n, GeneralIntegrators::Euler)?;
+    let implicit = Test {}.integrate(&[1_f64], 0., h, n, GeneralIntegrators::ImplicitEuler)?;
+    let analytic =
+        TestAnalytic {}.integrate(&[1_f64], 0., h, n, GeneralIntegrators::ImplicitEuler)?;
+
+    // Check that the explicit method blows up, while the implicit one decays
+    let (x_explicit, x_implicit) = (explicit[(0, n)], implicit[(0, n)]);
+    if x_explicit.abs() < 1e10 || !(0. ..1e-10).contains(&x_implicit) {
+        return Err(anyhow::anyhow!(
+            "The methods didn't behave as expected: {x_explicit} vs. {x_implicit}"
+        ));
+    }
+
+    // Compare the results obtained with the analytic Jacobian
+    if implicit
+        .result(0)
+        .iter()
+        .zip(analytic.result(0).iter())
+        .any(|(&x, &x_a)| (x - x_a).abs() >= 1e-12)
+    {
+        return Err(anyhow::anyhow!(
+            "The analytic Jacobian gives different results"
+        ));
+    }
+
+    Ok(())
+}
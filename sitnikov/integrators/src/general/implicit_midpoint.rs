@@ -0,0 +1,73 @@
+//! Provides the [`implicit_midpoint`] macro, plus tests for the method
+
+/// Defines the [`implicit_midpoint`](crate::GeneralIntegrator#method.implicit_midpoint) method
+macro_rules! implicit_midpoint {
+    () => {
+        /// Integrate the system using the implicit midpoint rule
+        ///
+        /// On each iteration, the equation `x_{i+1} = x_i + h f(t_i + h / 2,
+        /// (x_i + x_{i+1}) / 2)` is solved using Newton's method. The method
+        /// is symplectic and A-stable, but needs the full right-hand side
+        /// of the system (so it's a general integrator, unlike the other
+        /// symplectic ones, which need separable Hamiltonians).
+        ///
+        /// Arguments:
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Number of iterations;
+        /// * `result` --- Solution;
+        /// * `callback` --- Function called after each step (returns `false` to stop);
+        /// * `token` --- Private token.
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn implicit_midpoint(
+            &self,
+            t_0: F,
+            h: F,
+            n: usize,
+            result: &mut Solution<F>,
+            callback: &mut dyn FnMut(usize, F, &[F]) -> bool,
+            token: &Token,
+        ) -> anyhow::Result<()> {
+            // Get the initial state
+            let mut x = result.initial_values();
+            // Integrate
+            for i in 0..n {
+                // Compute the time moment of the midpoint
+                let t = t_0 + (F::from(i).unwrap() + 0.5) * h;
+                // Define the equation for the next state and its Jacobian
+                let midpoint = |y: &[F]| -> Vec<F> {
+                    x.iter()
+                        .zip(y.iter())
+                        .map(|(&x, &y)| 0.5 * (x + y))
+                        .collect()
+                };
+                let g = |y: &[F]| -> anyhow::Result<Vec<F>> {
+                    let f = self.update(t, &midpoint(y))?;
+                    Ok((0..y.len()).map(|j| y[j] - x[j] - h * f[j]).collect())
+                };
+                let jacobian = |y: &[F]| -> anyhow::Result<DMatrix<F>> {
+                    let jacobian = self.jacobian(t, &midpoint(y), token)?;
+                    Ok(DMatrix::from_fn(y.len(), y.len(), |i, j| {
+                        let delta = if i == j { 1. } else { 0. };
+                        delta - 0.5 * h * jacobian[(i, j)]
+                    }))
+                };
+                // Compute the next state
+                x = newton(g, jacobian, &x)
+                    .with_context(|| format!("Couldn't solve for the state after t = {t}"))?;
+                // Put the new state in the result
+                result.set_state_from_slice(i + 1, &x);
+                result.set_time(i + 1, t_0 + F::from(i + 1).unwrap() * h);
+                if !callback(i + 1, result.times()[i + 1], &x) {
+                    break;
+                }
+            }
+            Ok(())
+        }
+    };
+}
+
+pub(super) use implicit_midpoint;
+
+#[cfg(test)]
+super::test_method::test_method!(GeneralIntegrators::ImplicitMidpoint);
@@ -1,6 +1,10 @@
 //! Provides the [`integrate`] macro
 
-/// Defines the [`integrate`](crate::GeneralIntegrator#method.integrate) method
+/// Defines the [`integrate`](crate::GeneralIntegrator#method.integrate),
+/// [`integrate_with`](crate::GeneralIntegrator#method.integrate_with) and
+/// [`integrate_into`](crate::GeneralIntegrator#method.integrate_into) methods (plus the
+/// [`dispatch`](crate::GeneralIntegrator#method.dispatch) and
+/// [`solve`](crate::GeneralIntegrator#method.solve) methods they're based on)
 macro_rules! integrate {
     () => {
         /// Integrate the system of 1st-order ODEs
@@ -11,7 +15,6 @@ macro_rules! integrate {
         /// * `h` --- Time step;
         /// * `n` --- Number of iterations;
         /// * `integrator` --- Integration method.
-        #[replace_float_literals(F::from(literal).unwrap())]
         fn integrate(
             &self,
             x: &[F],
@@ -19,23 +22,273 @@ macro_rules! integrate {
             h: F,
             n: usize,
             integrator: Integrators,
-        ) -> anyhow::Result<Result<F>> {
+        ) -> anyhow::Result<Solution<F>> {
+            self.integrate_with(x, t_0, h, n, integrator, |_, _, _| {})
+        }
+
+        /// Integrate the system of 1st-order ODEs, calling the passed function
+        /// after each step with the index of the new state, its time moment
+        /// and the state itself
+        ///
+        /// Arguments:
+        /// * `x` --- Vector of initial values;
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Number of iterations;
+        /// * `integrator` --- Integration method;
+        /// * `callback` --- Function called after each step.
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn integrate_with(
+            &self,
+            x: &[F],
+            t_0: F,
+            h: F,
+            n: usize,
+            integrator: Integrators,
+            mut callback: impl FnMut(usize, F, &[F]),
+        ) -> anyhow::Result<Solution<F>> {
+            // Get a token for using the private methods
+            let token = Token {};
+            self.dispatch(
+                x,
+                t_0,
+                h,
+                n,
+                integrator,
+                &mut |i, t, x| {
+                    callback(i, t, x);
+                    true
+                },
+                &token,
+            )
+        }
+
+        /// Integrate the system of 1st-order ODEs into the passed solution,
+        /// reusing its memory (see [`Solution::reset`](crate::Solution::reset))
+        ///
+        /// Arguments:
+        /// * `result` --- Solution to reuse;
+        /// * `x` --- Vector of initial values;
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Number of iterations;
+        /// * `integrator` --- Integration method.
+        fn integrate_into(
+            &self,
+            result: &mut Solution<F>,
+            x: &[F],
+            t_0: F,
+            h: F,
+            n: usize,
+            integrator: Integrators,
+        ) -> anyhow::Result<()> {
             // Get a token for using the private methods
             let token = Token {};
+            // Check the dimension of the state
+            self.check_dim(x, &token)?;
+            // Reset the solution
+            result.reset(x, t_0, n);
+            self.solve(result, t_0, h, n, integrator, &mut |_, _, _| true, &token)
+        }
+
+        /// Integrate the system of 1st-order ODEs using the specified method,
+        /// calling the passed function after each step (the integration stops
+        /// if it returns `false`, and the solution is truncated)
+        ///
+        /// Arguments:
+        /// * `x` --- Vector of initial values;
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Maximum number of iterations;
+        /// * `integrator` --- Integration method;
+        /// * `callback` --- Function called after each step;
+        /// * `token` --- Private token.
+        #[allow(clippy::too_many_arguments)]
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn dispatch(
+            &self,
+            x: &[F],
+            t_0: F,
+            h: F,
+            n: usize,
+            integrator: Integrators,
+            callback: &mut dyn FnMut(usize, F, &[F]) -> bool,
+            token: &Token,
+        ) -> anyhow::Result<Solution<F>> {
             // Prepare a result matrix
-            let mut result = self.prepare(x.to_vec(), n, &token);
+            let mut result = self.prepare(x, t_0, n, token)?;
+            // Integrate
+            self.solve(&mut result, t_0, h, n, integrator, callback, token)?;
+            Ok(result)
+        }
+
+        /// Integrate the system of 1st-order ODEs using the specified method,
+        /// starting from the first state of the prepared solution, calling the
+        /// passed function after each step (the integration stops if it returns
+        /// `false`, and the solution is truncated)
+        ///
+        /// Arguments:
+        /// * `result` --- Prepared solution;
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Maximum number of iterations;
+        /// * `integrator` --- Integration method;
+        /// * `callback` --- Function called after each step;
+        /// * `token` --- Private token.
+        #[allow(clippy::too_many_arguments)]
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn solve(
+            &self,
+            result: &mut Solution<F>,
+            t_0: F,
+            h: F,
+            n: usize,
+            integrator: Integrators,
+            callback: &mut dyn FnMut(usize, F, &[F]) -> bool,
+            token: &Token,
+        ) -> anyhow::Result<()> {
             // Call the specified method to perform integration
             match integrator {
+                Integrators::AdamsBashforth { steps } => {
+                    self.adams_bashforth(t_0, h, n, steps, result, callback, token)
+                        .with_context(|| {
+                            format!(
+                                "Couldn't integrate using the {steps}-step Adams-Bashforth method"
+                            )
+                        })?;
+                }
+                Integrators::AdamsMoulton { steps, corrections } => {
+                    self.adams_moulton(t_0, h, n, steps, corrections, result, callback, token)
+                        .with_context(|| {
+                            format!(
+                                "Couldn't integrate using the {steps}-step \
+                                Adams-Bashforth-Moulton method"
+                            )
+                        })?;
+                }
+                Integrators::CashKarp => {
+                    self.cash_karp(t_0, h, n, result, callback, token)
+                        .with_context(|| "Couldn't integrate using the Cash-Karp method")?;
+                }
+                Integrators::Euler => {
+                    self.euler(t_0, h, n, result, callback, token)
+                        .with_context(|| "Couldn't integrate using the Euler method")?;
+                }
+                Integrators::ImplicitEuler => {
+                    self.implicit_euler(t_0, h, n, result, callback, token)
+                        .with_context(|| "Couldn't integrate using the implicit Euler method")?;
+                }
+                Integrators::ImplicitMidpoint => {
+                    self.implicit_midpoint(t_0, h, n, result, callback, token)
+                        .with_context(|| "Couldn't integrate using the implicit midpoint rule")?;
+                }
+                Integrators::Rosenbrock2nd => {
+                    self.rosenbrock_2nd(t_0, h, n, result, callback, token)
+                        .with_context(|| {
+                            "Couldn't integrate using the 2nd-order Rosenbrock method"
+                        })?;
+                }
+                Integrators::RungeKutta2nd => {
+                    self.runge_kutta_2nd(t_0, h, n, result, callback, token)
+                        .with_context(|| {
+                            "Couldn't integrate using the 2nd-order Runge-Kutta method"
+                        })?;
+                }
+                Integrators::RungeKutta3rd => {
+                    self.runge_kutta_3rd(t_0, h, n, result, callback, token)
+                        .with_context(|| {
+                            "Couldn't integrate using the 3rd-order Runge-Kutta method"
+                        })?;
+                }
                 Integrators::RungeKutta4th => {
-                    self.runge_kutta_4th(t_0, h, n, &mut result, &token)
+                    self.runge_kutta_4th(t_0, h, n, result, callback, token)
                         .with_context(|| {
                             "Couldn't integrate using the 4th-order Runge-Kutta method"
                         })?;
                 }
+                Integrators::RungeKutta45 => {
+                    self.runge_kutta_45(t_0, h, n, result, callback, token)
+                        .with_context(|| {
+                            "Couldn't integrate using the Runge-Kutta-Fehlberg method"
+                        })?;
+                }
             }
-            Ok((result))
+            // Drop the states which weren't computed
+            let len = result.times().len();
+            result.truncate(len);
+            Ok(())
         }
     };
 }
 
 pub(super) use integrate;
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_callback() -> anyhow::Result<()> {
+    use crate::{Float, GeneralIntegrator, GeneralIntegrators, ResultExt};
+
+    // Implement the trait on a test struct
+    struct Test {}
+    impl<F: Float> GeneralIntegrator<F> for Test {
+        fn update(&self, t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![t, x[0] * F::sin(t)])
+        }
+    }
+    let test = Test {};
+
+    // Integrate, collecting the steps in the callback
+    let n = 100;
+    let mut steps = Vec::new();
+    let result = test.integrate_with(
+        &[0., 0.],
+        0.,
+        1e-2,
+        n,
+        GeneralIntegrators::RungeKutta4th,
+        |i, t, x| steps.push((i, t, x.to_vec())),
+    )?;
+
+    // Compare the steps with the solution
+    if steps.len() != n
+        || steps
+            .iter()
+            .enumerate()
+            .any(|(j, (i, t, x))| *i != j + 1 || *t != result.times()[*i] || *x != result.state(*i))
+    {
+        return Err(anyhow::anyhow!(
+            "The steps passed to the callback don't match the solution"
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_into() -> anyhow::Result<()> {
+    use crate::{Float, GeneralIntegrator, GeneralIntegrators};
+
+    // Implement the trait on a test struct
+    struct Test {}
+    impl<F: Float> GeneralIntegrator<F> for Test {
+        fn update(&self, t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![t, x[0] * F::sin(t)])
+        }
+    }
+    let test = Test {};
+
+    // Integrate into the same solution several times
+    let integrator = GeneralIntegrators::RungeKutta4th;
+    let mut result = test.integrate(&[0., 0.], 0., 1e-2, 100, integrator)?;
+    let ptr = result.as_ptr();
+    for (x, n) in [([1., 0.], 100), ([0., 1.], 50)] {
+        test.integrate_into(&mut result, &x, 1., 1e-2, n, integrator)?;
+        if result != test.integrate(&x, 1., 1e-2, n, integrator)? || result.as_ptr() != ptr {
+            return Err(anyhow::anyhow!(
+                "The reused solution is incorrect (x = {x:?}, n = {n})"
+            ));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,366 @@
+//! Provides the [`integrate_adaptive`] macro, plus tests for the method
+
+/// Defines the [`integrate_adaptive`](crate::GeneralIntegrator#method.integrate_adaptive)
+/// and [`integrate_embedded`](crate::GeneralIntegrator#method.integrate_embedded) methods
+macro_rules! integrate_adaptive {
+    () => {
+        /// Integrate the system of 1st-order ODEs using the Runge-Kutta-Fehlberg
+        /// method with an adaptive time step (see
+        /// [`integrate_embedded`](crate::GeneralIntegrator#method.integrate_embedded))
+        ///
+        /// Arguments:
+        /// * `x` --- Vector of initial values;
+        /// * `t_0` --- Initial value of time;
+        /// * `t_end` --- Final value of time;
+        /// * `h_init` --- Initial time step (the sign is ignored);
+        /// * `tol` --- Absolute and relative tolerances
+        ///   (see [`Tolerances`](crate::Tolerances)).
+        fn integrate_adaptive(
+            &self,
+            x: &[F],
+            t_0: F,
+            t_end: F,
+            h_init: F,
+            tol: impl Into<Tolerances<F>>,
+        ) -> anyhow::Result<Solution<F>> {
+            self.integrate_embedded(x, t_0, t_end, h_init, tol, Pairs::Fehlberg)
+        }
+
+        /// Integrate the system of 1st-order ODEs using the embedded
+        /// Runge-Kutta pair with an adaptive time step
+        ///
+        /// After each step the time step is rescaled by `0.9 * err^(-1/5)`
+        /// (within `[0.2, 5]`), where `err` is the weighted norm of the
+        /// error estimate (see [`Tolerances`](crate::Tolerances)). The steps
+        /// with the norm exceeding 1 are rejected and retried. The last step
+        /// is shortened so the integration ends exactly at `t_end`. The time
+        /// step can't be smaller than a few units in the last place of the
+        /// time moments. If the pair has the First Same As Last property,
+        /// the last stage of each accepted step is reused
+        ///
+        /// Arguments:
+        /// * `x` --- Vector of initial values;
+        /// * `t_0` --- Initial value of time;
+        /// * `t_end` --- Final value of time;
+        /// * `h_init` --- Initial time step (the sign is ignored);
+        /// * `tol` --- Absolute and relative tolerances
+        ///   (see [`Tolerances`](crate::Tolerances));
+        /// * `pair` --- Embedded pair.
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn integrate_embedded(
+            &self,
+            x: &[F],
+            t_0: F,
+            t_end: F,
+            h_init: F,
+            tol: impl Into<Tolerances<F>>,
+            pair: Pairs,
+        ) -> anyhow::Result<Solution<F>> {
+            // Get a token for using the private methods
+            let token = Token {};
+            // Check the dimension of the state
+            self.check_dim(x, &token)?;
+            // Check the tolerances
+            let tol = tol.into();
+            tol.check(x.len())?;
+            // Get the direction of integration
+            let dir = if t_end < t_0 { -1. } else { 1. };
+            // Compute the limits of the time step
+            let h_max = (t_end - t_0).abs();
+            let h_min = 16. * F::epsilon() * t_0.abs().max(t_end.abs()).max(1.);
+            let mut h = h_init.abs().min(h_max);
+            // Integrate
+            let mut t = t_0;
+            let mut x = x.to_vec();
+            let mut times = vec![t];
+            let mut states = vec![x.clone()];
+            // Get the Butcher tableau
+            let tableau = pair.tableau();
+            let mut k_first: Option<Vec<F>> = None;
+            while (t_end - t) * dir > 0. {
+                if h < h_min {
+                    return Err(anyhow::anyhow!(
+                        "The time step underflowed the minimum ({h_min}) at t = {t}"
+                    ));
+                }
+                // Don't overshoot the final time moment
+                let last = h >= (t_end - t).abs();
+                let h_step = if last { (t_end - t).abs() } else { h };
+                // Make a step
+                let (x_new, delta, k_last) = self
+                    .embedded_step(t, dir * h_step, &x, tableau, k_first.as_deref(), &token)
+                    .with_context(|| format!("Couldn't make a step at t = {t}"))?;
+                // Compute the norm of the error relative to the tolerance
+                let err = tol.norm(&x, &x_new, &delta);
+                // Accept the step if the error is within the tolerance
+                // (reusing the last stage as the first stage of the next step
+                // if the pair allows that; after a rejected step, the first
+                // stage in the current state is still valid)
+                if err <= 1. {
+                    t = if last { t_end } else { t + dir * h_step };
+                    x = x_new;
+                    if tableau.fsal {
+                        k_first = Some(k_last);
+                    }
+                    times.push(t);
+                    states.push(x.clone());
+                }
+                // Rescale the time step
+                let factor = if err == 0. {
+                    5.
+                } else {
+                    (0.9 * err.powf(-1. / 5.)).max(0.2).min(5.)
+                };
+                h = (h_step * factor).min(h_max);
+            }
+            // Put the states and the time moments in the solution
+            let mut result = self.prepare(&x, t_0, times.len() - 1, &token)?;
+            for (i, (x, t)) in states.into_iter().zip(times).enumerate() {
+                result.set_state(i, x);
+                result.set_time(i, t);
+            }
+            Ok(result)
+        }
+    };
+}
+
+pub(super) use integrate_adaptive;
+
+#[test]
+fn test() -> anyhow::Result<()> {
+    use crate::{Float, GeneralIntegrator, GeneralIntegrators, ResultExt};
+
+    // Implement the trait on a test struct (a harmonic
+    // oscillator with a sharp spike of the force)
+    struct Test {}
+    impl<F: Float> GeneralIntegrator<F> for Test {
+        fn update(&self, t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            let spike =
+                F::from(1e3).unwrap() * F::exp(-F::from(1e4).unwrap() * (t - F::one()).powi(2));
+            Ok(vec![x[1], -x[0] + spike])
+        }
+    }
+    let test = Test {};
+
+    // Integrate with an adaptive time step
+    let x = [1., 0.];
+    let (t_0, t_end): (f64, f64) = (0., 10.);
+    let result = test.integrate_adaptive(&x, t_0, t_end, 1e-2, (1e-10, 1e-10))?;
+    let times = result.times();
+    let n = times.len() - 1;
+    if result.ncols() != n + 1 || times.windows(2).any(|w| w[1] <= w[0]) {
+        return Err(anyhow::anyhow!("The time moments are inconsistent"));
+    }
+    if (times[n] - t_end).abs() > 0. {
+        return Err(anyhow::anyhow!(
+            "The integration didn't end at the final time moment: {} vs. {}",
+            t_end,
+            times[n]
+        ));
+    }
+
+    // Integrate with a fixed tiny time step
+    let n_fixed: u32 = 100_000;
+    let h = (t_end - t_0) / f64::from(n_fixed);
+    let result_fixed = test.integrate(
+        &x,
+        t_0,
+        h,
+        n_fixed as usize,
+        GeneralIntegrators::RungeKutta45,
+    )?;
+
+    // Compare the results
+    let (x, x_fixed) = (result.state(n), result_fixed.state(n_fixed as usize));
+    if x.iter()
+        .zip(x_fixed.iter())
+        .any(|(&x, &x_fixed)| (x - x_fixed).abs() >= 1e-7)
+    {
+        return Err(anyhow::anyhow!(
+            "The result of integration is not the same as expected: {x_fixed:?} vs. {x:?}"
+        ));
+    }
+
+    // Check that the steps are adapted
+    let h_min = times
+        .windows(2)
+        .map(|w| w[1] - w[0])
+        .fold(f64::INFINITY, f64::min);
+    if n >= 5000 || h_min >= 1e-2 {
+        return Err(anyhow::anyhow!(
+            "The time step wasn't adapted: {} steps, the minimum time step is {}",
+            n,
+            h_min
+        ));
+    }
+
+    // Integrate backward
+    let result_backward = test.integrate_adaptive(&x, t_end, t_0, 1e-2, (1e-10, 1e-10))?;
+    let times_backward = result_backward.times();
+    let n_backward = times_backward.len() - 1;
+    if (times_backward[n_backward] - t_0).abs() > 0.
+        || (result_backward[(0, n_backward)] - 1.).abs() >= 1e-7
+    {
+        return Err(anyhow::anyhow!("The backward integration is incorrect"));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_underflow() -> anyhow::Result<()> {
+    use crate::{Float, GeneralIntegrator};
+
+    // Implement the trait on a test struct (the solution
+    // `x = 1 / (1 - t)` blows up at `t = 1`)
+    struct Test {}
+    impl<F: Float> GeneralIntegrator<F> for Test {
+        fn update(&self, _t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![x[0].powi(2)])
+        }
+    }
+    let test = Test {};
+
+    // Check that the integration through the singularity fails
+    if test
+        .integrate_adaptive(&[1.], 0., 2., 1e-2, (1e-8, 1e-8))
+        .is_ok()
+    {
+        return Err(anyhow::anyhow!(
+            "The integration through the singularity didn't fail"
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_tolerances() -> anyhow::Result<()> {
+    use crate::{Float, GeneralIntegrator, ResultExt, Tolerances};
+
+    // Implement the trait on a test struct (a harmonic oscillator
+    // plus a big, fast oscillating component)
+    struct Test {}
+    impl<F: Float> GeneralIntegrator<F> for Test {
+        fn update(&self, t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            let c = F::from(1e3).unwrap();
+            Ok(vec![x[1], -x[0], c * F::cos(c * t / F::from(10.).unwrap())])
+        }
+    }
+    let test = Test {};
+
+    // Integrate with the same tolerances for all of the
+    // components and with a loose one for the last one
+    let x = [1., 0., 0.];
+    let t_end = 10.;
+    let result = test.integrate_adaptive(&x, 0., t_end, 1e-2, (1e-10, 1e-10))?;
+    let result_loose = test.integrate_adaptive(
+        &x,
+        0.,
+        t_end,
+        1e-2,
+        Tolerances::new(&[1e-10, 1e-10, 1e2], &[1e-10]),
+    )?;
+
+    // Check that the loose component doesn't dominate the step size,
+    // while the rest of the components are still accurate
+    let (n, n_loose) = (result.ncols() - 1, result_loose.ncols() - 1);
+    if n_loose * 2 >= n || (result_loose.state(n_loose)[0] - f64::cos(t_end)).abs() >= 1e-8 {
+        return Err(anyhow::anyhow!(
+            "The per-component tolerances weren't respected: {n_loose} vs. {n} steps"
+        ));
+    }
+
+    // Check that the mismatched tolerances are rejected
+    if test
+        .integrate_adaptive(&x, 0., t_end, 1e-2, Tolerances::new(&[1e-10, 1e-10], &[0.]))
+        .is_ok()
+    {
+        return Err(anyhow::anyhow!(
+            "The mismatched tolerances weren't rejected"
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_cash_karp() -> anyhow::Result<()> {
+    use crate::{EmbeddedPairs, Float, GeneralIntegrator, ResultExt};
+
+    // Implement the trait on a test struct (a harmonic oscillator)
+    struct Test {}
+    impl<F: Float> GeneralIntegrator<F> for Test {
+        fn update(&self, _t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![x[1], -x[0]])
+        }
+    }
+    let test = Test {};
+
+    // Integrate using the Cash-Karp pair, compare with the exact solution
+    let t_end: f64 = 10.;
+    let result = test.integrate_embedded(
+        &[1., 0.],
+        0.,
+        t_end,
+        1e-2,
+        (1e-10, 1e-10),
+        EmbeddedPairs::CashKarp,
+    )?;
+    let n = result.ncols() - 1;
+    let x = result.state(n);
+    if (result.times()[n] - t_end).abs() > 0. || (x[0] - t_end.cos()).abs() >= 1e-7 {
+        return Err(anyhow::anyhow!(
+            "The result of integration is not the same as expected: {} vs. {}",
+            t_end.cos(),
+            x[0]
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_dormand_prince() -> anyhow::Result<()> {
+    use crate::{CountedIntegrator, EmbeddedPairs, Float, GeneralIntegrator, ResultExt};
+
+    // Implement the trait on a test struct (a harmonic oscillator)
+    struct Test {}
+    impl<F: Float> GeneralIntegrator<F> for Test {
+        fn update(&self, _t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![x[1], -x[0]])
+        }
+    }
+    let test = CountedIntegrator::new(Test {});
+
+    // Integrate over a long time span, compare with the exact solution
+    let t_end: f64 = 100.;
+    let result = test.integrate_embedded(
+        &[1., 0.],
+        0.,
+        t_end,
+        1e-2,
+        (1e-10, 1e-10),
+        EmbeddedPairs::DormandPrince54,
+    )?;
+    let steps = result.ncols() - 1;
+    let x = result.state(steps);
+    if (x[0] - t_end.cos()).abs() >= 1e-6 {
+        return Err(anyhow::anyhow!(
+            "The result of integration is not the same as expected: {} vs. {}",
+            t_end.cos(),
+            x[0]
+        ));
+    }
+
+    // Check that the last stages were reused
+    let evals = test.evals();
+    if evals < 6 * steps + 1 || evals > 6 * steps + 1 + 6 * steps / 20 {
+        return Err(anyhow::anyhow!(
+            "The number of evaluations is not close to {}: {evals}",
+            6 * steps + 1
+        ));
+    }
+
+    Ok(())
+}
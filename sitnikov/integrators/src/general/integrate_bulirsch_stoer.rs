@@ -0,0 +1,235 @@
+//! Provides the [`integrate_bulirsch_stoer`] macro, plus tests for the method
+
+/// Defines the [`integrate_bulirsch_stoer`](crate::GeneralIntegrator#method.integrate_bulirsch_stoer)
+/// method (plus the [`modified_midpoint`](crate::GeneralIntegrator#method.modified_midpoint)
+/// method it's based on)
+macro_rules! integrate_bulirsch_stoer {
+    () => {
+        /// Integrate the system over one step using the modified midpoint method
+        /// with the passed number of substeps, return the resulting state
+        ///
+        /// Arguments:
+        /// * `t` --- Current time moment;
+        /// * `x` --- Current state of the system;
+        /// * `f` --- Right-hand side in the current state;
+        /// * `h` --- Time step;
+        /// * `n` --- Number of substeps;
+        /// * `token` --- Private token.
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn modified_midpoint(
+            &self,
+            t: F,
+            x: &[F],
+            f: &[F],
+            h: F,
+            n: usize,
+            _: &Token,
+        ) -> anyhow::Result<Vec<F>> {
+            let h_sub = h / F::from(n).unwrap();
+            // Make the first (Euler) substep
+            let mut z_prev = x.to_vec();
+            let mut z: Vec<F> = (0..x.len()).map(|j| x[j] + h_sub * f[j]).collect();
+            // Make the rest of the (midpoint) substeps
+            for m in 1..n {
+                let f = self
+                    .update(t + F::from(m).unwrap() * h_sub, &z)
+                    .with_context(|| "Couldn't compute the right-hand side of a substep")?;
+                let z_next: Vec<F> = (0..x.len())
+                    .map(|j| z_prev[j] + 2. * h_sub * f[j])
+                    .collect();
+                z_prev = std::mem::replace(&mut z, z_next);
+            }
+            // Smooth the result
+            let f = self
+                .update(t + h, &z)
+                .with_context(|| "Couldn't compute the right-hand side at the end of the step")?;
+            Ok((0..x.len())
+                .map(|j| 0.5 * (z[j] + z_prev[j] + h_sub * f[j]))
+                .collect())
+        }
+
+        /// Integrate the system of 1st-order ODEs using the Gragg-Bulirsch-Stoer
+        /// method with an adaptive time step
+        ///
+        /// Each step is made several times using the modified midpoint method
+        /// with `2, 4, 6, ...` substeps, and the results are extrapolated
+        /// to the zero substep. The extrapolation stops as soon as the
+        /// difference between the last two orders is within the tolerance
+        /// (otherwise, the step is rejected after 8 rows). After each
+        /// step, the time step is rescaled by `0.94 * (0.65 / err)^(1 / (2k + 1))`
+        /// (within `[0.2, 4]`), where `k` is the last column of the tableau.
+        /// The last step is shortened so the integration ends exactly at `t_end`
+        ///
+        /// Arguments:
+        /// * `x` --- Vector of initial values;
+        /// * `t_0` --- Initial value of time;
+        /// * `t_end` --- Final value of time;
+        /// * `h_init` --- Initial time step (the sign is ignored);
+        /// * `tol` --- Absolute and relative tolerances (see [`Tolerances`](crate::Tolerances)).
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn integrate_bulirsch_stoer(
+            &self,
+            x: &[F],
+            t_0: F,
+            t_end: F,
+            h_init: F,
+            tol: impl Into<Tolerances<F>>,
+        ) -> anyhow::Result<Solution<F>> {
+            // Define the maximum number of rows in the extrapolation tableau
+            let rows = 8;
+            // Get a token for using the private methods
+            let token = Token {};
+            // Check the dimension of the state
+            self.check_dim(x, &token)?;
+            // Check the tolerances
+            let tol = tol.into();
+            tol.check(x.len())?;
+            // Get the direction of integration
+            let dir = if t_end < t_0 { -1. } else { 1. };
+            // Compute the limits of the time step
+            let h_max = (t_end - t_0).abs();
+            let h_min = 16. * F::epsilon() * t_0.abs().max(t_end.abs()).max(1.);
+            let mut h = h_init.abs().min(h_max);
+            // Integrate
+            let mut t = t_0;
+            let mut x = x.to_vec();
+            let mut times = vec![t];
+            let mut states = vec![x.clone()];
+            while (t_end - t) * dir > 0. {
+                if h < h_min {
+                    return Err(anyhow::anyhow!(
+                        "The time step underflowed the minimum ({h_min}) at t = {t}"
+                    ));
+                }
+                // Don't overshoot the final time moment
+                let last = h >= (t_end - t).abs();
+                let h_step = if last { (t_end - t).abs() } else { h };
+                // Compute the right-hand side in the current state
+                let f = self
+                    .update(t, &x)
+                    .with_context(|| format!("Couldn't compute the right-hand side at t = {t}"))?;
+                // Fill the extrapolation tableau row by row
+                let mut tableau: Vec<Vec<Vec<F>>> = Vec::with_capacity(rows);
+                let mut accepted = None;
+                let mut err = F::infinity();
+                for k in 0..rows {
+                    let n_k = 2 * (k + 1);
+                    let mut row = vec![self
+                        .modified_midpoint(t, &x, &f, dir * h_step, n_k, &token)
+                        .with_context(|| format!("Couldn't make a step at t = {t}"))?];
+                    for j in 1..=k {
+                        // Eliminate the next even power of the substep
+                        let ratio = F::from(n_k).unwrap() / F::from(2 * (k - j + 1)).unwrap();
+                        let factor = ratio.powi(2) - 1.;
+                        let entry = row[j - 1]
+                            .iter()
+                            .zip(tableau[k - 1][j - 1].iter())
+                            .map(|(&a, &b)| a + (a - b) / factor)
+                            .collect();
+                        row.push(entry);
+                    }
+                    // Estimate the error using the last two columns
+                    if k > 0 {
+                        let (x_new, x_prev) = (&row[k], &row[k - 1]);
+                        let delta: Vec<F> = x_new
+                            .iter()
+                            .zip(x_prev.iter())
+                            .map(|(&x_new, &x_prev)| x_new - x_prev)
+                            .collect();
+                        err = tol.norm(&x, x_new, &delta);
+                        if err <= 1. {
+                            accepted = Some((k, row[k].clone()));
+                            break;
+                        }
+                    }
+                    tableau.push(row);
+                }
+                // Accept the step if the extrapolation converged,
+                // and rescale the time step
+                let factor = if let Some((k, x_new)) = accepted {
+                    t = if last { t_end } else { t + dir * h_step };
+                    x = x_new;
+                    times.push(t);
+                    states.push(x.clone());
+                    if err == 0. {
+                        4.
+                    } else {
+                        let exponent = 1. / F::from(2 * k + 1).unwrap();
+                        (0.94 * (0.65 / err).powf(exponent)).max(0.2).min(4.)
+                    }
+                } else {
+                    0.2
+                };
+                h = (h_step * factor).min(h_max);
+            }
+            // Put the states and the time moments in the solution
+            let mut result = self.prepare(&x, t_0, times.len() - 1, &token)?;
+            for (i, (x, t)) in states.into_iter().zip(times).enumerate() {
+                result.set_state(i, x);
+                result.set_time(i, t);
+            }
+            Ok(result)
+        }
+    };
+}
+
+pub(super) use integrate_bulirsch_stoer;
+
+#[test]
+fn test() -> anyhow::Result<()> {
+    use crate::{Float, GeneralIntegrator, GeneralIntegrators, ResultExt};
+    use std::cell::Cell;
+
+    // Implement the trait on a test struct, counting the calls
+    struct Test {
+        calls: Cell<usize>,
+    }
+    impl<F: Float> GeneralIntegrator<F> for Test {
+        fn update(&self, t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            self.calls.set(self.calls.get() + 1);
+            Ok(vec![t, x[0] * F::sin(t)])
+        }
+    }
+    let test = Test {
+        calls: Cell::new(0),
+    };
+
+    // Define the exact solution
+    let t_end: f64 = 5.;
+    let exact =
+        -t_end.powi(2) / 2. * f64::cos(t_end) + t_end * f64::sin(t_end) + f64::cos(t_end) - 1.;
+
+    // Integrate using the Bulirsch-Stoer method
+    let x = [0., 0.];
+    let result = test.integrate_bulirsch_stoer(&x, 0., t_end, 0.1, (1e-14, 1e-14))?;
+    let calls = test.calls.replace(0);
+    let n = result.times().len() - 1;
+    let error = (result.state(n)[1] - exact).abs();
+    if (result.times()[n] - t_end).abs() > 0. || error >= 1e-12 {
+        return Err(anyhow::anyhow!(
+            "The result of integration is not the same as expected: {exact} vs. {}",
+            result.state(n)[1]
+        ));
+    }
+
+    // Integrate using the 4th-order Runge-Kutta method, check that it's
+    // less accurate even with several times more calls
+    let n_rk4 = 1000;
+    let result_rk4 = test.integrate(
+        &x,
+        0.,
+        t_end / 1000.,
+        n_rk4,
+        GeneralIntegrators::RungeKutta4th,
+    )?;
+    let calls_rk4 = test.calls.get();
+    let error_rk4 = (result_rk4.state(n_rk4)[1] - exact).abs();
+    if error_rk4 < error || 5 * calls >= calls_rk4 {
+        return Err(anyhow::anyhow!(
+            "The Bulirsch-Stoer method is not cheaper: {calls} vs. {calls_rk4} calls \
+            (the errors are {error} vs. {error_rk4})"
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,180 @@
+//! Provides the [`integrate_events`] macro, plus tests for the method
+
+/// Defines the [`integrate_events`](crate::GeneralIntegrator#method.integrate_events) method
+macro_rules! integrate_events {
+    () => {
+        /// Integrate the system of 1st-order ODEs, return the time moments
+        /// and the states at which the event function crosses zero
+        ///
+        /// The states between the steps are interpolated with cubic Hermite
+        /// polynomials (using the derivatives from [`update`](Self::update)).
+        /// Each step is checked for the sign changes of the event function
+        /// at a few points, so several crossings in one step are detected
+        /// as long as they're separated by these points. The crossings are
+        /// refined with the bisection method. The points where the event
+        /// function is exactly zero (including the initial one) are events
+        /// themselves. The steps are made one at a time (see [`step`](Self::step)),
+        /// so the multistep methods are rejected (they'd be restarted on each step)
+        ///
+        /// Arguments:
+        /// * `x` --- Vector of initial values;
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Number of iterations;
+        /// * `integrator` --- Integration method;
+        /// * `g` --- Event function (takes the time moment and the state).
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn integrate_events(
+            &self,
+            x: &[F],
+            t_0: F,
+            h: F,
+            n: usize,
+            integrator: Integrators,
+            g: impl Fn(F, &[F]) -> F,
+        ) -> anyhow::Result<Vec<(F, Vec<F>)>> {
+            // Get a token for using the private methods
+            let token = Token {};
+            // Make sure the method doesn't need the previous steps
+            if integrator.is_multistep() {
+                return Err(anyhow::anyhow!(
+                    "The multistep method {integrator:?} can't be used when detecting events"
+                ));
+            }
+            // Prepare a buffer for the steps
+            let mut buffer = self.prepare(x, t_0, 1, &token)?;
+            // Define the number of points checked in each step
+            let points = 4;
+            let mut events = Vec::new();
+            // Check the initial point
+            let mut x_1 = x.to_vec();
+            let mut g_prev = g(t_0, &x_1);
+            if g_prev.is_zero() {
+                events.push((t_0, x_1.clone()));
+            }
+            let mut d_1 = self
+                .update(t_0, &x_1)
+                .with_context(|| "Couldn't compute the initial derivative")?;
+            // Integrate
+            for i in 0..n {
+                // Compute the time moment
+                let t = t_0 + F::from(i).unwrap() * h;
+                // Make a step
+                let x_2 = self
+                    .step(&mut buffer, t, &x_1, h, integrator, &token)
+                    .with_context(|| format!("Couldn't make a step at t = {t}"))?;
+                let d_2 = self
+                    .update(t + h, &x_2)
+                    .with_context(|| format!("Couldn't compute the derivative at t = {}", t + h))?;
+                // Interpolate the state
+                let state = |s: F| hermite(&x_1, &d_1, &x_2, &d_2, h, s);
+                // Check the points of the step
+                let mut s_prev = 0.;
+                for k in 1..=points {
+                    let s = F::from(k).unwrap() / F::from(points).unwrap();
+                    let x_s = if k == points { x_2.clone() } else { state(s) };
+                    let g_s = g(t + s * h, &x_s);
+                    if g_s.is_zero() {
+                        events.push((t + s * h, x_s));
+                    } else if !g_prev.is_zero() && g_s.signum() != g_prev.signum() {
+                        // Refine the crossing
+                        let s_e = bisection(|s| Ok(g(t + s * h, &state(s))), s_prev, s)
+                            .with_context(|| {
+                                format!("Couldn't refine the crossing after t = {t}")
+                            })?;
+                        events.push((t + s_e * h, state(s_e)));
+                    }
+                    g_prev = g_s;
+                    s_prev = s;
+                }
+                x_1 = x_2;
+                d_1 = d_2;
+            }
+            Ok(events)
+        }
+    };
+}
+
+pub(super) use integrate_events;
+
+#[test]
+#[allow(clippy::cast_precision_loss)]
+fn test() -> anyhow::Result<()> {
+    use crate::{Float, GeneralIntegrator, GeneralIntegrators};
+
+    // Implement the trait on a test struct (a harmonic oscillator)
+    struct Test {}
+    impl<F: Float> GeneralIntegrator<F> for Test {
+        fn update(&self, _t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![x[1], -x[0]])
+        }
+    }
+    let test = Test {};
+    let x = [1_f64, 0.];
+
+    // Find the crossings of the position, compare
+    // them with the zeros of the cosine
+    let events = test.integrate_events(
+        &x,
+        0.,
+        0.1,
+        100,
+        GeneralIntegrators::RungeKutta4th,
+        |_, x| x[0],
+    )?;
+    if events.len() != 3
+        || events.iter().enumerate().any(|(k, (t, x))| {
+            (t - (k as f64 + 0.5) * std::f64::consts::PI).abs() >= 1e-5 || x[0].abs() >= 1e-5
+        })
+    {
+        return Err(anyhow::anyhow!(
+            "The crossings of the position are incorrect: {events:?}"
+        ));
+    }
+
+    // Find several crossings per step, check the zero at
+    // the initial point and at one of the time steps
+    let events =
+        test.integrate_events(&x, 0., 1., 10, GeneralIntegrators::RungeKutta4th, |t, _| {
+            f64::sin(5. * t)
+        })?;
+    if events.len() != 16
+        || events
+            .iter()
+            .enumerate()
+            .any(|(k, (t, _))| (t - k as f64 * std::f64::consts::PI / 5.).abs() >= 1e-12)
+    {
+        return Err(anyhow::anyhow!(
+            "The crossings of the sine are incorrect: {events:?}"
+        ));
+    }
+    let events =
+        test.integrate_events(&x, 0., 0.5, 4, GeneralIntegrators::RungeKutta4th, |t, _| {
+            t - 1.
+        })?;
+    if events.len() != 1 || (events[0].0 - 1.).abs() > 0. {
+        return Err(anyhow::anyhow!(
+            "The zero at the time step is incorrect: {events:?}"
+        ));
+    }
+
+    // Check that the multistep methods are rejected
+    if test
+        .integrate_events(
+            &x,
+            0.,
+            0.1,
+            100,
+            GeneralIntegrators::AdamsMoulton {
+                steps: 4,
+                corrections: 1,
+            },
+            |_, x| x[0],
+        )
+        .is_ok()
+    {
+        return Err(anyhow::anyhow!("A multistep method was accepted"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,120 @@
+//! Provides the [`integrate_extrapolated`] macro, plus tests for the method
+
+/// Defines the [`integrate_extrapolated`](crate::GeneralIntegrator#method.integrate_extrapolated) method
+macro_rules! integrate_extrapolated {
+    () => {
+        /// Integrate the system of 1st-order ODEs with the time steps
+        /// `h`, `h / 2`, ..., `h / 2^levels`, return the solution on
+        /// the grid of the biggest time step with the states improved
+        /// by the Richardson extrapolation
+        ///
+        /// The extrapolation is based on the order of the base method and the
+        /// powers in the expansion of its error (see [`order`](crate::GeneralIntegrators::order)
+        /// and [`error_step`](crate::GeneralIntegrators::error_step)).
+        ///
+        /// Arguments:
+        /// * `x` --- Vector of initial values;
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Number of iterations (with the biggest time step);
+        /// * `base` --- Base integration method;
+        /// * `levels` --- Number of halvings of the time step.
+        fn integrate_extrapolated(
+            &self,
+            x: &[F],
+            t_0: F,
+            h: F,
+            n: usize,
+            base: Integrators,
+            levels: usize,
+        ) -> anyhow::Result<Solution<F>> {
+            // Integrate with the biggest time step
+            let mut result = self
+                .integrate(x, t_0, h, n, base)
+                .with_context(|| "Couldn't integrate with the biggest time step")?;
+            // Integrate with the smaller time steps
+            let mut results = Vec::with_capacity(levels);
+            for k in 1..=levels {
+                let m = 1 << k;
+                results.push(
+                    self.integrate(x, t_0, h / F::from(m).unwrap(), n * m, base)
+                        .with_context(|| {
+                            format!("Couldn't integrate with the time step h / {m}")
+                        })?,
+                );
+            }
+            // Extrapolate the states on the common grid
+            for i in 1..=n {
+                let states: Vec<Vec<F>> = std::iter::once(result.state(i))
+                    .chain(
+                        results
+                            .iter()
+                            .enumerate()
+                            .map(|(k, r)| r.state(i << (k + 1))),
+                    )
+                    .collect();
+                result
+                    .set_state_from_slice(i, &richardson(&states, base.order(), base.error_step()));
+            }
+            Ok(result)
+        }
+    };
+}
+
+pub(super) use integrate_extrapolated;
+
+#[test]
+fn test() -> anyhow::Result<()> {
+    use crate::{Float, GeneralIntegrator, GeneralIntegrators, ResultExt};
+
+    // Implement the trait on a test struct
+    struct Test {}
+    impl<F: Float> GeneralIntegrator<F> for Test {
+        fn update(&self, t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![t, x[0] * F::sin(t)])
+        }
+    }
+    let test = Test {};
+
+    // Define the exact solution
+    let exact = |t: f64| -t.powi(2) / 2. * f64::cos(t) + t * f64::sin(t) + f64::cos(t) - 1.;
+
+    // Compute the errors of the extrapolated 2nd-order
+    // Runge-Kutta method with the time step and its half
+    let error = |h: f64, n: usize| -> anyhow::Result<f64> {
+        let result =
+            test.integrate_extrapolated(&[0., 0.], 0., h, n, GeneralIntegrators::RungeKutta2nd, 1)?;
+        Ok((result.state(n)[1] - exact(3.)).abs())
+    };
+    let (error_1, error_2) = (error(0.1, 30)?, error(0.05, 60)?);
+
+    // Check the empirical order
+    let order = (error_1 / error_2).log2();
+    if !(2.7..4.5).contains(&order) {
+        return Err(anyhow::anyhow!(
+            "The empirical order of the extrapolated method is incorrect: {order}"
+        ));
+    }
+
+    // Check the empirical order of the extrapolated implicit midpoint rule
+    // (its error contains only even powers, so two levels give the 6th order)
+    let error = |h: f64, n: usize| -> anyhow::Result<f64> {
+        let result = test.integrate_extrapolated(
+            &[0., 0.],
+            0.,
+            h,
+            n,
+            GeneralIntegrators::ImplicitMidpoint,
+            2,
+        )?;
+        Ok((result.state(n)[1] - exact(3.)).abs())
+    };
+    let order = (error(0.1, 30)? / error(0.05, 60)?).log2();
+    if !(5.5..6.5).contains(&order) {
+        return Err(anyhow::anyhow!(
+            "The empirical order of the extrapolated implicit midpoint rule is incorrect: {order}"
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+//! Provides the [`interpolate`] macro, plus tests for the method
+
+/// Defines the [`interpolate`](crate::GeneralIntegrator#method.interpolate) method
+macro_rules! interpolate {
+    () => {
+        /// Interpolate the solution at the passed time moment using cubic
+        /// Hermite polynomials (with the derivatives from [`update`](Self::update))
+        ///
+        /// Arguments:
+        /// * `solution` --- Solution of the system;
+        /// * `t` --- Time moment.
+        fn interpolate(&self, solution: &Solution<F>, t: F) -> anyhow::Result<Vec<F>> {
+            solution.interpolate(t, |t, x| self.update(t, x))
+        }
+    };
+}
+
+pub(super) use interpolate;
+
+#[test]
+fn test() -> anyhow::Result<()> {
+    use crate::{Float, GeneralIntegrator, GeneralIntegrators};
+
+    // Implement the trait on a test struct (a harmonic oscillator)
+    struct Test {}
+    impl<F: Float> GeneralIntegrator<F> for Test {
+        fn update(&self, _t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![x[1], -x[0]])
+        }
+    }
+    let test = Test {};
+
+    // Integrate with a coarse time step
+    let result = test.integrate(
+        &[1_f64, 0.],
+        0.,
+        0.1,
+        100,
+        GeneralIntegrators::RungeKutta4th,
+    )?;
+
+    // Compare the interpolated states on a dense grid with the exact solution
+    for k in 0..=1000 {
+        let t = f64::from(k) * 1e-2;
+        let x = test.interpolate(&result, t)?;
+        if (x[0] - t.cos()).abs() >= 1e-5 || (x[1] + t.sin()).abs() >= 1e-5 {
+            return Err(anyhow::anyhow!(
+                "The interpolated state at t = {t} is incorrect: {x:?}"
+            ));
+        }
+    }
+
+    // Check that the time moments outside of the solution are rejected
+    if test.interpolate(&result, -0.1).is_ok() || test.interpolate(&result, 10.1).is_ok() {
+        return Err(anyhow::anyhow!(
+            "The time moments outside of the solution weren't rejected"
+        ));
+    }
+
+    Ok(())
+}
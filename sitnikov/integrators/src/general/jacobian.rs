@@ -0,0 +1,88 @@
+//! Provides the [`jacobian`] macro, plus tests for the methods
+
+/// Defines the [`jacobian_fd`](crate::GeneralIntegrator#method.jacobian_fd)
+/// and [`jacobian`](crate::GeneralIntegrator#method.jacobian) methods
+macro_rules! jacobian {
+    () => {
+        /// Compute the Jacobian matrix of the system at
+        /// the current state using central differences
+        ///
+        /// Arguments:
+        /// * `t` --- Current time moment;
+        /// * `x` --- Current state of the system;
+        /// * `eps` --- Relative increment of the state components.
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn jacobian_fd(&self, t: F, x: &[F], eps: F) -> anyhow::Result<DMatrix<F>> {
+            let l = x.len();
+            let mut jacobian = DMatrix::zeros(l, l);
+            let mut x_m = x.to_vec();
+            // Compute the Jacobian column by column
+            for j in 0..l {
+                // Choose the increment relative to the magnitude of the component
+                let eps = eps * F::max(x[j].abs(), 1.);
+                // Compute the states on both sides of the current one
+                x_m[j] = x[j] + eps;
+                let f_r = self
+                    .update(t, &x_m)
+                    .with_context(|| "Couldn't compute the right state")?;
+                x_m[j] = x[j] - eps;
+                let f_l = self
+                    .update(t, &x_m)
+                    .with_context(|| "Couldn't compute the left state")?;
+                x_m[j] = x[j];
+                // Compute the column
+                for i in 0..l {
+                    jacobian[(i, j)] = (f_r[i] - f_l[i]) / (2. * eps);
+                }
+            }
+            Ok(jacobian)
+        }
+
+        /// Compute the Jacobian matrix of the system at the current state
+        /// (analytically if that's defined, using central differences otherwise)
+        ///
+        /// Arguments:
+        /// * `t` --- Current time moment;
+        /// * `x` --- Current state of the system;
+        /// * `token` --- Private token.
+        fn jacobian(&self, t: F, x: &[F], _: &Token) -> anyhow::Result<DMatrix<F>> {
+            match self.analytic_jacobian(t, x) {
+                Some(jacobian) => Ok(jacobian),
+                None => self.jacobian_fd(t, x, F::epsilon().sqrt()),
+            }
+        }
+    };
+}
+
+pub(super) use jacobian;
+
+#[test]
+fn test() -> anyhow::Result<()> {
+    use crate::{Float, GeneralIntegrator};
+    use nalgebra::DMatrix;
+
+    // Implement the trait on a test struct (a linear system)
+    struct Test {}
+    impl<F: Float> GeneralIntegrator<F> for Test {
+        fn update(&self, t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            let two = F::from(2.).unwrap();
+            Ok(vec![
+                two * x[0] - x[1] + t,
+                x[0] / two - F::from(3.).unwrap() * x[1],
+            ])
+        }
+    }
+    let test = Test {};
+
+    // Compare the finite-difference Jacobian with the analytic one
+    let eps = f64::EPSILON.sqrt();
+    let jacobian = test.jacobian_fd(1., &[0.3, -20.], eps)?;
+    let analytic = DMatrix::from_row_slice(2, 2, &[2., -1., 0.5, -3.]);
+    if (&jacobian - &analytic).amax() >= eps {
+        return Err(anyhow::anyhow!(
+            "The finite-difference Jacobian is incorrect: {analytic} vs. {jacobian}"
+        ));
+    }
+
+    Ok(())
+}
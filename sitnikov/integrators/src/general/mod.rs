@@ -1,27 +1,187 @@
 //! Provides the [`GeneralIntegrator`](crate::GeneralIntegrator) trait
 
+#[doc(hidden)]
+mod adams_bashforth;
+#[doc(hidden)]
+mod adams_moulton;
+#[doc(hidden)]
+mod cash_karp;
+#[doc(hidden)]
+mod convergence_study;
+#[doc(hidden)]
+mod embedded_step;
+#[doc(hidden)]
+mod euler;
+#[doc(hidden)]
+mod implicit_euler;
+#[doc(hidden)]
+mod implicit_midpoint;
 #[doc(hidden)]
 mod integrate;
 #[doc(hidden)]
+mod integrate_adaptive;
+#[cfg(feature = "parallel")]
+#[doc(hidden)]
+mod integrate_batch;
+#[doc(hidden)]
+mod integrate_bulirsch_stoer;
+#[doc(hidden)]
+mod integrate_events;
+#[doc(hidden)]
+mod integrate_extrapolated;
+#[doc(hidden)]
+mod integrate_streaming;
+#[doc(hidden)]
+mod integrate_until;
+#[doc(hidden)]
+mod integrate_while;
+#[doc(hidden)]
+mod interpolate;
+#[doc(hidden)]
+mod jacobian;
+#[doc(hidden)]
+mod rosenbrock_2nd;
+#[doc(hidden)]
+mod runge_kutta_2nd;
+#[doc(hidden)]
+mod runge_kutta_3rd;
+#[doc(hidden)]
+mod runge_kutta_45;
+#[doc(hidden)]
 mod runge_kutta_4th;
 
 #[cfg(test)]
 mod test_method;
 
 use anyhow::{self, Context};
-use nalgebra::DVector;
+use nalgebra::DMatrix;
 use numeric_literals::replace_float_literals;
 
+use std::collections::VecDeque;
+use std::path::Path;
+
+use crate::bisection::bisection;
+use crate::checkpoint::{load_checkpoint, save_checkpoint};
+use crate::hermite::hermite;
+use crate::integrate_resumable::integrate_resumable;
+use crate::lu;
+use crate::newton::newton;
+use crate::pairs::{Pairs, Tableau};
 use crate::prepare::prepare;
-use crate::{Float, Result, ResultExt, Token};
+use crate::richardson::richardson;
+use crate::step::step;
+use crate::steps::steps;
+use crate::{CheckpointParameters, Float, Result, ResultExt, Solution, Token, Tolerances};
 
+pub(self) use adams_bashforth::adams_bashforth;
+pub(self) use adams_moulton::adams_moulton;
+pub(self) use cash_karp::cash_karp;
+pub(self) use convergence_study::convergence_study;
+pub(self) use embedded_step::embedded_step;
+pub(self) use euler::euler;
+pub(self) use implicit_euler::implicit_euler;
+pub(self) use implicit_midpoint::implicit_midpoint;
 pub(self) use integrate::integrate;
+pub(self) use integrate_adaptive::integrate_adaptive;
+#[cfg(feature = "parallel")]
+pub(self) use integrate_batch::integrate_batch;
+pub(self) use integrate_bulirsch_stoer::integrate_bulirsch_stoer;
+pub(self) use integrate_events::integrate_events;
+pub(self) use integrate_extrapolated::integrate_extrapolated;
+pub(self) use integrate_streaming::integrate_streaming;
+pub(self) use integrate_until::integrate_until;
+pub(self) use integrate_while::integrate_while;
+pub(self) use interpolate::interpolate;
+pub(self) use jacobian::jacobian;
+pub(self) use rosenbrock_2nd::rosenbrock_2nd;
+pub(self) use runge_kutta_2nd::runge_kutta_2nd;
+pub(self) use runge_kutta_3rd::runge_kutta_3rd;
+pub(self) use runge_kutta_45::runge_kutta_45;
 pub(self) use runge_kutta_4th::runge_kutta_4th;
 
 /// General integrators
+#[derive(Clone, Copy, Debug)]
 pub enum Integrators {
+    /// Adams-Bashforth method with the specified number of steps (from 1 to 4)
+    AdamsBashforth {
+        /// Number of steps (which is also the order)
+        steps: usize,
+    },
+    /// Adams-Bashforth-Moulton predictor-corrector method with the
+    /// specified number of steps of the predictor (from 1 to 4)
+    AdamsMoulton {
+        /// Number of steps of the predictor (which is also the order)
+        steps: usize,
+        /// Number of correction sweeps
+        corrections: usize,
+    },
+    /// Cash-Karp method (the 5th-order estimates are kept)
+    CashKarp,
+    /// (Forward) Euler method
+    Euler,
+    /// Implicit (backward) Euler method
+    ImplicitEuler,
+    /// Implicit midpoint rule (symplectic)
+    ImplicitMidpoint,
+    /// 2nd-order Rosenbrock method
+    Rosenbrock2nd,
+    /// 2nd-order Runge-Kutta (midpoint) method
+    RungeKutta2nd,
+    /// 3rd-order Runge-Kutta (Kutta's) method
+    RungeKutta3rd,
     /// 4th-order Runge-Kutta method
     RungeKutta4th,
+    /// Runge-Kutta-Fehlberg method (the 5th-order estimates are kept)
+    RungeKutta45,
+}
+
+/// Get the theoretical order of the method (e.g., the expected
+/// slope of the error versus the time step on a log-log scale)
+///
+/// Arguments:
+/// * `method` --- Integration method.
+#[must_use]
+pub fn order(method: &Integrators) -> usize {
+    match *method {
+        Integrators::AdamsBashforth { steps } | Integrators::AdamsMoulton { steps, .. } => steps,
+        Integrators::Euler | Integrators::ImplicitEuler => 1,
+        Integrators::ImplicitMidpoint | Integrators::Rosenbrock2nd | Integrators::RungeKutta2nd => {
+            2
+        }
+        Integrators::RungeKutta3rd => 3,
+        Integrators::RungeKutta4th => 4,
+        Integrators::CashKarp | Integrators::RungeKutta45 => 5,
+    }
+}
+
+impl Integrators {
+    /// Get the order of the method (see [`general_order`](crate::general_order))
+    #[must_use]
+    pub fn order(self) -> usize {
+        order(&self)
+    }
+
+    /// Check if the method is a multistep one (reusing the
+    /// right-hand sides computed on the previous steps)
+    #[must_use]
+    pub fn is_multistep(self) -> bool {
+        matches!(
+            self,
+            Integrators::AdamsBashforth { steps } | Integrators::AdamsMoulton { steps, .. }
+                if steps > 1
+        )
+    }
+
+    /// Get the step between the powers of the time step in the expansion
+    /// of the error of the method (the errors of the symmetric methods
+    /// contain only even powers, so the step is 2 for them)
+    #[must_use]
+    pub fn error_step(self) -> usize {
+        match self {
+            Integrators::ImplicitMidpoint => 2,
+            _ => 1,
+        }
+    }
 }
 
 /// A general integrator for a system of 1st-order ODEs
@@ -33,8 +193,47 @@ pub trait Integrator<F: Float> {
     /// * `t` --- Current time moment;
     /// * `x` --- Current state of the system.
     fn update(&self, t: F, x: &[F]) -> anyhow::Result<Vec<F>>;
+    /// Compute the Jacobian matrix of the system analytically (if it's
+    /// not defined, the Jacobian is approximated by central differences)
+    ///
+    /// Arguments:
+    /// * `t` --- Current time moment;
+    /// * `x` --- Current state of the system.
+    fn analytic_jacobian(&self, _t: F, _x: &[F]) -> Option<DMatrix<F>> {
+        None
+    }
+    /// Get the length of the state of the system (if it's defined,
+    /// the vectors of initial values are checked against it)
+    fn dim(&self) -> Option<usize> {
+        None
+    }
     // The rest of the methods are defined by these macros
+    adams_bashforth!();
+    adams_moulton!();
+    cash_karp!();
+    convergence_study!();
+    embedded_step!();
+    euler!();
+    implicit_euler!();
+    implicit_midpoint!();
     integrate!();
+    integrate_adaptive!();
+    #[cfg(feature = "parallel")]
+    integrate_batch!();
+    integrate_bulirsch_stoer!();
+    integrate_events!();
+    integrate_extrapolated!();
+    integrate_resumable!();
+    integrate_streaming!();
+    integrate_until!();
+    integrate_while!();
+    interpolate!();
+    jacobian!();
     prepare!();
+    rosenbrock_2nd!();
+    runge_kutta_2nd!();
+    runge_kutta_3rd!();
+    runge_kutta_45!();
     runge_kutta_4th!();
+    step!();
 }
@@ -0,0 +1,136 @@
+//! Provides the [`rosenbrock_2nd`] macro, plus tests for the method
+
+/// Defines the [`rosenbrock_2nd`](crate::GeneralIntegrator#method.rosenbrock_2nd) method
+macro_rules! rosenbrock_2nd {
+    () => {
+        /// Integrate the system using the 2nd-order Rosenbrock method
+        /// (ROS2, see J. G. Verwer et al., 1999)
+        ///
+        /// This is a linearly-implicit method: instead of solving
+        /// a non-linear system on each iteration, it solves two linear
+        /// systems with the same matrix built from the Jacobian. This
+        /// makes it suitable for (mildly) stiff systems.
+        ///
+        /// Arguments:
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Number of iterations;
+        /// * `result` --- Solution;
+        /// * `callback` --- Function called after each step (returns `false` to stop);
+        /// * `token` --- Private token.
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn rosenbrock_2nd(
+            &self,
+            t_0: F,
+            h: F,
+            n: usize,
+            result: &mut Solution<F>,
+            callback: &mut dyn FnMut(usize, F, &[F]) -> bool,
+            token: &Token,
+        ) -> anyhow::Result<()> {
+            // Compute the diagonal coefficient
+            let gamma = F::coefficients().gamma;
+            // Get the initial state
+            let mut x = result.initial_values();
+            // Integrate
+            for i in 0..n {
+                // Compute the time moment
+                let t = t_0 + F::from(i).unwrap() * h;
+                // Compute the matrix of the linear systems
+                let jacobian = self
+                    .jacobian(t, &x, token)
+                    .with_context(|| "Couldn't compute the Jacobian")?;
+                let w = DMatrix::from_fn(x.len(), x.len(), |i, j| {
+                    let delta = if i == j { 1. } else { 0. };
+                    delta - gamma * h * jacobian[(i, j)]
+                });
+                let w = lu::lu(&w);
+                // Compute the first increment
+                let f = self
+                    .update(t, &x)
+                    .with_context(|| "Couldn't compute the first right-hand side")?;
+                let k_1 =
+                    lu::solve(&w, &f).with_context(|| "Couldn't solve for the first increment")?;
+                // Compute the modified state for the second increment
+                let x_m: Vec<F> = x
+                    .iter()
+                    .zip(k_1.iter())
+                    .map(|(&x, &k_1)| x + h * k_1)
+                    .collect();
+                // Compute the second increment
+                let f: Vec<F> = self
+                    .update(t + h, &x_m)
+                    .with_context(|| "Couldn't compute the second right-hand side")?
+                    .iter()
+                    .zip(k_1.iter())
+                    .map(|(&f, &k_1)| f - 2. * k_1)
+                    .collect();
+                let k_2 =
+                    lu::solve(&w, &f).with_context(|| "Couldn't solve for the second increment")?;
+                // Compute the final modified state
+                x = x
+                    .iter()
+                    .zip(k_1.iter())
+                    .zip(k_2.iter())
+                    .map(|((&x, &k_1), &k_2)| x + h * (1.5 * k_1 + 0.5 * k_2))
+                    .collect();
+                // Put the new state in the result
+                result.set_state_from_slice(i + 1, &x);
+                result.set_time(i + 1, t_0 + F::from(i + 1).unwrap() * h);
+                if !callback(i + 1, result.times()[i + 1], &x) {
+                    break;
+                }
+            }
+            Ok(())
+        }
+    };
+}
+
+pub(super) use rosenbrock_2nd;
+
+#[cfg(test)]
+super::test_method::test_method!(GeneralIntegrators::Rosenbrock2nd);
+
+#[test]
+fn test_stiff() -> anyhow::Result<()> {
+    use crate::{GeneralIntegrator, GeneralIntegrators, ResultExt};
+
+    // Implement the trait on a stiff linear system
+    struct Test {}
+    impl GeneralIntegrator<f64> for Test {
+        fn update(&self, _t: f64, x: &[f64]) -> anyhow::Result<Vec<f64>> {
+            Ok(vec![-1000. * x[0]])
+        }
+    }
+    let test = Test {};
+
+    // Define the integration parameters (the step is outside
+    // the stability region of the 4th-order Runge-Kutta method)
+    let x = [1.];
+    let t_0 = 0.;
+    let h = 1e-2;
+    let n = 100;
+
+    // Integrate using both methods
+    let rk = test
+        .integrate(&x, t_0, h, n, GeneralIntegrators::RungeKutta4th)
+        .with_context(|| "Couldn't integrate using the Runge-Kutta method")?;
+    let ros = test
+        .integrate(&x, t_0, h, n, GeneralIntegrators::Rosenbrock2nd)
+        .with_context(|| "Couldn't integrate using the Rosenbrock method")?;
+
+    // Check the results
+    let (x_rk, x_ros) = (rk.state(n)[0], ros.state(n)[0]);
+    if x_rk.abs() <= 1. {
+        return Err(anyhow::anyhow!(
+            "The Runge-Kutta method is expected to be unstable: {x_rk}"
+        ));
+    }
+    if x_ros.abs() >= 1e-6 {
+        return Err(anyhow::anyhow!(
+            "The Rosenbrock method is expected to be stable: {x_ros}"
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+//! Provides the [`runge_kutta_2nd`] macro, plus tests for the method
+
+/// Defines the [`runge_kutta_2nd`](crate::GeneralIntegrator#method.runge_kutta_2nd) method
+macro_rules! runge_kutta_2nd {
+    () => {
+        /// Integrate the system using the 2nd-order Runge-Kutta (midpoint) method
+        ///
+        /// Arguments:
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Number of iterations;
+        /// * `result` --- Solution;
+        /// * `callback` --- Function called after each step (returns `false` to stop);
+        /// * `token` --- Private token.
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn runge_kutta_2nd(
+            &self,
+            t_0: F,
+            h: F,
+            n: usize,
+            result: &mut Solution<F>,
+            callback: &mut dyn FnMut(usize, F, &[F]) -> bool,
+            _: &Token,
+        ) -> anyhow::Result<()> {
+            // Get the initial state
+            let mut x = result.initial_values();
+            // Integrate
+            for i in 0..n {
+                // Compute the time moments
+                let t = t_0 + F::from(i).unwrap() * h;
+                let t_2 = t + h / 2.;
+                // Compute the first increment
+                let k_1 = &self
+                    .update(t, &x)
+                    .with_context(|| "Couldn't compute the first increment")?;
+                // Compute the modified state for the second increment
+                let x_m: Vec<F> = x
+                    .iter()
+                    .zip(k_1.iter())
+                    .map(|(&x, &k_1)| x + h * k_1 / 2.)
+                    .collect();
+                // Compute the second increment
+                let k_2 = self
+                    .update(t_2, &x_m)
+                    .with_context(|| "Couldn't compute the second increment")?;
+                // Compute the final modified state
+                x = x
+                    .iter()
+                    .zip(k_2.iter())
+                    .map(|(&x, &k_2)| x + h * k_2)
+                    .collect();
+                // Put the new state in the result
+                result.set_state_from_slice(i + 1, &x);
+                result.set_time(i + 1, t_0 + F::from(i + 1).unwrap() * h);
+                if !callback(i + 1, result.times()[i + 1], &x) {
+                    break;
+                }
+            }
+            Ok(())
+        }
+    };
+}
+
+pub(super) use runge_kutta_2nd;
+
+#[cfg(test)]
+super::test_method::test_method!(GeneralIntegrators::RungeKutta2nd);
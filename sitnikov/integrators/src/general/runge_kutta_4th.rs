@@ -9,7 +9,8 @@ macro_rules! runge_kutta_4th {
         /// * `t_0` --- Initial value of time;
         /// * `h` --- Time step;
         /// * `n` --- Number of iterations;
-        /// * `result` --- Result matrix;
+        /// * `result` --- Solution;
+        /// * `callback` --- Function called after each step (returns `false` to stop);
         /// * `token` --- Private token.
         #[replace_float_literals(F::from(literal).unwrap())]
         fn runge_kutta_4th(
@@ -17,7 +18,8 @@ macro_rules! runge_kutta_4th {
             t_0: F,
             h: F,
             n: usize,
-            result: &mut Result<F>,
+            result: &mut Solution<F>,
+            callback: &mut dyn FnMut(usize, F, &[F]) -> bool,
             _: &Token,
         ) -> anyhow::Result<()> {
             // Get the initial state
@@ -75,7 +77,11 @@ macro_rules! runge_kutta_4th {
                     })
                     .collect();
                 // Put the new state in the result
-                result.set_state(i + 1, x.clone());
+                result.set_state_from_slice(i + 1, &x);
+                result.set_time(i + 1, t_0 + F::from(i + 1).unwrap() * h);
+                if !callback(i + 1, result.times()[i + 1], &x) {
+                    break;
+                }
             }
             Ok(())
         }
@@ -85,4 +91,4 @@ macro_rules! runge_kutta_4th {
 pub(super) use runge_kutta_4th;
 
 #[cfg(test)]
-super::test_method::test_method!(runge_kutta_4th, 4);
+super::test_method::test_method!(GeneralIntegrators::RungeKutta4th);
@@ -1,9 +1,18 @@
 //! Provides the [`test`] macro
 
-/// Tests the method for correctness and time reversibility
+/// Tests the method of the passed integrator for correctness and time
+/// reversibility (the method is called via [`solve`], so it always matches
+/// the integrator, which also provides the order)
+///
+/// The tolerance is `factor * h^order` (`factor` is 10 by default). A method
+/// without an integrator of its own (e.g., another implementation) can be
+/// tested in place of an integrator by passing it first, as in
+/// `test_method!(method as integrator)`
+///
+/// [`solve`]: crate::GeneralIntegrator#method.solve
 #[cfg(test)]
 macro_rules! test_method {
-    ($method:ident, $order:literal) => {
+    (@test $integrator:expr, $factor:literal, $integrate:expr) => {
         #[cfg(test)]
         use anyhow::{self, Context};
 
@@ -11,7 +20,9 @@ macro_rules! test_method {
         #[allow(clippy::cast_precision_loss)]
         fn test() -> anyhow::Result<()> {
             use crate::private::Token;
-            use crate::{Float, GeneralIntegrator, ResultExt};
+            use crate::{
+                general_order, Float, GeneralIntegrator, GeneralIntegrators, ResultExt, Solution,
+            };
 
             // Implement the trait on a test struct
             type F = f64;
@@ -22,18 +33,21 @@ macro_rules! test_method {
                 }
             }
             let test = Test {};
+            let integrate: fn(&Test, &mut Solution<F>, F, F, usize, &Token) -> anyhow::Result<()> =
+                $integrate;
 
             // Define the integration parameters
             let x = vec![0., 0.];
             let t_0 = 0.;
             let h = 1e-2;
+            let order = i32::try_from(general_order(&$integrator)).unwrap();
             let n = 3000;
             let t = t_0 + h * n as f64;
             let token = Token {};
 
             // Integrate forward
-            let mut result = test.prepare(x, n, &token);
-            test.$method(t_0, h, n, &mut result, &token)
+            let mut result = test.prepare(&x, t_0, n, &token)?;
+            integrate(&test, &mut result, t_0, h, n, &token)
                 .with_context(|| "Couldn't integrate forward")?;
 
             // Check the results
@@ -44,15 +58,24 @@ macro_rules! test_method {
             let x: Vec<F> = result.state(n);
             if x.iter()
                 .zip(x_0.iter())
-                .any(|(&x, &x_0)| (x - x_0).abs() >= 10. * h.powi($order))
+                .any(|(&x, &x_0)| (x - x_0).abs() >= $factor * h.powi(order))
             {
                 return Err(anyhow::anyhow!(
                     "The result of integration is not the same as expected: {x_0:?} vs {x:?}"
                 ));
             }
 
+            // Check the time moments
+            let times = result.times();
+            if times.len() != n + 1 || (times[n] - t).abs() >= 1e-12 {
+                return Err(anyhow::anyhow!(
+                    "The time moments are not the same as expected: {t} vs {:?}",
+                    times.last()
+                ));
+            }
+
             // Integrate backward
-            test.$method(t, -h, n, &mut result, &token)
+            integrate(&test, &mut result, t, -h, n, &token)
                 .with_context(|| "Couldn't integrate backward")?;
 
             // Check the results
@@ -60,7 +83,7 @@ macro_rules! test_method {
             let x: Vec<F> = result.state(0);
             if x.iter()
                 .zip(x_0.iter())
-                .any(|(&x, &x_0)| (x - x_0).abs() >= 10. * h.powi($order))
+                .any(|(&x, &x_0)| (x - x_0).abs() >= $factor * h.powi(order))
             {
                 return Err(anyhow::anyhow!(
                     "The integrator doesn't have time reversibility: {x_0:?} vs {x:?}"
@@ -70,6 +93,23 @@ macro_rules! test_method {
             Ok(())
         }
     };
+    ($method:ident as $integrator:expr) => {
+        super::test_method::test_method!(@test $integrator, 10., |test, result, t_0, h, n, token| {
+            test.$method(t_0, h, n, result, &mut |_, _, _| true, token)
+        });
+    };
+    ($integrator:expr) => {
+        super::test_method::test_method!($integrator, 10.);
+    };
+    ($integrator:expr, $factor:literal) => {
+        super::test_method::test_method!(
+            @test $integrator,
+            $factor,
+            |test, result, t_0, h, n, token| {
+                test.solve(result, t_0, h, n, $integrator, &mut |_, _, _| true, token)
+            }
+        );
+    };
 }
 
 #[cfg(test)]
@@ -0,0 +1,25 @@
+//! Provides the [`hermite`] routine
+
+use crate::Float;
+
+/// Interpolate the state between two steps using cubic Hermite polynomials
+///
+/// Arguments:
+/// * `x_1` --- State at the start of the time step;
+/// * `d_1` --- Derivative of the state at the start of the time step;
+/// * `x_2` --- State at the end of the time step;
+/// * `d_2` --- Derivative of the state at the end of the time step;
+/// * `h` --- Time step;
+/// * `s` --- Fraction of the time step.
+pub fn hermite<F: Float>(x_1: &[F], d_1: &[F], x_2: &[F], d_2: &[F], h: F, s: F) -> Vec<F> {
+    let (two, three) = (F::from(2.).unwrap(), F::from(3.).unwrap());
+    // Compute the basis functions
+    let h_00 = two * s.powi(3) - three * s.powi(2) + F::one();
+    let h_10 = s.powi(3) - two * s.powi(2) + s;
+    let h_01 = -two * s.powi(3) + three * s.powi(2);
+    let h_11 = s.powi(3) - s.powi(2);
+    // Interpolate
+    (0..x_1.len())
+        .map(|j| h_00 * x_1[j] + h_10 * h * d_1[j] + h_01 * x_2[j] + h_11 * h * d_2[j])
+        .collect()
+}
@@ -0,0 +1,239 @@
+//! Provides the [`integrate_resumable`] macro, plus tests for the method
+
+/// Defines the `integrate_resumable` method of both integrators (see
+/// [`GeneralIntegrator`](crate::GeneralIntegrator#method.integrate_resumable)
+/// and [`SymplecticIntegrator`](crate::SymplecticIntegrator#method.integrate_resumable))
+macro_rules! integrate_resumable {
+    () => {
+        /// Integrate the system of 1st-order ODEs, saving a checkpoint (see
+        /// [`save_checkpoint`](crate::save_checkpoint)) every `every` steps.
+        /// If the checkpoint file already exists, the integration is resumed
+        /// from it (an error is returned if the checkpoint was saved by an
+        /// integration with different parameters, see [`CheckpointParameters`](crate::CheckpointParameters))
+        ///
+        /// Note that the solution of a resumed integration covers only the
+        /// steps from the checkpoint onward: its first state is the
+        /// checkpointed one, not the vector of initial values
+        ///
+        /// The integration is always made in chunks ending on the multiples of
+        /// `every` steps, so a resumed integration makes exactly the same
+        /// operations as an uninterrupted one, and the states are identical.
+        /// The multistep methods are rejected, since they'd be restarted
+        /// at the start of each chunk
+        ///
+        /// Arguments:
+        /// * `x` --- Vector of initial values;
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Number of iterations;
+        /// * `integrator` --- Integration method;
+        /// * `path` --- Path to the checkpoint file;
+        /// * `every` --- Number of steps between the checkpoints.
+        #[allow(clippy::too_many_arguments)]
+        fn integrate_resumable(
+            &self,
+            x: &[F],
+            t_0: F,
+            h: F,
+            n: usize,
+            integrator: Integrators,
+            path: &Path,
+            every: usize,
+        ) -> anyhow::Result<Solution<F>> {
+            if every == 0 {
+                return Err(anyhow::anyhow!(
+                    "The number of steps between the checkpoints must be positive"
+                ));
+            }
+            if integrator.is_multistep() {
+                return Err(anyhow::anyhow!(
+                    "The multistep method {integrator:?} can't be used when checkpointing"
+                ));
+            }
+            // Start from the checkpoint if it exists (and
+            // it was saved by the same integration)
+            let parameters = CheckpointParameters {
+                len: x.len(),
+                t_0,
+                h,
+                n,
+                integrator: format!("{integrator:?}"),
+            };
+            let (mut step, mut x, mut t) = if path.exists() {
+                load_checkpoint(path, &parameters)
+                    .with_context(|| "Couldn't load the checkpoint")?
+            } else {
+                (0, x.to_vec(), t_0)
+            };
+            // Integrate in chunks
+            let mut result: Option<Solution<F>> = None;
+            while step < n {
+                let m = usize::min(every - step % every, n - step);
+                let chunk = self
+                    .integrate(&x, t, h, m, integrator)
+                    .with_context(|| format!("Couldn't integrate from the step {step}"))?;
+                step += m;
+                x = chunk.state(m);
+                t = chunk.times()[m];
+                save_checkpoint(path, &parameters, step, &x, t)
+                    .with_context(|| format!("Couldn't save the checkpoint at the step {step}"))?;
+                match result {
+                    Some(ref mut result) => result.append(&chunk),
+                    None => result = Some(chunk),
+                }
+            }
+            match result {
+                Some(result) => Ok(result),
+                None => self.prepare(&x, t, 0, &Token {}),
+            }
+        }
+    };
+}
+
+pub(super) use integrate_resumable;
+
+/// A test system (a forced harmonic oscillator) which
+/// fails after the time moment `t_fail`, if it's set
+#[cfg(test)]
+struct Test {
+    /// Time moment of the failure
+    t_fail: Option<f64>,
+}
+
+#[cfg(test)]
+impl Test {
+    /// Compute the acceleration (unless the system failed)
+    fn acceleration(&self, t: f64, z: f64) -> anyhow::Result<f64> {
+        if matches!(self.t_fail, Some(t_fail) if t > t_fail) {
+            return Err(anyhow::anyhow!("The system failed at {t}"));
+        }
+        Ok(t - z)
+    }
+}
+
+#[cfg(test)]
+impl crate::GeneralIntegrator<f64> for Test {
+    fn update(&self, t: f64, x: &[f64]) -> anyhow::Result<Vec<f64>> {
+        Ok(vec![x[1], self.acceleration(t, x[0])?])
+    }
+}
+
+#[cfg(test)]
+impl crate::SymplecticIntegrator<f64> for Test {
+    fn accelerations(&self, t: f64, x: &[f64]) -> anyhow::Result<Vec<f64>> {
+        Ok(vec![self.acceleration(t, x[0])?])
+    }
+}
+
+/// Integrate the test system with the passed resumable integration
+/// routine, interrupt it after a checkpoint, resume it, and compare
+/// the result with the one of an uninterrupted integration
+///
+/// Arguments:
+/// * `name` --- Name of the checkpoint file;
+/// * `integrate` --- Integration routine (takes the system, the number
+///   of iterations and the path to the checkpoint file).
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+fn test_resumable(
+    name: &str,
+    integrate: impl Fn(&Test, usize, &std::path::Path) -> anyhow::Result<crate::Solution<f64>>,
+) -> anyhow::Result<()> {
+    use crate::ResultExt;
+
+    let (test, interrupted) = (Test { t_fail: None }, Test { t_fail: Some(4.05) });
+    let n = 1000;
+
+    // Integrate without interruptions
+    let path = std::env::temp_dir().join(name);
+    let _ = std::fs::remove_file(&path);
+    let result = integrate(&test, n, &path)?;
+    std::fs::remove_file(&path)?;
+
+    // Integrate with an interruption after a checkpoint, then resume
+    if integrate(&interrupted, n, &path).is_ok() {
+        return Err(anyhow::anyhow!("The integration wasn't interrupted"));
+    }
+    let resumed = integrate(&test, n, &path)?;
+
+    // Check that the integration with different parameters isn't resumed
+    let mismatched = integrate(&test, 2 * n, &path);
+    std::fs::remove_file(&path)?;
+    if mismatched.is_ok() {
+        return Err(anyhow::anyhow!(
+            "The integration with different parameters was resumed"
+        ));
+    }
+
+    // Compare the results (the resumed solution starts from the checkpoint)
+    if result.ncols() != n + 1
+        || resumed.ncols() != n - 400 + 1
+        || (0..=n - 400).any(|i| resumed.state(i) != result.state(400 + i))
+        || resumed.times() != &result.times()[400..]
+    {
+        return Err(anyhow::anyhow!(
+            "The resumed integration differs from the uninterrupted one"
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_general() -> anyhow::Result<()> {
+    use crate::{GeneralIntegrator, GeneralIntegrators};
+
+    test_resumable("integrators-test-general-resumable.bin", |test, n, path| {
+        GeneralIntegrator::integrate_resumable(
+            test,
+            &[1., 0.],
+            0.,
+            1e-2,
+            n,
+            GeneralIntegrators::RungeKutta4th,
+            path,
+            100,
+        )
+    })?;
+
+    // Check that the multistep methods are rejected
+    let path = std::env::temp_dir().join("integrators-test-multistep-resumable.bin");
+    if GeneralIntegrator::integrate_resumable(
+        &Test { t_fail: None },
+        &[1., 0.],
+        0.,
+        1e-2,
+        1000,
+        GeneralIntegrators::AdamsBashforth { steps: 4 },
+        &path,
+        100,
+    )
+    .is_ok()
+        || path.exists()
+    {
+        return Err(anyhow::anyhow!("A multistep method was accepted"));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_symplectic() -> anyhow::Result<()> {
+    use crate::{SymplecticIntegrator, SymplecticIntegrators};
+
+    test_resumable(
+        "integrators-test-symplectic-resumable.bin",
+        |test, n, path| {
+            SymplecticIntegrator::integrate_resumable(
+                test,
+                &[1., 0., -1.],
+                0.,
+                1e-2,
+                n,
+                SymplecticIntegrators::Yoshida4th,
+                path,
+                100,
+            )
+        },
+    )
+}
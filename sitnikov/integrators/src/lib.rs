@@ -2,17 +2,51 @@
 //! 1st-order ordinary differential equations (ODEs). Specifically,
 //! a user can implement either the [`GeneralIntegrator`] trait or
 //! the [`SymplecticIntegrator`] trait by defining the system in
-//! question and gain a variety of methods for integrating it.
+//! question and gain a variety of methods for integrating it. The
+//! systems with a complex-valued state can implement the
+//! [`ComplexIntegrator`] trait instead.
 
 #[doc(hidden)]
 mod general;
 #[doc(hidden)]
 mod symplectic;
 
+#[doc(hidden)]
+mod bisection;
+#[doc(hidden)]
+mod cached;
+#[doc(hidden)]
+mod checkpoint;
+#[doc(hidden)]
+mod coefficients;
+#[doc(hidden)]
+mod complex;
+#[doc(hidden)]
+mod counted;
+#[doc(hidden)]
+mod hermite;
+#[doc(hidden)]
+mod integrate_resumable;
+#[doc(hidden)]
+mod lu;
+#[doc(hidden)]
+mod newton;
+#[doc(hidden)]
+mod pairs;
 #[doc(hidden)]
 mod prepare;
 #[doc(hidden)]
 mod result;
+#[doc(hidden)]
+mod richardson;
+#[doc(hidden)]
+mod solution;
+#[doc(hidden)]
+mod step;
+#[doc(hidden)]
+mod steps;
+#[doc(hidden)]
+mod tolerances;
 
 /// Provides a private [`Token`]
 mod private {
@@ -28,14 +62,42 @@ use std::fmt::{Debug, Display};
 
 use private::Token;
 
-pub use general::{Integrator as GeneralIntegrator, Integrators as GeneralIntegrators};
+pub use bisection::bisection;
+pub use cached::Integrator as CachedIntegrator;
+pub use checkpoint::{load_checkpoint, save_checkpoint, Parameters as CheckpointParameters};
+pub use coefficients::Coefficients;
+pub use complex::{Integrator as ComplexIntegrator, Solution as ComplexSolution};
+pub use counted::Integrator as CountedIntegrator;
+pub use general::{
+    order as general_order, Integrator as GeneralIntegrator, Integrators as GeneralIntegrators,
+};
+pub use hermite::hermite;
+pub use newton::newton;
+pub use pairs::Pairs as EmbeddedPairs;
 pub use result::{Ext as ResultExt, Result};
-pub use symplectic::{Integrator as SymplecticIntegrator, Integrators as SymplecticIntegrators};
+pub use solution::Solution;
+pub use symplectic::{
+    order as symplectic_order, Integrator as SymplecticIntegrator,
+    Integrators as SymplecticIntegrators,
+};
+pub use tolerances::{Norm as ErrorNorm, Tolerances};
 
 /// A general trait for all floating point type numbers
-pub trait Float: 'static + Copy + Debug + Display + NumFloat {}
-impl Float for f32 {}
-impl Float for f64 {}
+pub trait Float: 'static + Copy + Debug + Display + NumFloat {
+    /// Get the coefficients of the integration methods
+    /// computed in the precision of this type
+    fn coefficients() -> &'static Coefficients<Self>;
+}
+impl Float for f32 {
+    fn coefficients() -> &'static Coefficients<Self> {
+        &coefficients::F32
+    }
+}
+impl Float for f64 {
+    fn coefficients() -> &'static Coefficients<Self> {
+        &coefficients::F64
+    }
+}
 
 /// The biggest floating-point type with implemented [`Float`] trait
 type FloatMax = f64;
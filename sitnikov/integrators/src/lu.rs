@@ -0,0 +1,30 @@
+//! Provides the [`lu`] and [`solve`] routines
+
+use anyhow::anyhow;
+use nalgebra::{DMatrix, DVector, Dynamic, LU};
+
+use crate::{Float, FloatMax};
+
+/// The LU decomposition of a square matrix
+pub type Decomposition = LU<FloatMax, Dynamic, Dynamic>;
+
+/// Compute the LU decomposition of a square matrix
+///
+/// The decomposition is performed in the biggest floating-point
+/// type, since [`Float`] doesn't require [`nalgebra::RealField`]
+pub fn lu<F: Float>(a: &DMatrix<F>) -> Decomposition {
+    a.map(|e| e.to_f64().unwrap()).lu()
+}
+
+/// Solve a system of linear equations using the LU decomposition of its matrix
+///
+/// Arguments:
+/// * `lu` --- LU decomposition of the matrix of the system;
+/// * `b` --- Right-hand side of the system.
+pub fn solve<F: Float>(lu: &Decomposition, b: &[F]) -> anyhow::Result<Vec<F>> {
+    let b = DVector::from_iterator(b.len(), b.iter().map(|e| e.to_f64().unwrap()));
+    let x = lu
+        .solve(&b)
+        .ok_or_else(|| anyhow!("The matrix of the system is singular"))?;
+    Ok(x.iter().map(|&e| F::from(e).unwrap()).collect())
+}
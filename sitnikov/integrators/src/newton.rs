@@ -0,0 +1,74 @@
+//! Provides the [`newton`] routine
+
+use anyhow::{anyhow, Context};
+use nalgebra::DMatrix;
+use numeric_literals::replace_float_literals;
+
+use crate::{lu, Float};
+
+/// Maximum number of iterations
+const MAX_ITER: u16 = 100;
+
+/// Find a root of a system of non-linear equations `f(x) = 0` using Newton's method
+///
+/// Arguments:
+/// * `f` --- Left-hand side of the system;
+/// * `jacobian` --- Jacobian matrix of the left-hand side;
+/// * `initial` --- Initial guess.
+#[replace_float_literals(F::from(literal).unwrap())]
+pub fn newton<F: Float>(
+    f: impl Fn(&[F]) -> anyhow::Result<Vec<F>>,
+    jacobian: impl Fn(&[F]) -> anyhow::Result<DMatrix<F>>,
+    initial: &[F],
+) -> anyhow::Result<Vec<F>> {
+    let mut x = initial.to_vec();
+    // On each iteration
+    for _ in 0..MAX_ITER {
+        // Compute the function value, stop if it's already a root
+        let f_x = f(&x).with_context(|| "Couldn't compute the function value")?;
+        if f_x.iter().all(F::is_zero) {
+            return Ok(x);
+        }
+        // Compute the correction
+        let jacobian = jacobian(&x).with_context(|| "Couldn't compute the Jacobian")?;
+        let delta = lu::solve(&lu::lu(&jacobian), &f_x)
+            .with_context(|| "Couldn't solve for the correction")?;
+        // Compute the next point
+        for (x, &delta) in x.iter_mut().zip(delta.iter()) {
+            *x = *x - delta;
+        }
+        // Check if the correction is small enough
+        // (relative to the magnitude of the root)
+        if x.iter()
+            .zip(delta.iter())
+            .all(|(&x, &delta)| delta.abs() < F::epsilon() * (10. + x.abs()))
+        {
+            return Ok(x);
+        }
+    }
+    Err(anyhow!(
+        "Newton's method didn't converge with initial = {initial:?}"
+    ))
+}
+
+#[test]
+fn test() -> anyhow::Result<()> {
+    // Intersect a circle with a line
+    let f = |x: &[f64]| Ok(vec![x[0].powi(2) + x[1].powi(2) - 4., x[0] - x[1]]);
+    let jacobian = |x: &[f64]| {
+        Ok(DMatrix::from_row_slice(
+            2,
+            2,
+            &[2. * x[0], 2. * x[1], 1., -1.],
+        ))
+    };
+    let x = newton(f, jacobian, &[1., 0.5])?;
+
+    // Compare to the known result
+    let x_0 = std::f64::consts::SQRT_2;
+    if x.iter().any(|&x| (x - x_0).abs() >= 10. * f64::EPSILON) {
+        return Err(anyhow!("The root is incorrect: {x_0} vs. {x:?}"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,227 @@
+//! Provides the [`EmbeddedPairs`](crate::EmbeddedPairs) enum and the Butcher tableaus of the pairs
+
+use num::Float as NumFloat;
+
+use crate::Float;
+
+/// A rational number (the numerator and the denominator)
+type Ratio = (i32, i32);
+
+/// A Butcher tableau of an embedded Runge-Kutta pair
+/// with the coefficients given as rational numbers
+pub(crate) struct RationalTableau {
+    /// Nodes
+    pub nodes: &'static [Ratio],
+    /// Runge-Kutta matrix (the lower triangle, row by row)
+    pub matrix: &'static [&'static [Ratio]],
+    /// Weights of the higher-order estimate
+    pub weights_high: &'static [Ratio],
+    /// Weights of the lower-order estimate
+    pub weights_low: &'static [Ratio],
+    /// Whether the last stage is the first stage of the next step
+    /// (First Same As Last; the last row of the matrix is then the
+    /// same as the weights of the higher-order estimate)
+    pub fsal: bool,
+}
+
+/// A Butcher tableau of an embedded Runge-Kutta pair computed in
+/// the precision of the float type of the integration (this is done
+/// once per type, see [`Coefficients`](crate::Coefficients); the type
+/// is public, but it can't be named outside of the crate)
+#[derive(Debug)]
+pub struct Tableau<F> {
+    /// Nodes
+    pub nodes: Vec<F>,
+    /// Runge-Kutta matrix (the lower triangle, row by row)
+    pub matrix: Vec<Vec<F>>,
+    /// Weights of the higher-order estimate
+    pub weights_high: Vec<F>,
+    /// Weights of the lower-order estimate
+    pub weights_low: Vec<F>,
+    /// Whether the last stage is the first stage of the next step
+    pub fsal: bool,
+}
+
+impl RationalTableau {
+    /// Compute the tableau in the precision of the specified float type
+    pub(crate) fn compute<F: NumFloat>(&self) -> Tableau<F> {
+        let compute = |v: &[Ratio]| -> Vec<F> {
+            v.iter()
+                .map(|&(n, d)| F::from(n).unwrap() / F::from(d).unwrap())
+                .collect()
+        };
+        Tableau {
+            nodes: compute(self.nodes),
+            matrix: self.matrix.iter().map(|a| compute(a)).collect(),
+            weights_high: compute(self.weights_high),
+            weights_low: compute(self.weights_low),
+            fsal: self.fsal,
+        }
+    }
+}
+
+/// Butcher tableau of the Runge-Kutta-Fehlberg pair
+pub(crate) const FEHLBERG: RationalTableau = RationalTableau {
+    nodes: &[(0, 1), (1, 4), (3, 8), (12, 13), (1, 1), (1, 2)],
+    matrix: &[
+        &[],
+        &[(1, 4)],
+        &[(3, 32), (9, 32)],
+        &[(1932, 2197), (-7200, 2197), (7296, 2197)],
+        &[(439, 216), (-8, 1), (3680, 513), (-845, 4104)],
+        &[(-8, 27), (2, 1), (-3544, 2565), (1859, 4104), (-11, 40)],
+    ],
+    weights_high: &[
+        (16, 135),
+        (0, 1),
+        (6656, 12825),
+        (28561, 56430),
+        (-9, 50),
+        (2, 55),
+    ],
+    weights_low: &[
+        (25, 216),
+        (0, 1),
+        (1408, 2565),
+        (2197, 4104),
+        (-1, 5),
+        (0, 1),
+    ],
+    fsal: false,
+};
+
+/// Butcher tableau of the Cash-Karp pair
+pub(crate) const CASH_KARP: RationalTableau = RationalTableau {
+    nodes: &[(0, 1), (1, 5), (3, 10), (3, 5), (1, 1), (7, 8)],
+    matrix: &[
+        &[],
+        &[(1, 5)],
+        &[(3, 40), (9, 40)],
+        &[(3, 10), (-9, 10), (6, 5)],
+        &[(-11, 54), (5, 2), (-70, 27), (35, 27)],
+        &[
+            (1631, 55296),
+            (175, 512),
+            (575, 13824),
+            (44275, 110_592),
+            (253, 4096),
+        ],
+    ],
+    weights_high: &[
+        (37, 378),
+        (0, 1),
+        (250, 621),
+        (125, 594),
+        (0, 1),
+        (512, 1771),
+    ],
+    weights_low: &[
+        (2825, 27648),
+        (0, 1),
+        (18575, 48384),
+        (13525, 55296),
+        (277, 14336),
+        (1, 4),
+    ],
+    fsal: false,
+};
+
+/// Butcher tableau of the Dormand-Prince pair
+pub(crate) const DORMAND_PRINCE: RationalTableau = RationalTableau {
+    nodes: &[(0, 1), (1, 5), (3, 10), (4, 5), (8, 9), (1, 1), (1, 1)],
+    matrix: &[
+        &[],
+        &[(1, 5)],
+        &[(3, 40), (9, 40)],
+        &[(44, 45), (-56, 15), (32, 9)],
+        &[(19372, 6561), (-25360, 2187), (64448, 6561), (-212, 729)],
+        &[
+            (9017, 3168),
+            (-355, 33),
+            (46732, 5247),
+            (49, 176),
+            (-5103, 18656),
+        ],
+        &[
+            (35, 384),
+            (0, 1),
+            (500, 1113),
+            (125, 192),
+            (-2187, 6784),
+            (11, 84),
+        ],
+    ],
+    weights_high: &[
+        (35, 384),
+        (0, 1),
+        (500, 1113),
+        (125, 192),
+        (-2187, 6784),
+        (11, 84),
+        (0, 1),
+    ],
+    weights_low: &[
+        (5179, 57600),
+        (0, 1),
+        (7571, 16695),
+        (393, 640),
+        (-92097, 339_200),
+        (187, 2100),
+        (1, 40),
+    ],
+    fsal: true,
+};
+
+/// Embedded Runge-Kutta pairs (the higher-order estimates are kept)
+#[derive(Clone, Copy)]
+pub enum Pairs {
+    /// Cash-Karp 5(4) pair
+    CashKarp,
+    /// Dormand-Prince 5(4) pair (the last stage of an accepted
+    /// step is reused as the first stage of the next one)
+    DormandPrince54,
+    /// Runge-Kutta-Fehlberg 4(5) pair
+    Fehlberg,
+}
+
+impl Pairs {
+    /// Get the order of the higher-order estimate
+    #[must_use]
+    pub fn order(self) -> usize {
+        match self {
+            Pairs::CashKarp | Pairs::DormandPrince54 | Pairs::Fehlberg => 5,
+        }
+    }
+    /// Get the Butcher tableau of the pair in the precision of the float type
+    pub(crate) fn tableau<F: Float>(self) -> &'static Tableau<F> {
+        let c = F::coefficients();
+        match self {
+            Pairs::CashKarp => &c.cash_karp,
+            Pairs::DormandPrince54 => &c.dormand_prince,
+            Pairs::Fehlberg => &c.fehlberg,
+        }
+    }
+}
+
+#[test]
+fn test() -> anyhow::Result<()> {
+    // Check the consistency conditions of the tableaus
+    for pair in [Pairs::CashKarp, Pairs::DormandPrince54, Pairs::Fehlberg] {
+        let tableau = pair.tableau::<f64>();
+        if tableau
+            .matrix
+            .iter()
+            .zip(tableau.nodes.iter())
+            .any(|(a, &c)| (a.iter().sum::<f64>() - c).abs() >= 1e-14)
+            || (tableau.weights_high.iter().sum::<f64>() - 1.).abs() >= 1e-14
+            || (tableau.weights_low.iter().sum::<f64>() - 1.).abs() >= 1e-14
+            || tableau.fsal
+                && tableau.matrix[tableau.matrix.len() - 1]
+                    != tableau.weights_high[..tableau.matrix.len() - 1]
+        {
+            return Err(anyhow::anyhow!("The tableau is inconsistent"));
+        }
+    }
+
+    Ok(())
+}
@@ -1,25 +1,56 @@
 //! Provides the [`prepare`] macro, plus tests for the method
 
-/// Defines the `prepare` method
+/// Defines the `check_dim` and `prepare` methods (the optional
+/// literal is the number of parts the state must consist of)
 macro_rules! prepare {
-    () => {
-        /// Prepare a matrix for the result and
-        /// put the initial values in the first column
+    ($($parts:literal)?) => {
+        /// Check that the length of the vector of initial values
+        /// matches the dimension of the system (if it's defined)
         ///
         /// Arguments:
         /// * `x` --- Vector of initial values;
+        /// * `token` --- Private token.
+        fn check_dim(&self, x: &[F], _: &Token) -> anyhow::Result<()> {
+            let len = x.len();
+            if let Some(dim) = self.dim() {
+                if len != dim {
+                    return Err(anyhow::anyhow!(
+                        "The length of the vector of initial values ({len}) \
+                        doesn't match the dimension of the system ({dim})"
+                    ));
+                }
+            }
+            $(
+                if len % $parts != 0 {
+                    return Err(anyhow::anyhow!(
+                        "The length of the vector of initial values ({len}) \
+                        must be a multiple of {}",
+                        $parts
+                    ));
+                }
+            )?
+            Ok(())
+        }
+
+        /// Prepare a solution: put the initial values in the first
+        /// column of the result matrix and the initial value of time
+        /// in the first element of the vector of time moments
+        ///
+        /// Arguments:
+        /// * `x` --- Vector of initial values;
+        /// * `t_0` --- Initial value of time;
         /// * `n` --- Number of iterations;
         /// * `token` --- Private token.
         #[replace_float_literals(F::from(literal).unwrap())]
-        fn prepare(&self, x: Vec<F>, n: usize, _: &Token) -> Result<F> {
+        fn prepare(&self, x: &[F], t_0: F, n: usize, token: &Token) -> anyhow::Result<Solution<F>> {
+            // Check the dimension of the state
+            self.check_dim(x, token)?;
             // Create a matrix for the solution
             let mut result = Result::new(x.len(), n + 1);
-            // Wrap the initial values in a column vector
-            let x = DVector::from(x);
-            // Put the initial values in the first row
-            result.set_column(0, &x);
-            // Return the matrix
-            result
+            // Put the initial values in the first column
+            result.set_state_from_slice(0, x);
+            // Wrap the matrix in a solution
+            Ok(Solution::new(result, t_0))
         }
     };
 }
@@ -43,9 +74,8 @@ fn test() -> anyhow::Result<()> {
     let token = Token {};
 
     // Prepare a matrix with initial data
-    let x = vec![1., 2., 3., 4., 5.];
-    let x_0 = x.clone();
-    let result = test.prepare(x, 0, &token);
+    let x_0 = vec![1., 2., 3., 4., 5.];
+    let result = test.prepare(&x_0, 0., 0, &token)?;
 
     // Check the first column of the matrix
     let x: Vec<F> = result.initial_values();
@@ -55,5 +85,83 @@ fn test() -> anyhow::Result<()> {
         ));
     }
 
+    // Check the time moments
+    if result.times() != [0.] {
+        return Err(anyhow::anyhow!(
+            "The time moments are not the same as the initial value of time"
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_dim() -> anyhow::Result<()> {
+    use crate::{
+        Float, GeneralIntegrator, GeneralIntegrators, SymplecticIntegrator, SymplecticIntegrators,
+    };
+
+    // Implement the traits on test structs with the dimensions defined
+    struct Test {}
+    impl<F: Float> GeneralIntegrator<F> for Test {
+        fn update(&self, _t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![x[1], -x[0]])
+        }
+        fn dim(&self) -> Option<usize> {
+            Some(2)
+        }
+    }
+    struct TestSymplectic {}
+    impl<F: Float> SymplecticIntegrator<F> for TestSymplectic {
+        fn accelerations(&self, _t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![-x[0]])
+        }
+        fn dim(&self) -> Option<usize> {
+            Some(3)
+        }
+    }
+    struct TestSymplecticUndefined {}
+    impl<F: Float> SymplecticIntegrator<F> for TestSymplecticUndefined {
+        fn accelerations(&self, _t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![-x[0]])
+        }
+    }
+
+    let (test, test_symplectic, test_undefined) =
+        (Test {}, TestSymplectic {}, TestSymplecticUndefined {});
+
+    // Check that the matching states are accepted
+    test.integrate(&[1., 0.], 0., 1e-2, 10, GeneralIntegrators::RungeKutta4th)?;
+    test_symplectic.integrate(
+        &[1., 0., -1.],
+        0.,
+        1e-2,
+        10,
+        SymplecticIntegrators::Leapfrog,
+    )?;
+
+    // Check that the mismatched states are rejected
+    if test
+        .integrate(
+            &[1., 0., 0.],
+            0.,
+            1e-2,
+            10,
+            GeneralIntegrators::RungeKutta4th,
+        )
+        .is_ok()
+        || test
+            .integrate_adaptive(&[1.], 0., 1., 1e-2, (1e-8, 1e-8))
+            .is_ok()
+        || test_symplectic
+            .integrate(&[1., 0.], 0., 1e-2, 10, SymplecticIntegrators::Leapfrog)
+            .is_ok()
+        || test_undefined
+            .integrate(&[1., 0.], 0., 1e-2, 10, SymplecticIntegrators::Leapfrog)
+            .is_ok()
+    {
+        return Err(anyhow::anyhow!("The mismatched state wasn't rejected"));
+    }
+
     Ok(())
 }
@@ -2,6 +2,8 @@
 
 use nalgebra::{DVector, Dynamic, Matrix, VecStorage};
 
+use std::iter;
+
 use crate::Float;
 
 /// The type of the result matrix
@@ -11,14 +13,27 @@ pub type Result<F> = Matrix<F, Dynamic, Dynamic, VecStorage<F, Dynamic, Dynamic>
 pub trait Ext<F: Float> {
     /// Initialize a matrix with `nrows` rows and `ncols` columns
     fn new(nrows: usize, ncols: usize) -> Self;
+    /// Reset the matrix for `n` iterations starting from the initial values
+    /// `x`, reusing its memory (it's reallocated only if it has to grow)
+    fn reset(&mut self, x: &[F], n: usize);
     /// Get initial values
     fn initial_values(&self) -> Vec<F>;
     /// Set the `i`-th state of the system
     fn set_state(&mut self, i: usize, x: Vec<F>);
+    /// Set the `i`-th state of the system from a borrowed slice
+    /// (without allocating an intermediate vector)
+    fn set_state_from_slice(&mut self, i: usize, x: &[F]);
     /// Get the `i`-th state of the system
     fn state(&self, i: usize) -> Vec<F>;
     /// Get the `i`-th result vector
     fn result(&self, i: usize) -> Vec<F>;
+    /// Get the `i`-th result vector averaged in non-overlapping blocks of
+    /// `factor` columns (the last block is averaged over the remaining
+    /// columns), return an error if the factor is zero
+    fn downsample_avg(&self, i: usize, factor: usize) -> anyhow::Result<Vec<F>>;
+    /// Format the first `max_cols` states as an aligned table with a time
+    /// column (given the initial value of time `t_0` and the time step `h`)
+    fn display_trajectory(&self, t_0: F, h: F, max_cols: usize) -> String;
 }
 
 impl<F: Float> Ext<F> for Result<F> {
@@ -27,6 +42,16 @@ impl<F: Float> Ext<F> for Result<F> {
         let ncols = Dynamic::new(ncols);
         Matrix::zeros_generic(nrows, ncols)
     }
+    fn reset(&mut self, x: &[F], n: usize) {
+        let (nrows, ncols) = (x.len(), n + 1);
+        // Take the storage out of the matrix and resize it
+        let mut data: Vec<F> = std::mem::replace(self, Self::new(0, 0)).data.into();
+        data.clear();
+        data.resize(nrows * ncols, F::zero());
+        *self = Matrix::from_vec_generic(Dynamic::new(nrows), Dynamic::new(ncols), data);
+        // Put the initial values in the first column
+        self.set_state_from_slice(0, x);
+    }
     fn initial_values(&self) -> Vec<F> {
         self.state(0)
     }
@@ -34,10 +59,188 @@ impl<F: Float> Ext<F> for Result<F> {
         let x = DVector::from(x);
         self.set_column(i, &x);
     }
+    fn set_state_from_slice(&mut self, i: usize, x: &[F]) {
+        self.column_mut(i).copy_from_slice(x);
+    }
     fn state(&self, i: usize) -> Vec<F> {
         self.column(i).into_iter().copied().collect()
     }
     fn result(&self, i: usize) -> Vec<F> {
         self.row(i).into_iter().copied().collect()
     }
+    fn downsample_avg(&self, i: usize, factor: usize) -> anyhow::Result<Vec<F>> {
+        if factor == 0 {
+            return Err(anyhow::anyhow!("The downsampling factor must be positive"));
+        }
+        Ok(self
+            .result(i)
+            .chunks(factor)
+            .map(|c| c.iter().fold(F::zero(), |acc, &x| acc + x) / F::from(c.len()).unwrap())
+            .collect())
+    }
+    fn display_trajectory(&self, t_0: F, h: F, max_cols: usize) -> String {
+        let n = usize::min(max_cols, self.ncols());
+        // Format the cells, starting with the header
+        let mut rows: Vec<Vec<String>> = vec![iter::once("t".to_string())
+            .chain((0..self.nrows()).map(|i| format!("x[{i}]")))
+            .collect()];
+        for j in 0..n {
+            let t = t_0 + F::from(j).unwrap() * h;
+            rows.push(
+                iter::once(format!("{t:.6}"))
+                    .chain(self.column(j).iter().map(|x| format!("{x:.6}")))
+                    .collect(),
+            );
+        }
+        // Compute the widths of the columns
+        let widths: Vec<usize> = (0..=self.nrows())
+            .map(|k| rows.iter().map(|r| r[k].len()).max().unwrap_or(0))
+            .collect();
+        // Align the cells
+        let mut table = rows
+            .iter()
+            .map(|r| {
+                r.iter()
+                    .zip(widths.iter())
+                    .map(|(c, &w)| format!("{c:>w$}"))
+                    .collect::<Vec<_>>()
+                    .join("  ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        // Indicate the truncation
+        if self.ncols() > n {
+            table.push_str("\n... (");
+            table.push_str(&(self.ncols() - n).to_string());
+            table.push_str(" more)");
+        }
+        table
+    }
+}
+
+#[test]
+#[allow(clippy::cast_precision_loss)]
+fn test_downsample_avg() -> anyhow::Result<()> {
+    // Prepare a matrix with a constant row and a ramp
+    let n = 10;
+    let result = Result::<f64>::from_fn(2, n, |i, j| if i == 0 { 3. } else { j as f64 });
+
+    for factor in 1..=n + 1 {
+        // Downsample the constant row
+        let x = result.downsample_avg(0, factor)?;
+        if x.len() != (n + factor - 1) / factor || x.iter().any(|&x| (x - 3.).abs() >= f64::EPSILON)
+        {
+            return Err(anyhow::anyhow!(
+                "The downsampled constant row is incorrect (factor = {factor}): {x:?}"
+            ));
+        }
+
+        // Downsample the ramp and compare to the means of the blocks
+        let x = result.downsample_avg(1, factor)?;
+        let x_0: Vec<f64> = (0..n)
+            .step_by(factor)
+            .map(|start| {
+                let end = usize::min(start + factor, n) - 1;
+                (start + end) as f64 / 2.
+            })
+            .collect();
+        if x.iter()
+            .zip(x_0.iter())
+            .any(|(&x, &x_0)| (x - x_0).abs() >= 10. * f64::EPSILON)
+            || x.len() != x_0.len()
+        {
+            return Err(anyhow::anyhow!(
+                "The downsampled ramp is incorrect (factor = {factor}): {x_0:?} vs {x:?}"
+            ));
+        }
+    }
+
+    // Check that the zero factor is rejected
+    if result.downsample_avg(0, 0).is_ok() {
+        return Err(anyhow::anyhow!("The zero factor was accepted"));
+    }
+
+    Ok(())
+}
+
+#[test]
+#[allow(clippy::cast_precision_loss)]
+fn test_set_state_from_slice() -> anyhow::Result<()> {
+    // Prepare two empty matrices
+    let (nrows, ncols) = (7, 5);
+    let mut result = Result::<f64>::new(nrows, ncols);
+    let mut result_slice = Result::<f64>::new(nrows, ncols);
+
+    // Set the states using both methods
+    for j in 0..ncols {
+        let x: Vec<f64> = (0..nrows).map(|i| (i * ncols + j) as f64 / 3.).collect();
+        result.set_state(j, x.clone());
+        result_slice.set_state_from_slice(j, &x);
+    }
+
+    // Compare the matrices
+    if result != result_slice {
+        return Err(anyhow::anyhow!(
+            "The states set from slices differ from the cloned ones"
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_display_trajectory() -> anyhow::Result<()> {
+    // Prepare a tiny matrix
+    let result = Result::<f64>::from_row_slice(2, 3, &[1., 2., 3., -4., 5., 6.]);
+
+    // Format the first two states
+    let table = result.display_trajectory(0., 0.5, 2);
+    let expected = [
+        "       t      x[0]       x[1]",
+        "0.000000  1.000000  -4.000000",
+        "0.500000  2.000000   5.000000",
+        "... (1 more)",
+    ]
+    .join("\n");
+    if table != expected {
+        return Err(anyhow::anyhow!(
+            "The table is incorrect:\n{expected}\nvs.\n{table}"
+        ));
+    }
+
+    // Format all states
+    let table = result.display_trajectory(0., 0.5, 10);
+    if table.lines().count() != 4 || table.contains("more") {
+        return Err(anyhow::anyhow!(
+            "The table of all states is incorrect:\n{table}"
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+#[allow(clippy::cast_precision_loss)]
+fn test_reset() -> anyhow::Result<()> {
+    // Prepare a filled matrix
+    let mut result = Result::<f64>::from_fn(3, 10, |i, j| (i + j) as f64);
+    let ptr = result.as_ptr();
+
+    // Shrink it, check that the memory is reused
+    result.reset(&[1., 2.], 4);
+    let mut expected = Result::<f64>::new(2, 5);
+    expected.set_state_from_slice(0, &[1., 2.]);
+    if result != expected || result.as_ptr() != ptr {
+        return Err(anyhow::anyhow!("The shrunk matrix is incorrect"));
+    }
+
+    // Grow it
+    result.reset(&[1., 2., 3., 4.], 9);
+    let mut expected = Result::<f64>::new(4, 10);
+    expected.set_state_from_slice(0, &[1., 2., 3., 4.]);
+    if result != expected {
+        return Err(anyhow::anyhow!("The grown matrix is incorrect"));
+    }
+
+    Ok(())
 }
@@ -0,0 +1,33 @@
+//! Provides the [`richardson`] routine
+
+use crate::Float;
+
+/// Extrapolate the states computed with the time steps `h`, `h / 2`,
+/// `h / 4`, ... using the Richardson extrapolation (the Neville scheme)
+///
+/// The error of the base method is assumed to be expandable in the powers
+/// `h^p`, `h^(p + q)`, `h^(p + 2q)`, ..., one of which is eliminated on each
+/// level of the extrapolation (`q = 2` for symmetric methods, since their
+/// errors contain only even powers, and `q = 1` otherwise)
+///
+/// Arguments:
+/// * `states` --- States in the order of decreasing time steps;
+/// * `order` --- Order of the base method (`p`);
+/// * `step` --- Step between the powers in the expansion of the error (`q`).
+pub fn richardson<F: Float>(states: &[Vec<F>], order: usize, step: usize) -> Vec<F> {
+    let mut table = states.to_vec();
+    for j in 1..states.len() {
+        // Compute the factor eliminating the error term `h^(p + (j - 1) q)`
+        let power = i32::try_from(order + (j - 1) * step).unwrap();
+        let factor = F::from(2.).unwrap().powi(power) - F::one();
+        // Combine the consecutive entries (the latest ones are at the end)
+        for k in (j..states.len()).rev() {
+            table[k] = table[k]
+                .iter()
+                .zip(table[k - 1].iter())
+                .map(|(&a, &b)| a + (a - b) / factor)
+                .collect();
+        }
+    }
+    table.pop().unwrap()
+}
@@ -0,0 +1,175 @@
+//! Provides the [`Solution`] struct
+
+use std::ops::{Deref, DerefMut};
+
+use anyhow::{anyhow, Context};
+
+use crate::hermite::hermite;
+use crate::{Float, Result, ResultExt};
+
+/// A solution of the system: the result matrix, the time moments of its
+/// states and (optionally) the energies and the corrector residuals in them
+///
+/// The struct dereferences to the result matrix, so the callers
+/// interested only in the states can use it as such
+#[derive(Clone, Debug, PartialEq)]
+pub struct Solution<F: Float> {
+    /// Result matrix
+    states: Result<F>,
+    /// Time moments of the states
+    times: Vec<F>,
+    /// Energies in the states (empty if not recorded)
+    energies: Vec<F>,
+    /// Corrector residuals in the states (empty if not recorded)
+    residuals: Vec<F>,
+}
+
+impl<F: Float> Solution<F> {
+    /// Wrap the result matrix with the initial value of time put in the vector
+    /// of time moments (which reserves space for the rest of the states)
+    pub fn new(states: Result<F>, t_0: F) -> Self {
+        let mut times = Vec::with_capacity(states.ncols());
+        times.push(t_0);
+        Self {
+            states,
+            times,
+            energies: Vec::new(),
+            residuals: Vec::new(),
+        }
+    }
+    /// Reset the solution for `n` iterations starting from the initial values
+    /// `x` at the time moment `t_0`, reusing the memory of the result matrix
+    /// (the energies and the residuals are cleared)
+    ///
+    /// Arguments:
+    /// * `x` --- Vector of initial values;
+    /// * `t_0` --- Initial value of time;
+    /// * `n` --- Number of iterations.
+    pub fn reset(&mut self, x: &[F], t_0: F, n: usize) {
+        self.states.reset(x, n);
+        self.times.clear();
+        self.times.push(t_0);
+        self.energies.clear();
+        self.residuals.clear();
+    }
+    /// Get the time moments of the states
+    #[must_use]
+    pub fn times(&self) -> &[F] {
+        &self.times
+    }
+    /// Set the time moment of the `i`-th state (it can be
+    /// the next one after the last recorded moment)
+    pub fn set_time(&mut self, i: usize, t: F) {
+        if i == self.times.len() {
+            self.times.push(t);
+        } else {
+            self.times[i] = t;
+        }
+    }
+    /// Get the energies in the states (empty if not recorded)
+    #[must_use]
+    pub fn energies(&self) -> &[F] {
+        &self.energies
+    }
+    /// Set the energies in the states
+    pub fn set_energies(&mut self, energies: Vec<F>) {
+        self.energies = energies;
+    }
+    /// Get the corrector residuals in the states (empty if not recorded)
+    #[must_use]
+    pub fn residuals(&self) -> &[F] {
+        &self.residuals
+    }
+    /// Set the corrector residuals in the states
+    pub fn set_residuals(&mut self, residuals: Vec<F>) {
+        self.residuals = residuals;
+    }
+    /// Append the states of another solution, which starts
+    /// from the last state of this one (so its first state,
+    /// time moment, energy and residual are skipped)
+    pub fn append(&mut self, other: &Self) {
+        let n = self.states.ncols();
+        let m = other.states.ncols() - 1;
+        let states = std::mem::replace(&mut self.states, Result::new(0, 0));
+        self.states = states.insert_columns(n, m, F::zero());
+        for j in 1..=m {
+            self.states.set_column(n - 1 + j, &other.states.column(j));
+        }
+        self.times.extend_from_slice(&other.times[1..]);
+        if let Some(energies) = other.energies.get(1..) {
+            self.energies.extend_from_slice(energies);
+        }
+        if let Some(residuals) = other.residuals.get(1..) {
+            self.residuals.extend_from_slice(residuals);
+        }
+    }
+    /// Keep only the first `len` states
+    pub fn truncate(&mut self, len: usize) {
+        let ncols = self.states.ncols();
+        if len < ncols {
+            let states = std::mem::replace(&mut self.states, Result::new(0, 0));
+            self.states = states.remove_columns(len, ncols - len);
+        }
+        self.times.truncate(len);
+        self.energies.truncate(len);
+        self.residuals.truncate(len);
+    }
+    /// Interpolate the state at the passed time moment using cubic Hermite
+    /// polynomials between the nearest states (the time moment must be
+    /// within the time span of the solution)
+    ///
+    /// Arguments:
+    /// * `t` --- Time moment;
+    /// * `derivatives` --- Function computing the derivatives of a state.
+    pub fn interpolate(
+        &self,
+        t: F,
+        derivatives: impl Fn(F, &[F]) -> anyhow::Result<Vec<F>>,
+    ) -> anyhow::Result<Vec<F>> {
+        let n = self.times.len();
+        let (first, last) = (self.times[0], self.times[n - 1]);
+        if t < first.min(last) || t > first.max(last) {
+            return Err(anyhow!(
+                "The time moment {t} is outside of the solution's time span [{first}, {last}]"
+            ));
+        }
+        if n == 1 {
+            return Ok(self.states.state(0));
+        }
+        // Find the step containing the time moment
+        let forward = last >= first;
+        let i = self
+            .times
+            .partition_point(|&t_i| if forward { t_i <= t } else { t_i >= t })
+            .clamp(1, n - 1)
+            - 1;
+        let (t_1, t_2) = (self.times[i], self.times[i + 1]);
+        let (x_1, x_2) = (self.states.state(i), self.states.state(i + 1));
+        // Compute the derivatives at the ends of the step
+        let d_1 = derivatives(t_1, &x_1)
+            .with_context(|| format!("Couldn't compute the derivatives at t = {t_1}"))?;
+        let d_2 = derivatives(t_2, &x_2)
+            .with_context(|| format!("Couldn't compute the derivatives at t = {t_2}"))?;
+        // Interpolate
+        let h = t_2 - t_1;
+        Ok(hermite(&x_1, &d_1, &x_2, &d_2, h, (t - t_1) / h))
+    }
+    /// Unwrap the result matrix
+    #[must_use]
+    pub fn into_states(self) -> Result<F> {
+        self.states
+    }
+}
+
+impl<F: Float> Deref for Solution<F> {
+    type Target = Result<F>;
+    fn deref(&self) -> &Self::Target {
+        &self.states
+    }
+}
+
+impl<F: Float> DerefMut for Solution<F> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.states
+    }
+}
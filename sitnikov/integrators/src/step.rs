@@ -0,0 +1,80 @@
+//! Provides the [`step`] macro, plus tests for the method
+
+/// Defines the `step` method of both integrators (see
+/// [`GeneralIntegrator`](crate::GeneralIntegrator#method.step)
+/// and [`SymplecticIntegrator`](crate::SymplecticIntegrator#method.step))
+macro_rules! step {
+    () => {
+        /// Make a step using the specified method, return the new state
+        ///
+        /// The step is made in the passed buffer (a solution prepared for one
+        /// iteration, see [`prepare`](Self::prepare)), so its memory is reused
+        /// between the steps. Note that the multistep methods are restarted
+        /// on each step (so they're bootstrapped instead)
+        ///
+        /// Arguments:
+        /// * `buffer` --- Solution used as a buffer;
+        /// * `t` --- Current time moment;
+        /// * `x` --- Current state of the system;
+        /// * `h` --- Time step;
+        /// * `integrator` --- Integration method;
+        /// * `token` --- Private token.
+        fn step(
+            &self,
+            buffer: &mut Solution<F>,
+            t: F,
+            x: &[F],
+            h: F,
+            integrator: Integrators,
+            token: &Token,
+        ) -> anyhow::Result<Vec<F>> {
+            buffer.reset(x, t, 1);
+            self.solve(buffer, t, h, 1, integrator, &mut |_, _, _| true, token)?;
+            Ok(buffer.state(1))
+        }
+    };
+}
+
+pub(super) use step;
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test() -> anyhow::Result<()> {
+    use crate::private::Token;
+    use crate::{Float, GeneralIntegrator, GeneralIntegrators, ResultExt};
+
+    // Implement the trait on a test struct
+    struct Test {}
+    impl<F: Float> GeneralIntegrator<F> for Test {
+        fn update(&self, t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![t, x[0] * F::sin(t)])
+        }
+    }
+    let test = Test {};
+    let token = Token {};
+
+    // Make the steps in the same buffer, compare them with the solution
+    let (x, h, n) = ([0_f64, 0.], 1e-2, 100);
+    let result = test.integrate(&x, 0., h, n, GeneralIntegrators::RungeKutta4th)?;
+    let mut buffer = test.prepare(&x, 0., 1, &token)?;
+    let mut x = x.to_vec();
+    for i in 0..n {
+        let t = result.times()[i];
+        x = test.step(
+            &mut buffer,
+            t,
+            &x,
+            h,
+            GeneralIntegrators::RungeKutta4th,
+            &token,
+        )?;
+        if x != result.state(i + 1) {
+            return Err(anyhow::anyhow!(
+                "The state after the step {} differs from the solution",
+                i + 1
+            ));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+//! Provides the [`steps`] routine
+
+use anyhow::anyhow;
+
+use crate::Float;
+
+/// Split the interval of integration into the whole number of time steps,
+/// return their number and the length of the final shorter step (if the
+/// time step doesn't divide the interval evenly)
+///
+/// Arguments:
+/// * `t_0` --- Initial value of time;
+/// * `t_end` --- Final value of time;
+/// * `h` --- Time step (its sign must match the direction of integration).
+pub fn steps<F: Float>(t_0: F, t_end: F, h: F) -> anyhow::Result<(usize, Option<F>)> {
+    if t_end == t_0 {
+        return Ok((0, None));
+    }
+    if h.is_zero() || (t_end - t_0).signum() != h.signum() {
+        return Err(anyhow!(
+            "The time step {h} doesn't lead from {t_0} to {t_end}"
+        ));
+    }
+    // Compute the number of steps, forgiving the round-off errors
+    let ratio = (t_end - t_0) / h;
+    let to_usize = |n: F| {
+        n.to_usize().ok_or_else(|| {
+            anyhow!(
+                "The number of time steps from {t_0} to {t_end} with the time step {h} is too big"
+            )
+        })
+    };
+    let tolerance = F::from(16.).unwrap() * F::epsilon() * ratio.max(F::one());
+    if (ratio - ratio.round()).abs() <= tolerance {
+        return Ok((to_usize(ratio.round())?, None));
+    }
+    let n = to_usize(ratio.floor())?;
+    Ok((n, Some(t_end - (t_0 + F::from(n).unwrap() * h))))
+}
+
+#[test]
+fn test() -> anyhow::Result<()> {
+    // Check the even and uneven splits
+    if steps(0., 1., 0.1)? != (10, None) || steps(1., 0., -0.1)? != (10, None) {
+        return Err(anyhow!("The interval wasn't split evenly"));
+    }
+    match steps(0., 1., 0.3_f64)? {
+        (3, Some(h_last)) if (h_last - 0.1).abs() < 1e-12 => (),
+        split => return Err(anyhow!("The interval was split incorrectly: {split:?}")),
+    }
+
+    // Check that the wrong time steps are rejected
+    if steps(0., 1., -0.1).is_ok() || steps(0., 1., 0.).is_ok() {
+        return Err(anyhow!("The wrong time step wasn't rejected"));
+    }
+
+    // Check that the infinite and too big numbers of steps are rejected
+    if steps(0., f64::INFINITY, 0.1).is_ok() || steps(0., 1e30, 1e-10).is_ok() {
+        return Err(anyhow!("The infinite number of steps wasn't rejected"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+//! Provides the [`convergence_study`] macro, plus tests for the method
+
+/// Defines the [`convergence_study`](crate::SymplecticIntegrator#method.convergence_study) method
+macro_rules! convergence_study {
+    () => {
+        /// Integrate the system until the final time moment with each
+        /// of the passed time steps, return the pairs of the time step and
+        /// the maximum absolute error of the final state (compared with
+        /// the reference state, NaN if the integration diverged to NaN)
+        ///
+        /// Only the first `reference.len()` components of the state are
+        /// compared, so the reference can omit the trailing ones
+        ///
+        /// Arguments:
+        /// * `x` --- Vector of initial values;
+        /// * `t_0` --- Initial value of time;
+        /// * `t_end` --- Final value of time;
+        /// * `hs` --- Time steps;
+        /// * `reference` --- Reference final state;
+        /// * `integrator` --- Integration method.
+        fn convergence_study(
+            &self,
+            x: &[F],
+            t_0: F,
+            t_end: F,
+            hs: &[F],
+            reference: &[F],
+            integrator: Integrators,
+        ) -> anyhow::Result<Vec<(F, F)>> {
+            hs.iter()
+                .map(|&h| {
+                    let result = self
+                        .integrate_until(x, t_0, t_end, h, integrator)
+                        .with_context(|| format!("Couldn't integrate with the time step {h}"))?;
+                    let x_end = result.state(result.ncols() - 1);
+                    let error = reference
+                        .iter()
+                        .zip(x_end.iter())
+                        .map(|(&r, &x)| (x - r).abs())
+                        .fold(
+                            F::zero(),
+                            |acc, e| if e.is_nan() || e > acc { e } else { acc },
+                        );
+                    Ok((h, error))
+                })
+                .collect()
+        }
+    };
+}
+
+pub(super) use convergence_study;
+
+#[test]
+#[allow(clippy::cast_precision_loss)]
+fn test() -> anyhow::Result<()> {
+    use crate::{symplectic_order, Float, SymplecticIntegrator, SymplecticIntegrators};
+
+    // Implement the trait on a test struct
+    struct Test {}
+    impl<F: Float> SymplecticIntegrator<F> for Test {
+        fn accelerations(&self, _t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![-x[0]])
+        }
+    }
+    let test = Test {};
+
+    // Compute the errors with the halved time steps
+    let study = test.convergence_study(
+        &[1_f64, 0., -1.],
+        0.,
+        3.,
+        &[0.1, 0.05, 0.025],
+        &[f64::cos(3.), -f64::sin(3.)],
+        SymplecticIntegrators::Leapfrog,
+    )?;
+
+    // Check the slopes
+    let order = symplectic_order(&SymplecticIntegrators::Leapfrog) as f64;
+    if study.len() != 3
+        || study.windows(2).any(|w| {
+            let slope = (w[0].1 / w[1].1).ln() / (w[0].0 / w[1].0).ln();
+            (slope - order).abs() >= 0.2
+        })
+    {
+        return Err(anyhow::anyhow!(
+            "The slopes don't match the order ({order}): {study:?}"
+        ));
+    }
+
+    // Check that the divergence is reported (the leapfrog method is
+    // unstable for the harmonic oscillator with a time step this big,
+    // and the state overflows, ending up as NaN)
+    let study = test.convergence_study(
+        &[1_f64, 0., -1.],
+        0.,
+        3000.,
+        &[3.],
+        &[f64::cos(3000.), -f64::sin(3000.)],
+        SymplecticIntegrators::Leapfrog,
+    )?;
+    if !study[0].1.is_nan() {
+        return Err(anyhow::anyhow!("The divergence isn't reported: {study:?}"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+//! Provides the [`forest_ruth`] macro, plus tests for the method
+
+/// Defines the [`forest_ruth`](crate::SymplecticIntegrator#method.forest_ruth) method
+macro_rules! forest_ruth {
+    () => {
+        /// Integrate the system using the 4th-order Forest-Ruth method
+        ///
+        /// This is the position (drift-kick-drift) form of the composition
+        /// with `theta = 1 / (2 - 2^(1/3))`. The accelerations of the next state
+        /// are computed at the final positions, so each step evaluates them
+        /// four times
+        ///
+        /// Arguments:
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Number of iterations;
+        /// * `result` --- Solution;
+        /// * `callback` --- Function called after each step (returns `false` to stop);
+        /// * `token` --- Private token.
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn forest_ruth(
+            &self,
+            t_0: F,
+            h: F,
+            n: usize,
+            result: &mut Solution<F>,
+            callback: &mut dyn FnMut(usize, F, &[F]) -> bool,
+            _: &Token,
+        ) -> anyhow::Result<()> {
+            // Compute the coefficients of the drifts and kicks
+            let c = F::coefficients();
+            let drifts = [
+                c.d_1 / 2. * h,
+                c.d_3 / 2. * h,
+                c.d_3 / 2. * h,
+                c.d_1 / 2. * h,
+            ];
+            let kicks = [c.d_1 * h, c.d_2 * h, c.d_1 * h];
+            // Get the initial state
+            let mut x = result.initial_values();
+            // Get the length of the state vector and its thirds
+            let l = x.len();
+            let lt1 = l / 3;
+            let lt2 = 2 * lt1;
+            // Integrate
+            for i in 0..n {
+                // Compute the time moment
+                let mut t = t_0 + F::from(i).unwrap() * h;
+                for (k, &drift) in drifts.iter().enumerate() {
+                    // Update the positions
+                    for j in 0..lt1 {
+                        x[j] = x[j] + drift * x[j + lt1];
+                    }
+                    t = t + drift;
+                    // Compute the accelerations
+                    let a = self
+                        .accelerations(t, &x[0..lt1])
+                        .with_context(|| "Couldn't compute the accelerations")?;
+                    // Update the accelerations and velocities
+                    // (there is no kick after the last drift)
+                    for j in lt1..lt2 {
+                        x[j + lt1] = a[j - lt1];
+                        if let Some(&kick) = kicks.get(k) {
+                            x[j] = x[j] + kick * x[j + lt1];
+                        }
+                    }
+                }
+                // Put the new state in the result
+                result.set_state_from_slice(i + 1, &x);
+                result.set_time(i + 1, t_0 + F::from(i + 1).unwrap() * h);
+                if !callback(i + 1, result.times()[i + 1], &x) {
+                    break;
+                }
+            }
+            Ok(())
+        }
+    };
+}
+
+pub(super) use forest_ruth;
+
+#[cfg(test)]
+super::test_method::test_method!(SymplecticIntegrators::ForestRuth);
+
+#[test]
+fn test_compare_with_yoshida() -> anyhow::Result<()> {
+    use crate::{Float, ResultExt, SymplecticIntegrator, SymplecticIntegrators};
+
+    // Implement the trait on a test struct (a pendulum)
+    struct Test {}
+    impl<F: Float> SymplecticIntegrator<F> for Test {
+        fn accelerations(&self, _t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![-F::sin(x[0])])
+        }
+    }
+    let test = Test {};
+
+    // Integrate using both methods
+    let x = [1., 0., -f64::sin(1.)];
+    let h = 1e-2;
+    let n = 1000;
+    let result = test.integrate(&x, 0., h, n, SymplecticIntegrators::ForestRuth)?;
+    let result_yoshida = test.integrate(&x, 0., h, n, SymplecticIntegrators::Yoshida4th)?;
+
+    // Compare the last states
+    let (x, x_yoshida) = (result.state(n), result_yoshida.state(n));
+    if x.iter()
+        .zip(x_yoshida.iter())
+        .any(|(&x, &x_yoshida)| (x - x_yoshida).abs() >= h.powi(4))
+    {
+        return Err(anyhow::anyhow!(
+            "The last states are not the same: {x_yoshida:?} vs {x:?}"
+        ));
+    }
+
+    Ok(())
+}
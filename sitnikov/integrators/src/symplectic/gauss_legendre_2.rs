@@ -0,0 +1,156 @@
+//! Provides the [`gauss_legendre_2`] macro, plus tests for the method
+
+/// Defines the [`gauss_legendre_2`](crate::SymplecticIntegrator#method.gauss_legendre_2) method
+macro_rules! gauss_legendre_2 {
+    () => {
+        /// Integrate the system using the 2-stage (4th-order) Gauss-Legendre method
+        ///
+        /// This is a collocation method, which is symplectic and A-stable.
+        /// The velocities of the stages are eliminated, so on each iteration
+        /// the coupled equations for the accelerations of both stages are
+        /// solved using Newton's method (with the Jacobian approximated by
+        /// central differences).
+        ///
+        /// Arguments:
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Number of iterations;
+        /// * `result` --- Solution;
+        /// * `callback` --- Function called after each step (returns `false` to stop);
+        /// * `token` --- Private token.
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn gauss_legendre_2(
+            &self,
+            t_0: F,
+            h: F,
+            n: usize,
+            result: &mut Solution<F>,
+            callback: &mut dyn FnMut(usize, F, &[F]) -> bool,
+            _: &Token,
+        ) -> anyhow::Result<()> {
+            // Define the coefficients of the method
+            let s = F::sqrt(3.) / 6.;
+            let c = [0.5 - s, 0.5 + s];
+            let a = [[0.25, 0.25 - s], [0.25 + s, 0.25]];
+            // Compute the coefficients of the accelerations in the
+            // positions of the stages (the square of the matrix)
+            let aa: Vec<Vec<F>> = (0..2)
+                .map(|i| {
+                    (0..2)
+                        .map(|k| a[i][0] * a[0][k] + a[i][1] * a[1][k])
+                        .collect()
+                })
+                .collect();
+            // Get the initial state
+            let mut x = result.initial_values();
+            let l = x.len() / 3;
+            // Integrate
+            for i in 0..n {
+                // Compute the time moment
+                let t = t_0 + F::from(i).unwrap() * h;
+                // Define the equations for the accelerations of the stages
+                let (q, v) = (&x[0..l], &x[l..2 * l]);
+                let g = |y: &[F]| -> anyhow::Result<Vec<F>> {
+                    let mut g = Vec::with_capacity(2 * l);
+                    for j in 0..2 {
+                        let q_j: Vec<F> = (0..l)
+                            .map(|k| {
+                                q[k] + h * c[j] * v[k]
+                                    + h * h * (aa[j][0] * y[k] + aa[j][1] * y[l + k])
+                            })
+                            .collect();
+                        let a_j = self.accelerations(t + c[j] * h, &q_j)?;
+                        g.extend((0..l).map(|k| y[j * l + k] - a_j[k]));
+                    }
+                    Ok(g)
+                };
+                let jacobian = |y: &[F]| -> anyhow::Result<DMatrix<F>> {
+                    let mut jacobian = DMatrix::zeros(2 * l, 2 * l);
+                    let mut y_m = y.to_vec();
+                    for j in 0..2 * l {
+                        let eps = F::epsilon().sqrt() * F::max(y[j].abs(), 1.);
+                        y_m[j] = y[j] + eps;
+                        let g_r = g(&y_m)?;
+                        y_m[j] = y[j] - eps;
+                        let g_l = g(&y_m)?;
+                        y_m[j] = y[j];
+                        for k in 0..2 * l {
+                            jacobian[(k, j)] = (g_r[k] - g_l[k]) / (2. * eps);
+                        }
+                    }
+                    Ok(jacobian)
+                };
+                // Solve for the accelerations (starting from the current ones)
+                let initial: Vec<F> = x[2 * l..].iter().chain(&x[2 * l..]).copied().collect();
+                let y = newton(&g, jacobian, &initial)
+                    .with_context(|| format!("Couldn't solve for the stages after t = {t}"))?;
+                // Compute the next state (the weights are 1/2, and the
+                // weights of the accelerations in the positions are
+                // `b_j (1 - c_j)`)
+                let q_new: Vec<F> = (0..l)
+                    .map(|k| {
+                        q[k] + h * v[k]
+                            + h * h * 0.5 * ((1. - c[0]) * y[k] + (1. - c[1]) * y[l + k])
+                    })
+                    .collect();
+                let v_new: Vec<F> = (0..l).map(|k| v[k] + h * 0.5 * (y[k] + y[l + k])).collect();
+                let a_new = self
+                    .accelerations(t + h, &q_new)
+                    .with_context(|| "Couldn't compute the accelerations")?;
+                x = [q_new, v_new, a_new].concat();
+                // Put the new state in the result
+                result.set_state_from_slice(i + 1, &x);
+                result.set_time(i + 1, t_0 + F::from(i + 1).unwrap() * h);
+                if !callback(i + 1, result.times()[i + 1], &x) {
+                    break;
+                }
+            }
+            Ok(())
+        }
+    };
+}
+
+pub(super) use gauss_legendre_2;
+
+#[cfg(test)]
+super::test_method::test_method!(SymplecticIntegrators::GaussLegendre2);
+
+#[test]
+fn test_energy() -> anyhow::Result<()> {
+    use crate::{Float, SymplecticIntegrator, SymplecticIntegrators};
+
+    // Implement the trait on a test struct (a pendulum)
+    struct Test {}
+    impl<F: Float> SymplecticIntegrator<F> for Test {
+        fn accelerations(&self, _t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![-F::sin(x[0])])
+        }
+        fn energy(&self, _t: F, x: &[F]) -> Option<F> {
+            Some(x[1].powi(2) / F::from(2.).unwrap() - F::cos(x[0]))
+        }
+    }
+    let test = Test {};
+
+    // Integrate over a long time span using both methods
+    let x = [2., 0., -f64::sin(2.)];
+    let (h, n) = (0.1, 10000);
+    let drift = |integrator| -> anyhow::Result<f64> {
+        let result = test.integrate(&x, 0., h, n, integrator)?;
+        let energies = result.energies();
+        Ok(energies
+            .iter()
+            .map(|e| (e - energies[0]).abs())
+            .fold(0., f64::max))
+    };
+    let drift_gl = drift(SymplecticIntegrators::GaussLegendre2)?;
+    let drift_yoshida = drift(SymplecticIntegrators::Yoshida4th)?;
+
+    // Check that the energy is conserved better than by the Yoshida method
+    if drift_gl >= 1e-4 || drift_gl >= drift_yoshida {
+        return Err(anyhow::anyhow!(
+            "The energy drift is too big: {drift_gl} (vs. {drift_yoshida} by the Yoshida method)"
+        ));
+    }
+
+    Ok(())
+}
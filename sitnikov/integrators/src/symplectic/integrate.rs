@@ -1,6 +1,10 @@
 //! Provides the [`integrate`] macro
 
-/// Defines the [`integrate`](crate::SymplecticIntegrator#method.integrate) method
+/// Defines the [`integrate`](crate::SymplecticIntegrator#method.integrate),
+/// [`integrate_with`](crate::SymplecticIntegrator#method.integrate_with) and
+/// [`integrate_into`](crate::SymplecticIntegrator#method.integrate_into) methods (plus the
+/// [`dispatch`](crate::SymplecticIntegrator#method.dispatch) and
+/// [`solve`](crate::SymplecticIntegrator#method.solve) methods they're based on)
 macro_rules! integrate {
     () => {
         /// Integrate the system of 1st-order ODEs
@@ -11,7 +15,6 @@ macro_rules! integrate {
         /// * `h` --- Time step;
         /// * `n` --- Number of iterations;
         /// * `integrator` --- Integration method.
-        #[replace_float_literals(F::from(literal).unwrap())]
         fn integrate(
             &self,
             x: &[F],
@@ -19,25 +22,276 @@ macro_rules! integrate {
             h: F,
             n: usize,
             integrator: Integrators,
-        ) -> anyhow::Result<Result<F>> {
+        ) -> anyhow::Result<Solution<F>> {
+            self.integrate_with(x, t_0, h, n, integrator, |_, _, _| {})
+        }
+
+        /// Integrate the system of 1st-order ODEs, calling the passed function
+        /// after each step with the index of the new state, its time moment
+        /// and the state itself
+        ///
+        /// Arguments:
+        /// * `x` --- Vector of initial values;
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Number of iterations;
+        /// * `integrator` --- Integration method;
+        /// * `callback` --- Function called after each step.
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn integrate_with(
+            &self,
+            x: &[F],
+            t_0: F,
+            h: F,
+            n: usize,
+            integrator: Integrators,
+            mut callback: impl FnMut(usize, F, &[F]),
+        ) -> anyhow::Result<Solution<F>> {
             // Get a token for using the private methods
             let token = Token {};
+            self.dispatch(
+                x,
+                t_0,
+                h,
+                n,
+                integrator,
+                &mut |i, t, x| {
+                    callback(i, t, x);
+                    true
+                },
+                &token,
+            )
+        }
+
+        /// Integrate the system of 1st-order ODEs into the passed solution,
+        /// reusing its memory (see [`Solution::reset`](crate::Solution::reset))
+        ///
+        /// Arguments:
+        /// * `result` --- Solution to reuse;
+        /// * `x` --- Vector of initial values;
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Number of iterations;
+        /// * `integrator` --- Integration method.
+        fn integrate_into(
+            &self,
+            result: &mut Solution<F>,
+            x: &[F],
+            t_0: F,
+            h: F,
+            n: usize,
+            integrator: Integrators,
+        ) -> anyhow::Result<()> {
+            // Get a token for using the private methods
+            let token = Token {};
+            // Check the dimension of the state
+            self.check_dim(x, &token)?;
+            // Reset the solution
+            result.reset(x, t_0, n);
+            self.solve(result, t_0, h, n, integrator, &mut |_, _, _| true, &token)
+        }
+
+        /// Integrate the system of 1st-order ODEs using the specified method,
+        /// calling the passed function after each step (the integration stops
+        /// if it returns `false`, and the solution is truncated)
+        ///
+        /// Arguments:
+        /// * `x` --- Vector of initial values;
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Maximum number of iterations;
+        /// * `integrator` --- Integration method;
+        /// * `callback` --- Function called after each step;
+        /// * `token` --- Private token.
+        #[allow(clippy::too_many_arguments)]
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn dispatch(
+            &self,
+            x: &[F],
+            t_0: F,
+            h: F,
+            n: usize,
+            integrator: Integrators,
+            callback: &mut dyn FnMut(usize, F, &[F]) -> bool,
+            token: &Token,
+        ) -> anyhow::Result<Solution<F>> {
             // Prepare a result matrix
-            let mut result = self.prepare(x.to_vec(), n, &token);
+            let mut result = self.prepare(x, t_0, n, token)?;
+            // Integrate
+            self.solve(&mut result, t_0, h, n, integrator, callback, token)?;
+            Ok(result)
+        }
+
+        /// Integrate the system of 1st-order ODEs using the specified method,
+        /// starting from the first state of the prepared solution, calling the
+        /// passed function after each step (the integration stops if it returns
+        /// `false`, and the solution is truncated)
+        ///
+        /// Arguments:
+        /// * `result` --- Prepared solution;
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Maximum number of iterations;
+        /// * `integrator` --- Integration method;
+        /// * `callback` --- Function called after each step;
+        /// * `token` --- Private token.
+        #[allow(clippy::too_many_arguments)]
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn solve(
+            &self,
+            result: &mut Solution<F>,
+            t_0: F,
+            h: F,
+            n: usize,
+            integrator: Integrators,
+            callback: &mut dyn FnMut(usize, F, &[F]) -> bool,
+            token: &Token,
+        ) -> anyhow::Result<()> {
             // Call the specified method to perform integration
             match integrator {
+                Integrators::ForestRuth => {
+                    self.forest_ruth(t_0, h, n, result, callback, token)
+                        .with_context(|| {
+                            "Couldn't integrate using the 4th-order Forest-Ruth method"
+                        })?;
+                }
+                Integrators::GaussLegendre2 => {
+                    self.gauss_legendre_2(t_0, h, n, result, callback, token)
+                        .with_context(|| {
+                            "Couldn't integrate using the 2-stage Gauss-Legendre method"
+                        })?;
+                }
                 Integrators::Leapfrog => {
-                    self.leapfrog(t_0, h, n, &mut result, &token)
+                    self.leapfrog(t_0, h, n, result, callback, token)
                         .with_context(|| "Couldn't integrate using the leapfrog method")?;
                 }
+                Integrators::Pefrl => {
+                    self.pefrl(t_0, h, n, result, callback, token)
+                        .with_context(|| "Couldn't integrate using the PEFRL method")?;
+                }
+                Integrators::StormerVerlet => {
+                    self.stormer_verlet(t_0, h, n, result, callback, token)
+                        .with_context(|| "Couldn't integrate using the Störmer-Verlet method")?;
+                }
+                Integrators::SymplecticEuler => {
+                    self.symplectic_euler(t_0, h, n, result, callback, token)
+                        .with_context(|| "Couldn't integrate using the symplectic Euler method")?;
+                }
+                Integrators::VelocityVerlet => {
+                    self.leapfrog(t_0, h, n, result, callback, token)
+                        .with_context(|| "Couldn't integrate using the velocity-Verlet method")?;
+                }
                 Integrators::Yoshida4th => {
-                    self.yoshida_4th(t_0, h, n, &mut result, &token)
+                    self.yoshida_4th(t_0, h, n, result, callback, token)
                         .with_context(|| "Coudln't integrate using the 4th-order Yoshida method")?;
                 }
+                Integrators::Yoshida6th => {
+                    self.yoshida_6th(t_0, h, n, result, callback, token)
+                        .with_context(|| "Couldn't integrate using the 6th-order Yoshida method")?;
+                }
             }
-            Ok(result)
+            // Drop the states which weren't computed
+            let len = result.times().len();
+            result.truncate(len);
+            // Record the energy at each step (if it's defined)
+            if let Some(e_0) = self.energy(result.times()[0], &result.state(0)) {
+                let mut energies = Vec::with_capacity(len);
+                energies.push(e_0);
+                for i in 1..len {
+                    energies.push(
+                        self.energy(result.times()[i], &result.state(i))
+                            .ok_or_else(|| {
+                                anyhow::anyhow!("The energy is undefined at the step {i}")
+                            })?,
+                    );
+                }
+                result.set_energies(energies);
+            }
+            Ok(())
         }
     };
 }
 
 pub(super) use integrate;
+
+#[test]
+fn test_energy() -> anyhow::Result<()> {
+    use crate::{Float, SymplecticIntegrator, SymplecticIntegrators};
+
+    // Implement the trait on test structs (a pendulum
+    // with and without the energy defined)
+    struct Test {}
+    impl<F: Float> SymplecticIntegrator<F> for Test {
+        fn accelerations(&self, _t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![-F::sin(x[0])])
+        }
+    }
+    struct TestEnergy {}
+    impl<F: Float> SymplecticIntegrator<F> for TestEnergy {
+        fn accelerations(&self, _t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![-F::sin(x[0])])
+        }
+        fn energy(&self, _t: F, x: &[F]) -> Option<F> {
+            Some(x[1].powi(2) / F::from(2.).unwrap() - F::cos(x[0]))
+        }
+    }
+
+    // Integrate both systems
+    let x = [1., 0., -f64::sin(1.)];
+    let n = 1000;
+    let result = Test {}.integrate(&x, 0., 1e-2, n, SymplecticIntegrators::Yoshida4th)?;
+    let result_energy =
+        TestEnergy {}.integrate(&x, 0., 1e-2, n, SymplecticIntegrators::Yoshida4th)?;
+
+    // Check the recorded energies
+    if !result.energies().is_empty() {
+        return Err(anyhow::anyhow!(
+            "The energies were recorded without the energy defined"
+        ));
+    }
+    let energies = result_energy.energies();
+    if energies.len() != n + 1 || energies.iter().any(|e| (e + f64::cos(1.)).abs() >= 1e-8) {
+        return Err(anyhow::anyhow!("The recorded energies are incorrect"));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_callback() -> anyhow::Result<()> {
+    use crate::{Float, ResultExt, SymplecticIntegrator, SymplecticIntegrators};
+
+    // Implement the trait on a test struct
+    struct Test {}
+    impl<F: Float> SymplecticIntegrator<F> for Test {
+        fn accelerations(&self, t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![t - x[0]])
+        }
+    }
+    let test = Test {};
+
+    // Integrate, accumulating the maximum position in the callback
+    let n = 1000;
+    let mut z_max = f64::NEG_INFINITY;
+    let result = test.integrate_with(
+        &[1., 0., -1.],
+        0.,
+        1e-2,
+        n,
+        SymplecticIntegrators::Yoshida4th,
+        |_, _, x| z_max = z_max.max(x[0]),
+    )?;
+
+    // Compare it with the solution
+    let z_max_result = result.result(0)[1..]
+        .iter()
+        .copied()
+        .fold(f64::NEG_INFINITY, f64::max);
+    if (z_max - z_max_result).abs() > 0. {
+        return Err(anyhow::anyhow!(
+            "The maximum position is incorrect: {z_max_result} vs {z_max}"
+        ));
+    }
+
+    Ok(())
+}
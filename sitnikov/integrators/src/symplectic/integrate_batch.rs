@@ -0,0 +1,78 @@
+//! Provides the [`integrate_batch`] macro, plus tests for the method
+
+/// Defines the [`integrate_batch`](crate::SymplecticIntegrator#method.integrate_batch) method
+macro_rules! integrate_batch {
+    () => {
+        /// Integrate the system of 1st-order ODEs from each of the vectors of
+        /// initial values in parallel (on the global thread pool of `rayon`),
+        /// return the results in the same order
+        ///
+        /// Arguments:
+        /// * `x_0s` --- Vectors of initial values;
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Number of iterations;
+        /// * `integrator` --- Integration method.
+        fn integrate_batch(
+            &self,
+            x_0s: &[Vec<F>],
+            t_0: F,
+            h: F,
+            n: usize,
+            integrator: Integrators,
+        ) -> Vec<anyhow::Result<Solution<F>>>
+        where
+            Self: Sync,
+            F: Send + Sync,
+        {
+            use rayon::prelude::*;
+            x_0s.par_iter()
+                .map(|x| self.integrate(x, t_0, h, n, integrator))
+                .collect()
+        }
+    };
+}
+
+pub(super) use integrate_batch;
+
+#[test]
+fn test() -> anyhow::Result<()> {
+    use crate::{Float, SymplecticIntegrator, SymplecticIntegrators};
+
+    // Implement the trait on a test struct
+    struct Test {}
+    impl<F: Float> SymplecticIntegrator<F> for Test {
+        fn accelerations(&self, t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![t - x[0]])
+        }
+        fn dim(&self) -> Option<usize> {
+            Some(3)
+        }
+    }
+    let test = Test {};
+
+    // Integrate from several initial values in parallel
+    let x_0s: Vec<Vec<f64>> = (0..32)
+        .map(|i| vec![f64::from(i) / 10., 0., -f64::from(i) / 10.])
+        .chain([vec![1.]])
+        .collect();
+    let results = test.integrate_batch(&x_0s, 0., 1e-2, 100, SymplecticIntegrators::Yoshida4th);
+
+    // Compare the results with the sequential ones
+    for (x, result) in x_0s.iter().zip(results.iter()).take(32) {
+        if *result.as_ref().map_err(|e| anyhow::anyhow!("{e:?}"))?
+            != test.integrate(x, 0., 1e-2, 100, SymplecticIntegrators::Yoshida4th)?
+        {
+            return Err(anyhow::anyhow!(
+                "The parallel result differs from the sequential one"
+            ));
+        }
+    }
+
+    // Check that the failure is reported in its place
+    if results.len() != 33 || results[32].is_ok() {
+        return Err(anyhow::anyhow!("The failed integration wasn't reported"));
+    }
+
+    Ok(())
+}
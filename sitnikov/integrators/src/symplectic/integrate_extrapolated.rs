@@ -0,0 +1,128 @@
+//! Provides the [`integrate_extrapolated`] macro, plus tests for the method
+
+/// Defines the [`integrate_extrapolated`](crate::SymplecticIntegrator#method.integrate_extrapolated) method
+macro_rules! integrate_extrapolated {
+    () => {
+        /// Integrate the system of 1st-order ODEs with the time steps
+        /// `h`, `h / 2`, ..., `h / 2^levels`, return the solution on
+        /// the grid of the biggest time step with the states improved
+        /// by the Richardson extrapolation
+        ///
+        /// The extrapolation is based on the order of the base method and the
+        /// powers in the expansion of its error (see [`order`](crate::SymplecticIntegrators::order)
+        /// and [`error_step`](crate::SymplecticIntegrators::error_step)). Only the positions
+        /// and the velocities are extrapolated: the accelerations are computed
+        /// anew from the extrapolated positions. The energies are not recorded
+        ///
+        /// Arguments:
+        /// * `x` --- Vector of initial values;
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Number of iterations (with the biggest time step);
+        /// * `base` --- Base integration method;
+        /// * `levels` --- Number of halvings of the time step.
+        fn integrate_extrapolated(
+            &self,
+            x: &[F],
+            t_0: F,
+            h: F,
+            n: usize,
+            base: Integrators,
+            levels: usize,
+        ) -> anyhow::Result<Solution<F>> {
+            // Integrate with the biggest time step
+            let mut result = self
+                .integrate(x, t_0, h, n, base)
+                .with_context(|| "Couldn't integrate with the biggest time step")?;
+            // Integrate with the smaller time steps
+            let mut results = Vec::with_capacity(levels);
+            for k in 1..=levels {
+                let m = 1 << k;
+                results.push(
+                    self.integrate(x, t_0, h / F::from(m).unwrap(), n * m, base)
+                        .with_context(|| {
+                            format!("Couldn't integrate with the time step h / {m}")
+                        })?,
+                );
+            }
+            // Extrapolate the states on the common grid
+            for i in 1..=n {
+                let states: Vec<Vec<F>> = std::iter::once(result.state(i))
+                    .chain(
+                        results
+                            .iter()
+                            .enumerate()
+                            .map(|(k, r)| r.state(i << (k + 1))),
+                    )
+                    .collect();
+                let mut x = richardson(&states, base.order(), base.error_step());
+                // Compute the accelerations at the extrapolated positions
+                let lt1 = x.len() / 3;
+                let a = self
+                    .accelerations(result.times()[i], &x[0..lt1])
+                    .with_context(|| format!("Couldn't compute the accelerations at {i}"))?;
+                x[2 * lt1..].copy_from_slice(&a);
+                result.set_state_from_slice(i, &x);
+            }
+            result.set_energies(Vec::new());
+            Ok(result)
+        }
+    };
+}
+
+pub(super) use integrate_extrapolated;
+
+#[test]
+fn test() -> anyhow::Result<()> {
+    use crate::{Float, ResultExt, SymplecticIntegrator, SymplecticIntegrators};
+
+    // Implement the trait on a test struct
+    struct Test {}
+    impl<F: Float> SymplecticIntegrator<F> for Test {
+        fn accelerations(&self, _t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![-x[0]])
+        }
+    }
+    let test = Test {};
+
+    // Integrate the harmonic oscillator using the leapfrog
+    // method with and without the extrapolation
+    let (x, h, n) = ([1_f64, 0., -1.], 0.1, 100);
+    let base = test.integrate(&x, 0., h, n, SymplecticIntegrators::Leapfrog)?;
+    let extrapolated =
+        test.integrate_extrapolated(&x, 0., h, n, SymplecticIntegrators::Leapfrog, 2)?;
+
+    // Compare the errors
+    let exact = f64::cos(10.);
+    let error = (base.state(n)[0] - exact).abs();
+    let error_extrapolated = (extrapolated.state(n)[0] - exact).abs();
+    if error_extrapolated * 1e3 >= error {
+        return Err(anyhow::anyhow!(
+            "The extrapolation didn't improve the accuracy: {error} vs. {error_extrapolated}"
+        ));
+    }
+
+    // Check that the accelerations match the extrapolated positions
+    if (extrapolated.state(n)[2] + extrapolated.state(n)[0]).abs() >= f64::EPSILON {
+        return Err(anyhow::anyhow!(
+            "The accelerations don't match the extrapolated positions: {:?}",
+            extrapolated.state(n)
+        ));
+    }
+
+    // Check the empirical order of the extrapolated 4th-order Yoshida method
+    // (its error contains only even powers, so two levels give the 8th order)
+    let error = |h: f64, n: usize| -> anyhow::Result<f64> {
+        let result =
+            test.integrate_extrapolated(&x, 0., h, n, SymplecticIntegrators::Yoshida4th, 2)?;
+        Ok((result.state(n)[0] - exact).abs())
+    };
+    let order = (error(0.2, 50)? / error(0.1, 100)?).log2();
+    if !(7.5..8.5).contains(&order) {
+        return Err(anyhow::anyhow!(
+            "The empirical order of the extrapolated method is incorrect: {order}"
+        ));
+    }
+
+    Ok(())
+}
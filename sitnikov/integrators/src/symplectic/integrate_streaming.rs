@@ -0,0 +1,121 @@
+//! Provides the [`integrate_streaming`] macro, plus tests for the method
+
+/// Defines the [`integrate_streaming`](crate::SymplecticIntegrator#method.integrate_streaming) method
+macro_rules! integrate_streaming {
+    () => {
+        /// Integrate the system of 1st-order ODEs, passing each state (starting
+        /// from the initial one) to the sink instead of keeping them in memory
+        ///
+        /// The steps are made one at a time (see [`step`](Self::step)), so
+        /// only the current state is held, and the multistep methods are
+        /// restarted on each step. The integration stops on the first error
+        /// of the sink
+        ///
+        /// Arguments:
+        /// * `x` --- Vector of initial values;
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Number of iterations;
+        /// * `integrator` --- Integration method;
+        /// * `sink` --- Function taking the index of the state and the state itself.
+        fn integrate_streaming(
+            &self,
+            x: &[F],
+            t_0: F,
+            h: F,
+            n: usize,
+            integrator: Integrators,
+            sink: &mut dyn FnMut(usize, &[F]) -> anyhow::Result<()>,
+        ) -> anyhow::Result<()> {
+            // Get a token for using the private methods
+            let token = Token {};
+            // Prepare a buffer for the steps
+            let mut buffer = self.prepare(x, t_0, 1, &token)?;
+            sink(0, x).with_context(|| "Couldn't pass the initial state to the sink")?;
+            let mut x = x.to_vec();
+            for i in 0..n {
+                // Compute the time moment
+                let t = t_0 + F::from(i).unwrap() * h;
+                // Make a step
+                x = self
+                    .step(&mut buffer, t, &x, h, integrator, &token)
+                    .with_context(|| format!("Couldn't make a step at t = {t}"))?;
+                sink(i + 1, &x)
+                    .with_context(|| format!("Couldn't pass the state {} to the sink", i + 1))?;
+            }
+            Ok(())
+        }
+    };
+}
+
+pub(super) use integrate_streaming;
+
+#[test]
+fn test() -> anyhow::Result<()> {
+    use crate::{Float, ResultExt, SymplecticIntegrator, SymplecticIntegrators};
+
+    // Implement the trait on a test struct
+    struct Test {}
+    impl<F: Float> SymplecticIntegrator<F> for Test {
+        fn accelerations(&self, t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![t - x[0]])
+        }
+    }
+    let test = Test {};
+
+    // Integrate the system, collecting the streamed states
+    let x = [1_f64, 0., -1.];
+    let n = 100;
+    let mut states = Vec::new();
+    test.integrate_streaming(
+        &x,
+        0.,
+        1e-2,
+        n,
+        SymplecticIntegrators::Yoshida4th,
+        &mut |i, x| {
+            if i != states.len() {
+                return Err(anyhow::anyhow!("The states are out of order"));
+            }
+            states.push(x.to_vec());
+            Ok(())
+        },
+    )?;
+
+    // Compare them with the solution
+    let result = test.integrate(&x, 0., 1e-2, n, SymplecticIntegrators::Yoshida4th)?;
+    if states.len() != n + 1
+        || states
+            .iter()
+            .enumerate()
+            .any(|(i, x)| *x != result.state(i))
+    {
+        return Err(anyhow::anyhow!(
+            "The streamed states don't match the solution"
+        ));
+    }
+
+    // Check that an error of the sink stops the integration
+    let mut count = 0;
+    let stopped = test.integrate_streaming(
+        &x,
+        0.,
+        1e-2,
+        n,
+        SymplecticIntegrators::Yoshida4th,
+        &mut |i, _| {
+            count += 1;
+            if i == 10 {
+                return Err(anyhow::anyhow!("The sink is full"));
+            }
+            Ok(())
+        },
+    );
+    if stopped.is_ok() || count != 11 {
+        return Err(anyhow::anyhow!(
+            "The error of the sink didn't stop the integration"
+        ));
+    }
+
+    Ok(())
+}
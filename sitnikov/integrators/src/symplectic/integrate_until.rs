@@ -0,0 +1,92 @@
+//! Provides the [`integrate_until`] macro, plus tests for the method
+
+/// Defines the [`integrate_until`](crate::SymplecticIntegrator#method.integrate_until) method
+macro_rules! integrate_until {
+    () => {
+        /// Integrate the system of 1st-order ODEs until the final time moment
+        ///
+        /// The whole number of time steps is made first. If the time step
+        /// doesn't divide the interval evenly, a final shorter step lands
+        /// exactly on `t_end` (and the solution gets an extra state)
+        ///
+        /// The map of the whole interval is still symplectic (it's a
+        /// composition of symplectic maps), but the final shorter step
+        /// breaks the backward error analysis: the steps of different
+        /// lengths exactly conserve different modified Hamiltonians, so
+        /// there is no single one conserved along the whole trajectory
+        ///
+        /// Arguments:
+        /// * `x` --- Vector of initial values;
+        /// * `t_0` --- Initial value of time;
+        /// * `t_end` --- Final value of time;
+        /// * `h` --- Time step (its sign must match the direction of integration);
+        /// * `integrator` --- Integration method.
+        fn integrate_until(
+            &self,
+            x: &[F],
+            t_0: F,
+            t_end: F,
+            h: F,
+            integrator: Integrators,
+        ) -> anyhow::Result<Solution<F>> {
+            // Compute the number of time steps
+            let (n, h_last) = steps(t_0, t_end, h)?;
+            // Make the whole time steps
+            let mut result = self.integrate(x, t_0, h, n, integrator)?;
+            // Make the final shorter step
+            if let Some(h_last) = h_last {
+                let t = t_0 + F::from(n).unwrap() * h;
+                let last = self
+                    .integrate(&result.state(n), t, h_last, 1, integrator)
+                    .with_context(|| format!("Couldn't make the final step at t = {t}"))?;
+                result.append(&last);
+            }
+            let i = result.times().len() - 1;
+            result.set_time(i, t_end);
+            Ok(result)
+        }
+    };
+}
+
+pub(super) use integrate_until;
+
+#[test]
+fn test() -> anyhow::Result<()> {
+    use crate::{Float, SymplecticIntegrator, SymplecticIntegrators};
+
+    // Implement the trait on a test struct
+    struct Test {}
+    impl<F: Float> SymplecticIntegrator<F> for Test {
+        fn accelerations(&self, t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![t - x[0]])
+        }
+    }
+    let test = Test {};
+
+    // Integrate with a time step which doesn't divide the interval evenly
+    let x = [1., 0., -1.];
+    let (t_0, t_end, h): (f64, f64, f64) = (0., 10., 3e-3);
+    let result = test.integrate_until(&x, t_0, t_end, h, SymplecticIntegrators::Yoshida4th)?;
+
+    // Check the number of states and the final time moment
+    let n = 3334;
+    let times = result.times();
+    if result.ncols() != n + 1 || times.len() != n + 1 || (times[n] - t_end).abs() > 0. {
+        return Err(anyhow::anyhow!(
+            "The number of states or the final time moment is incorrect: {} states, t = {:?}",
+            result.ncols(),
+            times.last()
+        ));
+    }
+
+    // Compare the final position with the analytic solution
+    let x_0 = t_end - f64::sin(t_end) + f64::cos(t_end);
+    if (result[(0, n)] - x_0).abs() >= 1e-8 {
+        return Err(anyhow::anyhow!(
+            "The final position is incorrect: {x_0} vs {}",
+            result[(0, n)]
+        ));
+    }
+
+    Ok(())
+}
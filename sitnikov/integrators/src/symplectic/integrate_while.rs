@@ -0,0 +1,101 @@
+//! Provides the [`integrate_while`] macro, plus tests for the method
+
+/// Defines the [`integrate_while`](crate::SymplecticIntegrator#method.integrate_while) method
+macro_rules! integrate_while {
+    () => {
+        /// Integrate the system of 1st-order ODEs until the stopping predicate
+        /// returns `true` (the state it returned `true` for is the last one)
+        /// or the maximum number of iterations is reached, return the solution
+        /// truncated to the computed states and the number of iterations made
+        ///
+        /// Arguments:
+        /// * `x` --- Vector of initial values;
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n_max` --- Maximum number of iterations;
+        /// * `integrator` --- Integration method;
+        /// * `stop` --- Stopping predicate (takes the index, the time moment and the state).
+        fn integrate_while(
+            &self,
+            x: &[F],
+            t_0: F,
+            h: F,
+            n_max: usize,
+            integrator: Integrators,
+            stop: impl Fn(usize, F, &[F]) -> bool,
+        ) -> anyhow::Result<(Solution<F>, usize)> {
+            // Get a token for using the private methods
+            let token = Token {};
+            // Integrate
+            let result = self.dispatch(
+                x,
+                t_0,
+                h,
+                n_max,
+                integrator,
+                &mut |i, t, x| !stop(i, t, x),
+                &token,
+            )?;
+            let n = result.ncols() - 1;
+            Ok((result, n))
+        }
+    };
+}
+
+pub(super) use integrate_while;
+
+#[test]
+fn test() -> anyhow::Result<()> {
+    use crate::{Float, ResultExt, SymplecticIntegrator, SymplecticIntegrators};
+
+    // Implement the trait on a test struct
+    struct Test {}
+    impl<F: Float> SymplecticIntegrator<F> for Test {
+        fn accelerations(&self, t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![t - x[0]])
+        }
+    }
+    let test = Test {};
+
+    // Integrate until the position exceeds the threshold
+    let x = [1., 0., -1.];
+    let threshold = 2.;
+    let (result, n) = test.integrate_while(
+        &x,
+        0.,
+        1e-2,
+        10_000,
+        SymplecticIntegrators::Yoshida4th,
+        |_, _, x| x[0] > threshold,
+    )?;
+
+    // Check that the solution is truncated right after the crossing
+    let z = result.result(0);
+    if n >= 10_000
+        || result.ncols() != n + 1
+        || result.times().len() != n + 1
+        || z[n] <= threshold
+        || z[..n].iter().any(|&z| z > threshold)
+    {
+        return Err(anyhow::anyhow!(
+            "The integration didn't stop right after the crossing: {n} iterations"
+        ));
+    }
+
+    // Check that the integration isn't stopped without the crossing
+    let (_, n) = test.integrate_while(
+        &x,
+        0.,
+        1e-2,
+        100,
+        SymplecticIntegrators::Yoshida4th,
+        |_, _, x| x[0] > 1e3,
+    )?;
+    if n != 100 {
+        return Err(anyhow::anyhow!(
+            "The integration stopped without the crossing: {n} iterations"
+        ));
+    }
+
+    Ok(())
+}
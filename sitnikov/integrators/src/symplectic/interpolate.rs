@@ -0,0 +1,74 @@
+//! Provides the [`interpolate`] macro, plus tests for the method
+
+/// Defines the [`interpolate`](crate::SymplecticIntegrator#method.interpolate) method
+macro_rules! interpolate {
+    () => {
+        /// Interpolate the solution at the passed time moment
+        ///
+        /// The positions and velocities are interpolated using cubic Hermite
+        /// polynomials (with the velocities and accelerations in the states
+        /// as their derivatives), while the accelerations are computed anew
+        ///
+        /// Arguments:
+        /// * `solution` --- Solution of the system;
+        /// * `t` --- Time moment.
+        fn interpolate(&self, solution: &Solution<F>, t: F) -> anyhow::Result<Vec<F>> {
+            // Interpolate the state (the derivatives of the
+            // accelerations are not known, so they're zeroed)
+            let mut x = solution.interpolate(t, |_, x| {
+                let lt1 = x.len() / 3;
+                let mut d = x[lt1..].to_vec();
+                d.extend(std::iter::repeat(F::zero()).take(lt1));
+                Ok(d)
+            })?;
+            // Compute the accelerations
+            let lt1 = x.len() / 3;
+            let a = self
+                .accelerations(t, &x[0..lt1])
+                .with_context(|| format!("Couldn't compute the accelerations at t = {t}"))?;
+            x[2 * lt1..].copy_from_slice(&a);
+            Ok(x)
+        }
+    };
+}
+
+pub(super) use interpolate;
+
+#[test]
+fn test() -> anyhow::Result<()> {
+    use crate::{Float, SymplecticIntegrator, SymplecticIntegrators};
+
+    // Implement the trait on a test struct (a harmonic oscillator)
+    struct Test {}
+    impl<F: Float> SymplecticIntegrator<F> for Test {
+        fn accelerations(&self, _t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![-x[0]])
+        }
+    }
+    let test = Test {};
+
+    // Integrate with a coarse time step
+    let result = test.integrate(
+        &[1_f64, 0., -1.],
+        0.,
+        0.1,
+        100,
+        SymplecticIntegrators::Yoshida4th,
+    )?;
+
+    // Compare the interpolated states on a dense grid with the exact solution
+    for k in 0..=1000 {
+        let t = f64::from(k) * 1e-2;
+        let x = test.interpolate(&result, t)?;
+        if (x[0] - t.cos()).abs() >= 1e-4
+            || (x[1] + t.sin()).abs() >= 1e-4
+            || (x[2] + x[0]).abs() > 0.
+        {
+            return Err(anyhow::anyhow!(
+                "The interpolated state at t = {t} is incorrect: {x:?}"
+            ));
+        }
+    }
+
+    Ok(())
+}
@@ -5,11 +5,18 @@ macro_rules! leapfrog {
     () => {
         /// Integrate the system using the leapfrog method
         ///
+        /// The positions are updated first (using the current velocities
+        /// and accelerations), then the velocities are updated using the
+        /// average of the current and new accelerations. In other words,
+        /// this is the velocity-Verlet method (the kick-drift-kick form
+        /// of the leapfrog method gives the same states).
+        ///
         /// Arguments:
         /// * `t_0` --- Initial value of time;
         /// * `h` --- Time step;
         /// * `n` --- Number of iterations;
-        /// * `result` --- Result matrix;
+        /// * `result` --- Solution;
+        /// * `callback` --- Function called after each step (returns `false` to stop);
         /// * `token` --- Private token.
         #[replace_float_literals(F::from(literal).unwrap())]
         fn leapfrog(
@@ -17,7 +24,8 @@ macro_rules! leapfrog {
             t_0: F,
             h: F,
             n: usize,
-            result: &mut Result<F>,
+            result: &mut Solution<F>,
+            callback: &mut dyn FnMut(usize, F, &[F]) -> bool,
             token: &Token,
         ) -> anyhow::Result<()> {
             // Get the initial state
@@ -31,7 +39,11 @@ macro_rules! leapfrog {
                     .leapfrog_once(t, &x, h, token)
                     .with_context(|| "Couldn't compute the next state")?;
                 // Put the new state in the result
-                result.set_state(i + 1, x.clone());
+                result.set_state_from_slice(i + 1, &x);
+                result.set_time(i + 1, t_0 + F::from(i + 1).unwrap() * h);
+                if !callback(i + 1, result.times()[i + 1], &x) {
+                    break;
+                }
             }
             Ok(())
         }
@@ -41,4 +53,31 @@ macro_rules! leapfrog {
 pub(super) use leapfrog;
 
 #[cfg(test)]
-super::test_method::test_method!(leapfrog, 2);
+super::test_method::test_method!(SymplecticIntegrators::Leapfrog);
+
+#[test]
+fn test_velocity_verlet() -> anyhow::Result<()> {
+    use crate::{Float, SymplecticIntegrator, SymplecticIntegrators};
+
+    // Implement the trait on a test struct (a pendulum)
+    struct Test {}
+    impl<F: Float> SymplecticIntegrator<F> for Test {
+        fn accelerations(&self, _t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![-F::sin(x[0])])
+        }
+    }
+    let test = Test {};
+
+    // Check that the velocity-Verlet method is the same as the leapfrog one
+    let x = [1., 0., -f64::sin(1.)];
+    let result = test.integrate(&x, 0., 1e-2, 1000, SymplecticIntegrators::Leapfrog)?;
+    let result_verlet =
+        test.integrate(&x, 0., 1e-2, 1000, SymplecticIntegrators::VelocityVerlet)?;
+    if result != result_verlet {
+        return Err(anyhow::anyhow!(
+            "The velocity-Verlet method gives different results"
+        ));
+    }
+
+    Ok(())
+}
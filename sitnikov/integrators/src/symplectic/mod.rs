@@ -1,13 +1,42 @@
 //! Provides the [`SymplecticIntegrator`](crate::SymplecticIntegrator) trait
 
+#[doc(hidden)]
+mod convergence_study;
+#[doc(hidden)]
+mod forest_ruth;
+#[doc(hidden)]
+mod gauss_legendre_2;
 #[doc(hidden)]
 mod integrate;
+#[cfg(feature = "parallel")]
+#[doc(hidden)]
+mod integrate_batch;
+#[doc(hidden)]
+mod integrate_extrapolated;
+#[doc(hidden)]
+mod integrate_streaming;
+#[doc(hidden)]
+mod integrate_until;
+#[doc(hidden)]
+mod integrate_while;
+#[doc(hidden)]
+mod interpolate;
 #[doc(hidden)]
 mod leapfrog;
 #[doc(hidden)]
 mod leapfrog_once;
 #[doc(hidden)]
+mod pefrl;
+#[doc(hidden)]
+mod stormer_verlet;
+#[doc(hidden)]
+mod symplectic_euler;
+#[doc(hidden)]
 mod yoshida_4th;
+#[doc(hidden)]
+mod yoshida_4th_once;
+#[doc(hidden)]
+mod yoshida_6th;
 
 #[cfg(test)]
 mod test_method;
@@ -15,26 +44,113 @@ mod test_method;
 mod yoshida_4th_2;
 
 use anyhow::{self, Context};
-use nalgebra::DVector;
+use nalgebra::DMatrix;
 use numeric_literals::replace_float_literals;
 
+use std::path::Path;
+
+use crate::checkpoint::{load_checkpoint, save_checkpoint};
+use crate::integrate_resumable::integrate_resumable;
+use crate::newton::newton;
 use crate::prepare::prepare;
-use crate::{Float, Result, ResultExt, Token};
+use crate::richardson::richardson;
+use crate::step::step;
+use crate::steps::steps;
+use crate::{CheckpointParameters, Float, Result, ResultExt, Solution, Token};
 
+pub(self) use convergence_study::convergence_study;
+pub(self) use forest_ruth::forest_ruth;
+pub(self) use gauss_legendre_2::gauss_legendre_2;
 pub(self) use integrate::integrate;
+#[cfg(feature = "parallel")]
+pub(self) use integrate_batch::integrate_batch;
+pub(self) use integrate_extrapolated::integrate_extrapolated;
+pub(self) use integrate_streaming::integrate_streaming;
+pub(self) use integrate_until::integrate_until;
+pub(self) use integrate_while::integrate_while;
+pub(self) use interpolate::interpolate;
 pub(self) use leapfrog::leapfrog;
 pub(self) use leapfrog_once::leapfrog_once;
+pub(self) use pefrl::pefrl;
+pub(self) use stormer_verlet::stormer_verlet;
+pub(self) use symplectic_euler::symplectic_euler;
 pub(self) use yoshida_4th::yoshida_4th;
+pub(self) use yoshida_4th_once::yoshida_4th_once;
+pub(self) use yoshida_6th::yoshida_6th;
 
 #[cfg(test)]
 pub(self) use yoshida_4th_2::yoshida_4th_2;
 
 /// Symplectic integrators
+#[derive(Clone, Copy, Debug)]
 pub enum Integrators {
-    /// Leapfrog method
+    /// 4th-order Forest-Ruth method
+    ForestRuth,
+    /// 2-stage (4th-order) Gauss-Legendre method
+    GaussLegendre2,
+    /// Leapfrog method (in the velocity-Verlet form)
     Leapfrog,
+    /// 4th-order PEFRL (Position Extended Forest-Ruth Like) method
+    Pefrl,
+    /// Störmer-Verlet method: the two-point recurrence for the positions
+    /// (valid since the accelerations don't depend on the velocities)
+    StormerVerlet,
+    /// Symplectic (semi-implicit) Euler method
+    SymplecticEuler,
+    /// Velocity-Verlet method: update the positions using the current
+    /// velocities and accelerations, compute the new accelerations,
+    /// then update the velocities using the average of both (this is
+    /// the same scheme as [`Leapfrog`](Integrators::Leapfrog), so the
+    /// results are identical)
+    VelocityVerlet,
     /// 4th-order Yoshida method
     Yoshida4th,
+    /// 6th-order Yoshida method
+    Yoshida6th,
+}
+
+/// Get the theoretical order of the method (e.g., the expected
+/// slope of the error versus the time step on a log-log scale)
+///
+/// Arguments:
+/// * `method` --- Integration method.
+#[must_use]
+pub fn order(method: &Integrators) -> usize {
+    match *method {
+        Integrators::SymplecticEuler => 1,
+        Integrators::Leapfrog | Integrators::StormerVerlet | Integrators::VelocityVerlet => 2,
+        Integrators::ForestRuth
+        | Integrators::GaussLegendre2
+        | Integrators::Pefrl
+        | Integrators::Yoshida4th => 4,
+        Integrators::Yoshida6th => 6,
+    }
+}
+
+impl Integrators {
+    /// Get the order of the method (see [`symplectic_order`](crate::symplectic_order))
+    #[must_use]
+    pub fn order(self) -> usize {
+        order(&self)
+    }
+
+    /// Get the step between the powers of the time step in the expansion
+    /// of the error of the method (the errors of the symmetric methods
+    /// contain only even powers, so the step is 2 for them)
+    #[must_use]
+    pub fn error_step(self) -> usize {
+        match self {
+            Integrators::SymplecticEuler => 1,
+            _ => 2,
+        }
+    }
+
+    /// Check if the method is a multistep one (none of the symplectic
+    /// methods are, but the check is shared with the general methods)
+    #[must_use]
+    pub fn is_multistep(self) -> bool {
+        false
+    }
 }
 
 /// A symplectic integrator for a system of 1st-order ODEs
@@ -46,12 +162,43 @@ pub trait Integrator<F: Float> {
     /// * `t` --- Current time moment;
     /// * `x` --- Current values of positions.
     fn accelerations(&self, t: F, x: &[F]) -> anyhow::Result<Vec<F>>;
+    /// Compute the energy of the system in the current state (if the
+    /// energy is defined, it's recorded at each step of the integration)
+    ///
+    /// Arguments:
+    /// * `t` --- Current time moment;
+    /// * `x` --- Current state of the system.
+    fn energy(&self, _t: F, _x: &[F]) -> Option<F> {
+        None
+    }
+    /// Get the length of the state of the system (if it's defined,
+    /// the vectors of initial values are checked against it)
+    fn dim(&self) -> Option<usize> {
+        None
+    }
     // The rest of the methods are defined by these macros
+    convergence_study!();
+    forest_ruth!();
+    gauss_legendre_2!();
     integrate!();
+    #[cfg(feature = "parallel")]
+    integrate_batch!();
+    integrate_extrapolated!();
+    integrate_resumable!();
+    integrate_streaming!();
+    integrate_until!();
+    integrate_while!();
+    interpolate!();
     leapfrog!();
     leapfrog_once!();
-    prepare!();
+    pefrl!();
+    prepare!(3);
+    step!();
+    stormer_verlet!();
+    symplectic_euler!();
     yoshida_4th!();
+    yoshida_4th_once!();
+    yoshida_6th!();
     #[cfg(test)]
     yoshida_4th_2!();
 }
@@ -0,0 +1,129 @@
+//! Provides the [`pefrl`] macro, plus tests for the method
+
+/// Defines the [`pefrl`](crate::SymplecticIntegrator#method.pefrl) method
+macro_rules! pefrl {
+    () => {
+        /// Integrate the system using the 4th-order PEFRL (Position Extended
+        /// Forest-Ruth Like) method (see I. P. Omelyan et al., 2002)
+        ///
+        /// The positions drift five times, and the velocities are kicked
+        /// between the drifts. The accelerations of the next state are
+        /// computed at the final positions, so each step evaluates them
+        /// five times
+        ///
+        /// Arguments:
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Number of iterations;
+        /// * `result` --- Solution;
+        /// * `callback` --- Function called after each step (returns `false` to stop);
+        /// * `token` --- Private token.
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn pefrl(
+            &self,
+            t_0: F,
+            h: F,
+            n: usize,
+            result: &mut Solution<F>,
+            callback: &mut dyn FnMut(usize, F, &[F]) -> bool,
+            _: &Token,
+        ) -> anyhow::Result<()> {
+            // Compute the coefficients of the drifts and kicks
+            let c = F::coefficients();
+            let drifts = [
+                c.xi * h,
+                c.chi * h,
+                (1. - 2. * (c.chi + c.xi)) * h,
+                c.chi * h,
+                c.xi * h,
+            ];
+            let kicks = [
+                (1. - 2. * c.lambda) / 2. * h,
+                c.lambda * h,
+                c.lambda * h,
+                (1. - 2. * c.lambda) / 2. * h,
+            ];
+            // Get the initial state
+            let mut x = result.initial_values();
+            // Get the length of the state vector and its thirds
+            let l = x.len();
+            let lt1 = l / 3;
+            let lt2 = 2 * lt1;
+            // Integrate
+            for i in 0..n {
+                // Compute the time moment
+                let mut t = t_0 + F::from(i).unwrap() * h;
+                for (k, &drift) in drifts.iter().enumerate() {
+                    // Update the positions
+                    for j in 0..lt1 {
+                        x[j] = x[j] + drift * x[j + lt1];
+                    }
+                    t = t + drift;
+                    // Compute the accelerations
+                    let a = self
+                        .accelerations(t, &x[0..lt1])
+                        .with_context(|| "Couldn't compute the accelerations")?;
+                    // Update the accelerations and velocities
+                    // (there is no kick after the last drift)
+                    for j in lt1..lt2 {
+                        x[j + lt1] = a[j - lt1];
+                        if let Some(&kick) = kicks.get(k) {
+                            x[j] = x[j] + kick * x[j + lt1];
+                        }
+                    }
+                }
+                // Put the new state in the result
+                result.set_state_from_slice(i + 1, &x);
+                result.set_time(i + 1, t_0 + F::from(i + 1).unwrap() * h);
+                if !callback(i + 1, result.times()[i + 1], &x) {
+                    break;
+                }
+            }
+            Ok(())
+        }
+    };
+}
+
+pub(super) use pefrl;
+
+#[cfg(test)]
+super::test_method::test_method!(SymplecticIntegrators::Pefrl);
+
+#[test]
+fn test_energy_drift() -> anyhow::Result<()> {
+    use crate::{Float, SymplecticIntegrator, SymplecticIntegrators};
+
+    // Implement the trait on a test struct (a pendulum)
+    struct Test {}
+    impl<F: Float> SymplecticIntegrator<F> for Test {
+        fn accelerations(&self, _t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![-F::sin(x[0])])
+        }
+    }
+    let test = Test {};
+
+    // Define the energy of the pendulum
+    let energy = |z: f64, z_v: f64| z_v.powi(2) / 2. - f64::cos(z);
+
+    // Compute the maximum deviation of the energy over a long integration
+    let x = [2., 0., -f64::sin(2.)];
+    let e_0 = energy(x[0], x[1]);
+    let drift = |integrator| -> anyhow::Result<f64> {
+        let result = test.integrate(&x, 0., 0.1, 20_000, integrator)?;
+        Ok(result
+            .column_iter()
+            .map(|x| (energy(x[0], x[1]) - e_0).abs())
+            .fold(0., f64::max))
+    };
+    let drift_pefrl = drift(SymplecticIntegrators::Pefrl)?;
+    let drift_leapfrog = drift(SymplecticIntegrators::Leapfrog)?;
+
+    // Compare the deviations
+    if drift_pefrl >= drift_leapfrog / 100. {
+        return Err(anyhow::anyhow!(
+            "The energy deviates too much: {drift_pefrl} (PEFRL) vs {drift_leapfrog} (leapfrog)"
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+//! Provides the [`stormer_verlet`] macro, plus tests for the method
+
+/// Defines the [`stormer_verlet`](crate::SymplecticIntegrator#method.stormer_verlet) method
+macro_rules! stormer_verlet {
+    () => {
+        /// Integrate the system using the Störmer-Verlet method
+        ///
+        /// The positions are computed by the two-point recurrence `z_{i+1} =
+        /// 2 z_i - z_{i-1} + h^2 a_i`, which doesn't use the velocities (the
+        /// previous positions of the first step are bootstrapped from the
+        /// initial velocities). This is valid only if the accelerations don't
+        /// depend on the velocities, which holds for any system implementing the
+        /// trait (the accelerations are computed from the positions only). The
+        /// velocities in the states are recovered afterwards the same way as
+        /// in the velocity-Verlet method, and don't affect the positions.
+        ///
+        /// Arguments:
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Number of iterations;
+        /// * `result` --- Solution;
+        /// * `callback` --- Function called after each step (returns `false` to stop);
+        /// * `token` --- Private token.
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn stormer_verlet(
+            &self,
+            t_0: F,
+            h: F,
+            n: usize,
+            result: &mut Solution<F>,
+            callback: &mut dyn FnMut(usize, F, &[F]) -> bool,
+            _: &Token,
+        ) -> anyhow::Result<()> {
+            // Get the initial state
+            let mut x = result.initial_values();
+            let l = x.len() / 3;
+            // Bootstrap the previous positions
+            let mut z_prev: Vec<F> = (0..l)
+                .map(|j| x[j] - h * x[l + j] + 0.5 * h.powi(2) * x[2 * l + j])
+                .collect();
+            // Integrate
+            for i in 0..n {
+                // Compute the next time moment
+                let t = t_0 + F::from(i + 1).unwrap() * h;
+                // Compute the next positions
+                let z: Vec<F> = (0..l)
+                    .map(|j| 2. * x[j] - z_prev[j] + h.powi(2) * x[2 * l + j])
+                    .collect();
+                // Compute the accelerations
+                let a = self
+                    .accelerations(t, &z)
+                    .with_context(|| format!("Couldn't compute the accelerations at t = {t}"))?;
+                // Recover the velocities
+                let v: Vec<F> = (0..l).map(|j| (z[j] - x[j]) / h + 0.5 * h * a[j]).collect();
+                z_prev = x[0..l].to_vec();
+                x = [z, v, a].concat();
+                // Put the new state in the result
+                result.set_state_from_slice(i + 1, &x);
+                result.set_time(i + 1, t);
+                if !callback(i + 1, t, &x) {
+                    break;
+                }
+            }
+            Ok(())
+        }
+    };
+}
+
+pub(super) use stormer_verlet;
+
+#[cfg(test)]
+super::test_method::test_method!(SymplecticIntegrators::StormerVerlet);
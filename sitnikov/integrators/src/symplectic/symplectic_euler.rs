@@ -0,0 +1,63 @@
+//! Provides the [`symplectic_euler`] macro, plus tests for the method
+
+/// Defines the [`symplectic_euler`](crate::SymplecticIntegrator#method.symplectic_euler) method
+macro_rules! symplectic_euler {
+    () => {
+        /// Integrate the system using the symplectic (semi-implicit) Euler method
+        ///
+        /// Arguments:
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Number of iterations;
+        /// * `result` --- Solution;
+        /// * `callback` --- Function called after each step (returns `false` to stop);
+        /// * `token` --- Private token.
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn symplectic_euler(
+            &self,
+            t_0: F,
+            h: F,
+            n: usize,
+            result: &mut Solution<F>,
+            callback: &mut dyn FnMut(usize, F, &[F]) -> bool,
+            _: &Token,
+        ) -> anyhow::Result<()> {
+            // Get the initial state
+            let mut x = result.initial_values();
+            // Get the length of the state vector and its thirds
+            let l = x.len();
+            let lt1 = l / 3;
+            let lt2 = 2 * lt1;
+            // Integrate
+            for i in 0..n {
+                // Compute the time moment
+                let t = t_0 + F::from(i).unwrap() * h;
+                // Update the positions
+                for j in 0..lt1 {
+                    x[j] = x[j] + x[j + lt1] * h;
+                }
+                // Compute new accelerations
+                let a = self
+                    .accelerations(t + h, &x[0..lt1])
+                    .with_context(|| "Couldn't compute the new accelerations")?;
+                // Update the accelerations and velocities
+                for j in lt1..lt2 {
+                    x[j + lt1] = a[j - lt1];
+                    x[j] = x[j] + x[j + lt1] * h;
+                }
+                // Put the new state in the result
+                result.set_state_from_slice(i + 1, &x);
+                result.set_time(i + 1, t_0 + F::from(i + 1).unwrap() * h);
+                if !callback(i + 1, result.times()[i + 1], &x) {
+                    break;
+                }
+            }
+            Ok(())
+        }
+    };
+}
+
+pub(super) use symplectic_euler;
+
+#[cfg(test)]
+super::test_method::test_method!(SymplecticIntegrators::SymplecticEuler);
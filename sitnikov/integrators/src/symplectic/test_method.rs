@@ -1,9 +1,17 @@
 //! Provides the [`test`] macro
 
-/// Tests the method for correctness and time reversibility
+/// Tests the method of the passed integrator for correctness and time
+/// reversibility (the method is called via [`solve`], so it always matches
+/// the integrator, which also provides the order)
+///
+/// The tolerance is `10 * h^order`. A method without an integrator of its
+/// own (e.g., another implementation) can be tested in place of an
+/// integrator by passing it first, as in `test_method!(method as integrator)`
+///
+/// [`solve`]: crate::SymplecticIntegrator#method.solve
 #[cfg(test)]
 macro_rules! test_method {
-    ($method:ident, $order:literal) => {
+    (@test $integrator:expr, $integrate:expr) => {
         #[cfg(test)]
         use anyhow::{self, Context};
 
@@ -11,7 +19,10 @@ macro_rules! test_method {
         #[allow(clippy::cast_precision_loss)]
         fn test() -> anyhow::Result<()> {
             use crate::private::Token;
-            use crate::{Float, ResultExt, SymplecticIntegrator};
+            use crate::{
+                symplectic_order, Float, ResultExt, Solution, SymplecticIntegrator,
+                SymplecticIntegrators,
+            };
 
             // Implement the trait on a test struct
             type F = f64;
@@ -22,6 +33,8 @@ macro_rules! test_method {
                 }
             }
             let test = Test {};
+            let integrate: fn(&Test, &mut Solution<F>, F, F, usize, &Token) -> anyhow::Result<()> =
+                $integrate;
 
             // Define the integration parameters
             let t_0 = 0.;
@@ -31,13 +44,14 @@ macro_rules! test_method {
                 .with_context(|| "Couldn't compute the acceleration")?;
             let x = vec![p_0, 0., a[0]];
             let h = 1e-2;
+            let order = i32::try_from(symplectic_order(&$integrator)).unwrap();
             let n = 3000;
             let t = t_0 + h * n as f64;
             let token = Token {};
 
             // Integrate forward
-            let mut result = test.prepare(x, n, &token);
-            test.$method(t_0, h, n, &mut result, &token)
+            let mut result = test.prepare(&x, t_0, n, &token)?;
+            integrate(&test, &mut result, t_0, h, n, &token)
                 .with_context(|| "Couldn't integrate forward")?;
 
             // Check the results
@@ -45,15 +59,24 @@ macro_rules! test_method {
             let x: Vec<F> = result.state(n);
             if x.iter()
                 .zip(x_0.iter())
-                .any(|(&x, &x_0)| (x - x_0).abs() >= 10. * h.powi($order))
+                .any(|(&x, &x_0)| (x - x_0).abs() >= 10. * h.powi(order))
             {
                 return Err(anyhow::anyhow!(
                     "The result of integration is not the same as expected: {x_0:?} vs {x:?}"
                 ));
             }
 
+            // Check the time moments
+            let times = result.times();
+            if times.len() != n + 1 || (times[n] - t).abs() >= 1e-12 {
+                return Err(anyhow::anyhow!(
+                    "The time moments are not the same as expected: {t} vs {:?}",
+                    times.last()
+                ));
+            }
+
             // Integrate backward
-            test.$method(t, -h, n, &mut result, &token)
+            integrate(&test, &mut result, t, -h, n, &token)
                 .with_context(|| "Couldn't integrate backward")?;
 
             // Check the results
@@ -61,7 +84,7 @@ macro_rules! test_method {
             let x: Vec<F> = result.state(0);
             if x.iter()
                 .zip(x_0.iter())
-                .any(|(&x, &x_0)| (x - x_0).abs() >= 10. * h.powi($order))
+                .any(|(&x, &x_0)| (x - x_0).abs() >= 10. * h.powi(order))
             {
                 return Err(anyhow::anyhow!(
                     "The integrator doesn't have time reversibility: {x_0:?} vs {x:?}"
@@ -71,6 +94,16 @@ macro_rules! test_method {
             Ok(())
         }
     };
+    ($method:ident as $integrator:expr) => {
+        super::test_method::test_method!(@test $integrator, |test, result, t_0, h, n, token| {
+            test.$method(t_0, h, n, result, &mut |_, _, _| true, token)
+        });
+    };
+    ($integrator:expr) => {
+        super::test_method::test_method!(@test $integrator, |test, result, t_0, h, n, token| {
+            test.solve(result, t_0, h, n, $integrator, &mut |_, _, _| true, token)
+        });
+    };
 }
 
 #[cfg(test)]
@@ -1,18 +1,5 @@
 //! Provides the [`yoshida_4th`] macro, plus tests for the method
 
-use lazy_static::lazy_static;
-
-use crate::FloatMax;
-
-lazy_static! {
-    /// The first coefficient in the 4th-order Yoshida method
-    pub static ref D_1: FloatMax = 1. / (2. - FloatMax::exp(FloatMax::ln(2.) / 3.));
-    /// The second coefficient in the 4th-order Yoshida method
-    pub static ref D_2: FloatMax = 1. - 2. * *D_1;
-    /// The sum of the first two coefficients in the 4th-order Yoshida method
-    pub static ref D_3: FloatMax = *D_1 + *D_2;
-}
-
 /// Defines the [`yoshida_4th`](crate::SymplecticIntegrator#method.yoshida_4th) method
 macro_rules! yoshida_4th {
     () => {
@@ -22,7 +9,8 @@ macro_rules! yoshida_4th {
         /// * `t_0` --- Initial value of time;
         /// * `h` --- Time step;
         /// * `n` --- Number of iterations;
-        /// * `result` --- Result matrix;
+        /// * `result` --- Solution;
+        /// * `callback` --- Function called after each step (returns `false` to stop);
         /// * `token` --- Private token.
         #[replace_float_literals(F::from(literal).unwrap())]
         fn yoshida_4th(
@@ -30,27 +18,26 @@ macro_rules! yoshida_4th {
             t_0: F,
             h: F,
             n: usize,
-            result: &mut Result<F>,
+            result: &mut Solution<F>,
+            callback: &mut dyn FnMut(usize, F, &[F]) -> bool,
             token: &Token,
         ) -> anyhow::Result<()> {
-            // Compute the increments
-            let i_1 = h * F::from(*yoshida_4th::D_1).unwrap();
-            let i_2 = h * F::from(*yoshida_4th::D_2).unwrap();
-            let i_3 = h * F::from(*yoshida_4th::D_3).unwrap();
             // Get the initial state
             let mut x = result.initial_values();
             // Integrate
             for i in 0..n {
                 // Compute the time moment
                 let t = t_0 + F::from(i).unwrap() * h;
-                // Compute the next states
-                for (l, h) in [(0., i_1), (i_1, i_2), (i_3, i_1)] {
-                    x = self
-                        .leapfrog_once(t + l, &x, h, token)
-                        .with_context(|| "Couldn't compute one of the next states")?;
-                }
+                // Compute the next state
+                x = self
+                    .yoshida_4th_once(t, &x, h, token)
+                    .with_context(|| "Couldn't compute the next state")?;
                 // Put the new state in the result
-                result.set_state(i + 1, x.clone());
+                result.set_state_from_slice(i + 1, &x);
+                result.set_time(i + 1, t_0 + F::from(i + 1).unwrap() * h);
+                if !callback(i + 1, result.times()[i + 1], &x) {
+                    break;
+                }
             }
             Ok(())
         }
@@ -60,4 +47,49 @@ macro_rules! yoshida_4th {
 pub(super) use yoshida_4th;
 
 #[cfg(test)]
-super::test_method::test_method!(yoshida_4th, 4);
+super::test_method::test_method!(SymplecticIntegrators::Yoshida4th);
+
+#[test]
+#[allow(clippy::cast_precision_loss)]
+fn test_yoshida_4th_once() -> anyhow::Result<()> {
+    use crate::private::Token;
+    use crate::{Float, ResultExt, SymplecticIntegrator};
+
+    // Implement the trait on a test struct
+    struct Test {}
+    impl<F: Float> SymplecticIntegrator<F> for Test {
+        fn accelerations(&self, t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![t - x[0]])
+        }
+    }
+    let test = Test {};
+
+    // Define the integration parameters
+    let x = vec![1., 0., -1.];
+    let t_0 = 0.;
+    let h = 1e-2;
+    let n = 100;
+    let token = Token {};
+
+    // Integrate using the matrix-filling method
+    let mut result = test.prepare(&x, t_0, n, &token)?;
+    test.yoshida_4th(t_0, h, n, &mut result, &mut |_, _, _| true, &token)
+        .with_context(|| "Couldn't integrate")?;
+
+    // Integrate step by step and compare the states
+    let mut x = x;
+    for i in 0..n {
+        x = test
+            .yoshida_4th_once(t_0 + i as f64 * h, &x, h, &token)
+            .with_context(|| "Couldn't compute the next state")?;
+        let s = result.state(i + 1);
+        if x != s {
+            return Err(anyhow::anyhow!(
+                "The states at the index {} are not the same: {s:?} vs {x:?}",
+                i + 1
+            ));
+        }
+    }
+
+    Ok(())
+}
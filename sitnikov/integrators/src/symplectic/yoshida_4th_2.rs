@@ -26,7 +26,8 @@ macro_rules! yoshida_4th_2 {
         /// * `t_0` --- Initial value of time;
         /// * `h` --- Time step;
         /// * `n` --- Number of iterations;
-        /// * `result` --- Result matrix;
+        /// * `result` --- Solution;
+        /// * `callback` --- Function called after each step (returns `false` to stop);
         /// * `token` --- Private token.
         #[replace_float_literals(F::from(literal).unwrap())]
         fn yoshida_4th_2(
@@ -34,7 +35,8 @@ macro_rules! yoshida_4th_2 {
             t_0: F,
             h: F,
             n: usize,
-            result: &mut Result<F>,
+            result: &mut Solution<F>,
+            callback: &mut dyn FnMut(usize, F, &[F]) -> bool,
             _: &Token,
         ) -> anyhow::Result<()> {
             // Compute the coefficients
@@ -80,7 +82,11 @@ macro_rules! yoshida_4th_2 {
                     x[i] = x[i] + c_4 * x[i + lt1];
                 }
                 // Put the new state in the result
-                result.set_state(i + 1, x.clone());
+                result.set_state_from_slice(i + 1, &x);
+                result.set_time(i + 1, t_0 + F::from(i + 1).unwrap() * h);
+                if !callback(i + 1, result.times()[i + 1], &x) {
+                    break;
+                }
             }
             Ok(())
         }
@@ -90,4 +96,4 @@ macro_rules! yoshida_4th_2 {
 pub(super) use yoshida_4th_2;
 
 #[cfg(test)]
-super::test_method::test_method!(yoshida_4th_2, 4);
+super::test_method::test_method!(yoshida_4th_2 as SymplecticIntegrators::Yoshida4th);
@@ -0,0 +1,33 @@
+//! Provides the [`yoshida_4th_once`] macro
+
+/// Defines the [`yoshida_4th_once`](crate::SymplecticIntegrator#method.yoshida_4th_once) method
+macro_rules! yoshida_4th_once {
+    () => {
+        /// Integrate the system once using the 4th-order Yoshida method,
+        /// return the next state of the system
+        ///
+        /// Arguments:
+        /// * `t` --- Current time moment;
+        /// * `x` --- Current state of the system;
+        /// * `h` --- Time step;
+        /// * `token` --- Private token.
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn yoshida_4th_once(&self, t: F, x: &[F], h: F, token: &Token) -> anyhow::Result<Vec<F>> {
+            // Compute the increments
+            let c = F::coefficients();
+            let i_1 = h * c.d_1;
+            let i_2 = h * c.d_2;
+            let i_3 = h * c.d_3;
+            // Compute the next states
+            let mut x = x.to_vec();
+            for (l, h) in [(0., i_1), (i_1, i_2), (i_3, i_1)] {
+                x = self
+                    .leapfrog_once(t + l, &x, h, token)
+                    .with_context(|| "Couldn't compute one of the next states")?;
+            }
+            Ok(x)
+        }
+    };
+}
+
+pub(super) use yoshida_4th_once;
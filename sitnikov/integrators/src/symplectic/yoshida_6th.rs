@@ -0,0 +1,97 @@
+//! Provides the [`yoshida_6th`] macro, plus tests for the method
+
+/// Defines the [`yoshida_6th`](crate::SymplecticIntegrator#method.yoshida_6th) method
+macro_rules! yoshida_6th {
+    () => {
+        /// Integrate the system using the 6th-order Yoshida method
+        /// (a symmetric composition of seven leapfrog substeps)
+        ///
+        /// Arguments:
+        /// * `t_0` --- Initial value of time;
+        /// * `h` --- Time step;
+        /// * `n` --- Number of iterations;
+        /// * `result` --- Solution;
+        /// * `callback` --- Function called after each step (returns `false` to stop);
+        /// * `token` --- Private token.
+        #[replace_float_literals(F::from(literal).unwrap())]
+        fn yoshida_6th(
+            &self,
+            t_0: F,
+            h: F,
+            n: usize,
+            result: &mut Solution<F>,
+            callback: &mut dyn FnMut(usize, F, &[F]) -> bool,
+            token: &Token,
+        ) -> anyhow::Result<()> {
+            // Compute the substeps
+            let substeps = F::coefficients().w.map(|w| w * h);
+            // Get the initial state
+            let mut x = result.initial_values();
+            // Integrate
+            for i in 0..n {
+                // Compute the time moment
+                let mut t = t_0 + F::from(i).unwrap() * h;
+                // Compute the next states
+                for &substep in &substeps {
+                    x = self
+                        .leapfrog_once(t, &x, substep, token)
+                        .with_context(|| "Couldn't compute one of the next states")?;
+                    t = t + substep;
+                }
+                // Put the new state in the result
+                result.set_state_from_slice(i + 1, &x);
+                result.set_time(i + 1, t_0 + F::from(i + 1).unwrap() * h);
+                if !callback(i + 1, result.times()[i + 1], &x) {
+                    break;
+                }
+            }
+            Ok(())
+        }
+    };
+}
+
+pub(super) use yoshida_6th;
+
+#[cfg(test)]
+super::test_method::test_method!(SymplecticIntegrators::Yoshida6th);
+
+#[test]
+#[allow(clippy::cast_precision_loss)]
+fn test_order() -> anyhow::Result<()> {
+    use crate::{Float, ResultExt, SymplecticIntegrator, SymplecticIntegrators};
+
+    // Implement the trait on a test struct (the same
+    // system as in the harness of the methods)
+    struct Test {}
+    impl<F: Float> SymplecticIntegrator<F> for Test {
+        fn accelerations(&self, t: F, x: &[F]) -> anyhow::Result<Vec<F>> {
+            Ok(vec![t - x[0]])
+        }
+    }
+    let test = Test {};
+
+    // Compute the errors of the position with a coarse time step and a halved one
+    // (big enough for the truncation error to dominate the round-off one)
+    let t = 30.;
+    let error = |n: usize| -> anyhow::Result<f64> {
+        let result = test.integrate(
+            &[1., 0., -1.],
+            0.,
+            t / n as f64,
+            n,
+            SymplecticIntegrators::Yoshida6th,
+        )?;
+        Ok((result.state(n)[0] - (t - f64::sin(t) + f64::cos(t))).abs())
+    };
+    let (error_1, error_2) = (error(100)?, error(200)?);
+
+    // Check that the ratio of the errors is close to `2^6`
+    let ratio = error_1 / error_2;
+    if !(48. ..=80.).contains(&ratio) {
+        return Err(anyhow::anyhow!(
+            "The ratio of the errors doesn't correspond to the 6th order: {ratio} ({error_1} vs {error_2})"
+        ));
+    }
+
+    Ok(())
+}
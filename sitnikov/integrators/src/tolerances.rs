@@ -0,0 +1,173 @@
+//! Provides the [`Tolerances`](crate::Tolerances) struct
+//! and the [`ErrorNorm`](crate::ErrorNorm) enum
+
+use anyhow::anyhow;
+use numeric_literals::replace_float_literals;
+
+use crate::Float;
+
+/// Norm of the error estimate of a step, scaled by the tolerances
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Norm {
+    /// Weighted root mean square norm
+    /// `sqrt(mean((err_i / (atol_i + rtol_i * |x_i|))^2))`
+    #[default]
+    Rms,
+    /// Weighted maximum norm `max(|err_i| / (atol_i + rtol_i * |x_i|))`
+    Max,
+}
+
+/// Absolute and relative tolerances of the adaptive methods, either
+/// per component of the state or the same for all of the components
+///
+/// The error estimate of a step is scaled by the tolerances per component,
+/// so the components of different scales (e.g., positions and the MEGNO
+/// integrands) are weighted independently. By default, the scaled errors
+/// are combined by the root mean square norm (see [`ErrorNorm`](crate::ErrorNorm))
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tolerances<F: Float> {
+    /// Absolute tolerances
+    atol: Vec<F>,
+    /// Relative tolerances
+    rtol: Vec<F>,
+    /// Norm of the scaled errors
+    norm: Norm,
+}
+
+impl<F: Float> Tolerances<F> {
+    /// Create the tolerances per component (each of the vectors
+    /// may also consist of a single value, which is broadcast)
+    ///
+    /// Arguments:
+    /// * `atol` --- Absolute tolerances;
+    /// * `rtol` --- Relative tolerances.
+    #[must_use]
+    pub fn new(atol: &[F], rtol: &[F]) -> Self {
+        Self {
+            atol: atol.to_vec(),
+            rtol: rtol.to_vec(),
+            norm: Norm::default(),
+        }
+    }
+    /// Create the same tolerances for all of the components
+    ///
+    /// Arguments:
+    /// * `atol` --- Absolute tolerance;
+    /// * `rtol` --- Relative tolerance.
+    #[must_use]
+    pub fn scalar(atol: F, rtol: F) -> Self {
+        Self::new(&[atol], &[rtol])
+    }
+    /// Use the passed norm of the scaled errors
+    ///
+    /// Arguments:
+    /// * `norm` --- Norm of the scaled errors.
+    #[must_use]
+    pub fn with_norm(mut self, norm: Norm) -> Self {
+        self.norm = norm;
+        self
+    }
+    /// Get the tolerances of the component
+    fn get(&self, i: usize) -> (F, F) {
+        let get = |tol: &[F]| if tol.len() == 1 { tol[0] } else { tol[i] };
+        (get(&self.atol), get(&self.rtol))
+    }
+    /// Check that the tolerances are valid for a state of the passed length
+    #[replace_float_literals(F::from(literal).unwrap())]
+    pub(crate) fn check(&self, len: usize) -> anyhow::Result<()> {
+        for (tol, name) in [(&self.atol, "absolute"), (&self.rtol, "relative")] {
+            if tol.len() != 1 && tol.len() != len {
+                return Err(anyhow!(
+                    "The number of {name} tolerances ({}) must be either 1 or \
+                    the length of the state ({len})",
+                    tol.len()
+                ));
+            }
+        }
+        if (0..len).any(|i| {
+            let (atol, rtol) = self.get(i);
+            atol < 0. || rtol < 0. || atol + rtol <= 0.
+        }) {
+            return Err(anyhow!(
+                "The tolerances must be non-negative, with at least one of them positive"
+            ));
+        }
+        Ok(())
+    }
+    /// Compute the weighted norm of the error estimate of a step
+    /// (or infinity if the new state isn't finite)
+    ///
+    /// Arguments:
+    /// * `x` --- Current state of the system;
+    /// * `x_new` --- New state of the system;
+    /// * `delta` --- Error estimate of the new state.
+    #[replace_float_literals(F::from(literal).unwrap())]
+    pub(crate) fn norm(&self, x: &[F], x_new: &[F], delta: &[F]) -> F {
+        if !x_new.iter().all(|x| x.is_finite()) {
+            return F::infinity();
+        }
+        let scaled = (0..x.len()).map(|i| {
+            let (atol, rtol) = self.get(i);
+            delta[i].abs() / (atol + rtol * x[i].abs().max(x_new[i].abs()))
+        });
+        match self.norm {
+            Norm::Rms => {
+                let n = F::from(x.len()).unwrap();
+                (scaled.fold(0., |acc, e| acc + e * e) / n).sqrt()
+            }
+            Norm::Max => scaled.fold(0., F::max),
+        }
+    }
+}
+
+impl<F: Float> From<(F, F)> for Tolerances<F> {
+    fn from((atol, rtol): (F, F)) -> Self {
+        Self::scalar(atol, rtol)
+    }
+}
+
+#[test]
+fn test() -> anyhow::Result<()> {
+    // Check the norms with the broadcast tolerances
+    let (x, x_new, delta) = ([1_f64, -2.], [1., -4.], [1e-6, -2e-6]);
+    let tol = Tolerances::scalar(0., 1e-6);
+    if (tol.norm(&x, &x_new, &delta) - 0.625_f64.sqrt()).abs() >= 1e-12 {
+        return Err(anyhow::anyhow!("The RMS norm is incorrect"));
+    }
+    if (tol.clone().with_norm(Norm::Max).norm(&x, &x_new, &delta) - 1.).abs() >= 1e-12 {
+        return Err(anyhow::anyhow!("The maximum norm is incorrect"));
+    }
+    if Tolerances::from((0., 1e-6)) != tol {
+        return Err(anyhow::anyhow!("The conversion from a tuple is incorrect"));
+    }
+
+    // Check the norms with the per-component tolerances
+    let tol = Tolerances::new(&[1e-6, 1e-3], &[0.]);
+    if (tol.norm(&x, &x_new, &delta) - (0.5_f64 + 2e-6).sqrt()).abs() >= 1e-12 {
+        return Err(anyhow::anyhow!("The per-component RMS norm is incorrect"));
+    }
+    if tol.norm(&x, &[1., f64::NAN], &delta).is_finite() {
+        return Err(anyhow::anyhow!("The norm of a non-finite state is finite"));
+    }
+
+    // Check that the norms give different decisions for a step with
+    // one of the components slightly over the tolerance (accepted
+    // by the RMS norm, but rejected by the maximum norm)
+    let delta = [1.2e-6, 0.];
+    if tol.norm(&x, &x_new, &delta) > 1.
+        || tol.clone().with_norm(Norm::Max).norm(&x, &x_new, &delta) <= 1.
+    {
+        return Err(anyhow::anyhow!("The norms don't differ in the decisions"));
+    }
+
+    // Check the validation
+    if tol.check(2).is_err()
+        || tol.check(3).is_ok()
+        || Tolerances::new(&[0., 1e-6], &[0.]).check(2).is_ok()
+        || Tolerances::scalar(-1e-6, 1e-6).check(2).is_ok()
+    {
+        return Err(anyhow::anyhow!("The validation is incorrect"));
+    }
+
+    Ok(())
+}
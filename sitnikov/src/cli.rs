@@ -1,18 +1,20 @@
 //! This module defines the command-line interface of the program
 
 use anyhow::Result;
-use clap::Parser;
+use clap::{CommandFactory, ErrorKind, Parser};
 use numeric_literals::replace_float_literals;
 use paste::paste;
 
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
+use crate::model::{MegnoDefinition, Method, OutputFormat};
 use crate::Float;
 
 /// Command-line interface arguments
 #[derive(Parser)]
 #[clap(author, version, about)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Args<F: 'static + Float> {
     /// Output directory
     #[clap(short, long, validator = Self::validate_output)]
@@ -20,7 +22,66 @@ pub struct Args<F: 'static + Float> {
     /// Compute MEGNOs?
     #[clap(long = "megno")]
     pub compute_megnos: bool,
-    /// Eccentricity
+    /// Definition of MEGNOs
+    #[clap(long = "megno-definition", arg_enum, default_value = "hinse")]
+    pub megno_definition: MegnoDefinition,
+    /// Tolerance of the error of the reference trajectory (if exceeded,
+    /// MEGNOs are recomputed with the 6th-order Yoshida method)
+    #[clap(long = "megno-tolerance", validator = Self::validate_megno_tolerance)]
+    pub megno_tolerance: Option<F>,
+    /// Average MEGNOs computed forward and backward in time?
+    #[clap(long = "symmetric-megno")]
+    pub symmetric_megno: bool,
+    /// Compute the Poincaré section (the position and the velocity
+    /// at the moments the primaries pass the pericenter)?
+    #[clap(long = "poincare")]
+    pub compute_poincare: bool,
+    /// Estimate the maximal Lyapunov exponent (by evolving a tangent vector
+    /// along the trajectory and renormalizing it periodically)?
+    #[clap(long = "lyapunov")]
+    pub compute_lyapunov: bool,
+    /// Number of iterations between the renormalizations of the
+    /// tangent vector (when estimating the Lyapunov exponent)
+    #[clap(
+        long = "lyapunov-interval",
+        default_value_t = 10,
+        validator = Self::validate_lyapunov_interval
+    )]
+    pub lyapunov_interval: usize,
+    /// Also integrate the equations of motion in the fictitious time (by the
+    /// Sundman transformation, so the time steps shrink near the close
+    /// approaches of the third body to the primaries)?
+    #[clap(long = "regularized")]
+    pub compute_regularized: bool,
+    /// Standard deviation of the random displacement of the second
+    /// trajectory (used when computing MEGNOs) [default: 1e-1]
+    #[clap(
+        long = "megno-variation",
+        conflicts_with = "fixed-variation",
+        validator = Self::validate_megno_variation
+    )]
+    pub megno_variation: Option<F>,
+    /// Displace the second trajectory by this magnitude along
+    /// the position axis instead of a random displacement
+    #[clap(long = "fixed-variation", validator = Self::validate_fixed_variation)]
+    pub fixed_variation: Option<F>,
+    /// Seed of the random number generator (used when computing MEGNOs)
+    #[clap(long, default_value_t = 1)]
+    pub seed: u64,
+    /// Format of the output files
+    #[clap(long = "format", arg_enum, default_value = "bin")]
+    pub output_format: OutputFormat,
+    /// Write the results in single precision?
+    #[clap(long = "output-f32")]
+    pub output_f32: bool,
+    /// Create the output directory (and its parents) if it's missing?
+    #[clap(long = "create-dirs")]
+    pub create_dirs: bool,
+    /// Write the energy of the third body along the trajectory?
+    #[clap(long = "energy")]
+    pub output_energy: bool,
+    /// Eccentricity (the orbit of the primaries is unbound if it's greater
+    /// than 1; the parabolic case is not supported)
     #[clap(short, help_heading = "MODEL", default_value = "0.0", validator = Self::validate_e)]
     pub e: F,
     /// Time at the pericenter (a fraction of $ 2 \pi $)
@@ -35,9 +96,27 @@ pub struct Args<F: 'static + Float> {
     /// Time step (multiple of $ \pi / 2 $)
     #[clap(short, help_heading = "INTEGRATION", default_value = "1e-2", validator = Self::validate_h)]
     pub h: F,
-    /// Number of periods (multiple of $ 2 \pi $)
+    /// Number of periods (multiple of $ 2 \pi $; there is no period
+    /// if the orbit is unbound, so it's just the unit of time then)
     #[clap(short = 'P', help_heading = "INTEGRATION", default_value_t = 1000, validator = Self::validate_p)]
     pub p: usize,
+    /// Integration method of the equations of motion (only the symplectic
+    /// ones are valid when computing MEGNOs; the MEGNO equations are always
+    /// integrated by the 4th-order Runge-Kutta method)
+    #[clap(
+        long,
+        arg_enum,
+        help_heading = "INTEGRATION",
+        default_value = "yoshida4"
+    )]
+    pub method: Method,
+    /// Print the first states of the trajectory after the integration
+    /// (and the method used for MEGNOs, if they're computed)?
+    #[clap(long)]
+    pub verbose: bool,
+    /// Record the wall-clock time of each block of this many iterations
+    #[clap(long = "timings", help_heading = "INTEGRATION", validator = Self::validate_timing_block)]
+    pub timing_block: Option<usize>,
 }
 
 /// Create a validator for an argument
@@ -68,10 +147,21 @@ macro_rules! validator {
 impl<F: 'static + Float> Args<F> {
     /// Check if the output directory is a valid path
     fn validate_output(s: &str) -> Result<(), String> {
-        if Path::new(s).is_dir() {
+        let path = Path::new(s);
+        if path.is_dir() || !path.exists() {
             Ok(())
         } else {
-            Err("output must be an existing directory".to_string())
+            Err("output must be a directory".to_string())
+        }
+    }
+
+    /// Check if the output directory exists (unless it should be created)
+    fn validate_output_exists(&self) -> Result<(), String> {
+        if self.create_dirs || self.output.is_dir() {
+            Ok(())
+        } else {
+            Err("output must be an existing directory; ".to_string()
+                + "pass `--create-dirs` to create it")
         }
     }
 
@@ -96,7 +186,23 @@ impl<F: 'static + Float> Args<F> {
             })
     }
 
-    validator!(e, F, 0.0..1.0, "eccentricity");
+    /// Check if the eccentricity is valid (the parabolic case is excluded,
+    /// since the radius vanishes at the pericenter on such an orbit when
+    /// the absolute value of the semi-major axis is the unit of length)
+    fn validate_e(s: &str) -> Result<(), String> {
+        let range = 0.0..=F::max_value();
+        F::from_str(s)
+            .map_err(|_| "Couldn't parse the argument `eccentricity`".to_string())
+            .and_then(|e| {
+                if !range.contains(&e) {
+                    Err(format!("eccentricity is not in the range `{range:?}`"))
+                } else if e == 1. {
+                    Err("the parabolic case (`e = 1`) is not supported".to_string())
+                } else {
+                    Ok(())
+                }
+            })
+    }
     validator!(tau, F, 0.0..1.0, "time at the pericenter");
     validator!(
         z_0,
@@ -110,10 +216,111 @@ impl<F: 'static + Float> Args<F> {
         -F::max_value()..=F::max_value(),
         "initial value of velocity of the third body"
     );
+    /// Check if the number of iterations to skip before computing
+    /// MEGNOs is smaller than the total number of iterations
+    fn validate_i_m(&self) -> Result<(), String> {
+        let n = (F::from(self.p).unwrap() * 4. / self.h).round();
+        let i_m = (1. / self.h).round();
+        if !self.compute_megnos || i_m < n {
+            Ok(())
+        } else {
+            Err(
+                "the number of iterations to skip before computing MEGNOs ".to_string()
+                    + "must be smaller than the total number of iterations; "
+                    + "increase the number of periods",
+            )
+        }
+    }
+
+    /// Check if the integration method is valid when computing MEGNOs
+    fn validate_method(&self) -> Result<(), String> {
+        if self.compute_megnos && self.method == Method::Rk4 {
+            Err(
+                "the equations of motion must be integrated by a symplectic ".to_string()
+                    + "method when computing MEGNOs; choose `leapfrog`, `yoshida4` or `yoshida6`",
+            )
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Check if the Poincaré section is not requested together with MEGNOs
+    fn validate_poincare(&self) -> Result<(), String> {
+        if self.compute_poincare && self.compute_megnos {
+            Err("the Poincaré section can't be computed together with MEGNOs".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Check if the options which rely on the period of the primaries
+    /// are not requested when their orbit is unbound
+    fn validate_unbound(&self) -> Result<(), String> {
+        if self.e <= 1. {
+            Ok(())
+        } else if self.compute_poincare {
+            Err(
+                "the Poincaré section can't be computed if the orbit of the ".to_string()
+                    + "primaries is unbound (they pass the pericenter only once)",
+            )
+        } else if self.compute_megnos {
+            Err(
+                "MEGNOs can't be computed if the orbit of the primaries is unbound ".to_string()
+                    + "(the iterations skipped before computing them are "
+                    + "a quarter of the period)",
+            )
+        } else {
+            Ok(())
+        }
+    }
+
     validator!(p, usize, 1..=usize::MAX, "number of periods");
+    validator!(
+        megno_tolerance,
+        F,
+        F::min_positive_value()..=F::max_value(),
+        "tolerance of the error of the reference trajectory"
+    );
+    validator!(
+        lyapunov_interval,
+        usize,
+        1..=usize::MAX,
+        "number of iterations between the renormalizations"
+    );
+    validator!(
+        megno_variation,
+        F,
+        F::min_positive_value()..=F::max_value(),
+        "standard deviation of the random variation"
+    );
+    validator!(
+        fixed_variation,
+        F,
+        F::min_positive_value()..=F::max_value(),
+        "magnitude of the fixed variation"
+    );
+    validator!(
+        timing_block,
+        usize,
+        1..=usize::MAX,
+        "number of iterations in a timed block"
+    );
 }
 
 /// Parse the arguments
 pub fn parse<F: Float>() -> Args<F> {
-    Args::parse()
+    let args = Args::parse();
+    // Check the arguments that depend on each other
+    if let Err(e) = args
+        .validate_i_m()
+        .and(args.validate_output_exists())
+        .and(args.validate_method())
+        .and(args.validate_poincare())
+        .and(args.validate_unbound())
+    {
+        Args::<F>::command()
+            .error(ErrorKind::ValueValidation, e)
+            .exit();
+    }
+    args
 }
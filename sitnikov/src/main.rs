@@ -45,6 +45,10 @@ fn main() -> Result<()> {
     model
         .integrate()
         .with_context(|| "Couldn't integrate the model")?;
+    // Print the first states if that's requested
+    if args.verbose {
+        println!("{}", model.display(10));
+    }
     // Write the results
     model
         .write(&args.output)
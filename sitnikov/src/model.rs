@@ -3,15 +3,20 @@
 mod comp;
 mod io;
 
+use clap::ArgEnum;
 use integrators::ResultExt;
 
-#[cfg(test)]
+#[cfg(feature = "profiling")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
 use numeric_literals::replace_float_literals;
 
 use crate::Float;
 
 /// A model of the Sitnikov problem
 #[derive(Clone)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Model<F: Float> {
     /// Eccentricity
     e: F,
@@ -25,12 +30,48 @@ pub struct Model<F: Float> {
     h: F,
     /// Number of iterations
     n: usize,
+    /// Integration method of the equations of motion
+    method: Method,
     /// An index of the first value for MEGNOs
     i_m: usize,
     /// Compute MEGNOs?
     compute_megnos: bool,
+    /// Definition of MEGNOs
+    megno_definition: MegnoDefinition,
+    /// Tolerance of the error of the reference trajectory
+    /// (if MEGNOs should be recomputed with the 6th-order
+    /// Yoshida method when it's exceeded)
+    megno_tolerance: Option<F>,
+    /// Average MEGNOs computed forward and backward in time?
+    symmetric_megno: bool,
+    /// Compute the Poincaré section?
+    compute_poincare: bool,
+    /// Estimate the maximal Lyapunov exponent?
+    compute_lyapunov: bool,
+    /// Number of iterations between the renormalizations
+    /// of the tangent vector (when estimating the exponent)
+    lyapunov_interval: usize,
+    /// Integrate the equations of motion in the fictitious time?
+    compute_regularized: bool,
+    /// Method of displacing the second trajectory when computing MEGNOs
+    variation: VariationMode<F>,
+    /// Seed of the random number generator
+    seed: u64,
+    /// Format of the output files
+    output_format: OutputFormat,
+    /// Write the results in single precision?
+    output_f32: bool,
+    /// Create the output directory (and its parents) if it's missing?
+    create_dirs: bool,
+    /// Write the energy of the third body along the trajectory?
+    output_energy: bool,
+    /// Number of iterations in a timed block (if timing is enabled)
+    timing_block: Option<usize>,
     /// Results of the integration
     results: Results<F>,
+    /// Number of calls of the acceleration function
+    #[cfg(feature = "profiling")]
+    acceleration_calls: Counter,
 }
 
 #[cfg(test)]
@@ -46,13 +87,123 @@ impl<F: Float> Model<F> {
             x_0: Vec::new(),
             h: h * F::FRAC_PI_2(),
             n: (1000. * 4. / h).round().to_usize().unwrap(),
+            method: Method::Yoshida4,
             i_m: 0,
             compute_megnos: false,
+            megno_definition: MegnoDefinition::Hinse,
+            megno_tolerance: None,
+            symmetric_megno: false,
+            compute_poincare: false,
+            compute_lyapunov: false,
+            lyapunov_interval: 10,
+            compute_regularized: false,
+            variation: VariationMode::default(),
+            seed: 1,
+            output_format: OutputFormat::Bin,
+            output_f32: false,
+            create_dirs: false,
+            output_energy: false,
+            timing_block: None,
             results: Results::new(),
+            #[cfg(feature = "profiling")]
+            acceleration_calls: Counter::default(),
         }
     }
 }
 
+/// Integration method of the equations of motion
+///
+/// When MEGNOs are computed, the method is used for the iterations before the
+/// MEGNO integration (which are done for both trajectories), and the MEGNO
+/// equations are always integrated by the 4th-order Runge-Kutta method (they
+/// are not Hamiltonian), so only the symplectic methods are valid then
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Method {
+    /// Leapfrog method (2nd-order, symplectic)
+    Leapfrog,
+    /// 4th-order Yoshida method (symplectic)
+    Yoshida4,
+    /// 6th-order Yoshida method (symplectic)
+    Yoshida6,
+    /// 4th-order Runge-Kutta method (not symplectic)
+    Rk4,
+}
+
+/// Definition of MEGNOs
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MegnoDefinition {
+    /// The time elapsed from the start of the MEGNO integration is
+    /// replaced with the time itself (see T. C. Hinse et al., 2010)
+    Hinse,
+    /// The time elapsed from the start of the MEGNO
+    /// integration is used (see P. M. Cincotta, C. Simó, 2000)
+    Original,
+}
+
+/// Format of the output files
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Native-endian `bincode` (a `.bin` file per vector)
+    Bin,
+    /// CSV (a `.csv` file per table, with a header)
+    Csv,
+    /// JSON (a `.json` file per table, with an array of records)
+    Json,
+}
+
+/// Method of displacing the second trajectory when computing MEGNOs
+#[derive(Clone, Copy, Debug)]
+pub enum VariationMode<F: Float> {
+    /// Sample the position and the velocity from normal
+    /// distributions with the means in the original values
+    Random {
+        /// Standard deviation
+        sd: F,
+    },
+    /// Displace the position and the velocity along the fixed direction
+    Fixed {
+        /// Direction of the displacement (normalized before use)
+        direction: (F, F),
+        /// Magnitude of the displacement
+        magnitude: F,
+    },
+}
+
+#[replace_float_literals(F::from(literal).unwrap())]
+impl<F: Float> Default for VariationMode<F> {
+    fn default() -> Self {
+        VariationMode::Random { sd: 1e-1 }
+    }
+}
+
+/// A counter which can be incremented through a shared reference
+#[cfg(feature = "profiling")]
+#[derive(Default)]
+struct Counter(AtomicUsize);
+
+#[cfg(feature = "profiling")]
+impl Clone for Counter {
+    fn clone(&self) -> Self {
+        Self(AtomicUsize::new(self.get()))
+    }
+}
+
+#[cfg(feature = "profiling")]
+impl Counter {
+    /// Increment the counter
+    fn increment(&self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+    /// Get the current value of the counter
+    fn get(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+    /// Reset the counter to zero
+    fn reset(&self) {
+        self.0.store(0, Ordering::Relaxed);
+    }
+}
+
 /// Results of integration
 #[derive(Clone)]
 struct Results<F: Float> {
@@ -60,6 +211,17 @@ struct Results<F: Float> {
     x: integrators::Result<F>,
     /// The integrated trajectories, MEGNOs and mean MEGNOs
     m: integrators::Result<F>,
+    /// The trajectory integrated in the fictitious time
+    /// (time, position and velocity)
+    s: integrators::Result<F>,
+    /// Points of the Poincaré section (position and velocity)
+    poincare: Vec<(F, F)>,
+    /// Finite-time estimates of the maximal Lyapunov exponent
+    lyapunov: Vec<F>,
+    /// Wall-clock durations of the timed blocks of iterations
+    timings: Vec<Duration>,
+    /// Were MEGNOs computed with the higher-order method?
+    higher_order: bool,
 }
 
 impl<F: Float> Results<F> {
@@ -68,6 +230,11 @@ impl<F: Float> Results<F> {
         Self {
             x: integrators::Result::<F>::new(0, 0),
             m: integrators::Result::<F>::new(0, 0),
+            s: integrators::Result::<F>::new(0, 0),
+            poincare: Vec::new(),
+            lyapunov: Vec::new(),
+            timings: Vec::new(),
+            higher_order: false,
         }
     }
 }
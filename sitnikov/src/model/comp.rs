@@ -1,7 +1,19 @@
 //! Provides computation methods
 
 mod acceleration;
+mod clone_with_initial_conditions;
+mod detect_period;
 mod eccentric_anomaly;
+mod energy;
+mod escape_time;
 mod integrate;
+mod integrate_backward;
+mod integrate_higher_order;
+mod integrate_regularized;
+mod lyapunov;
 mod newton_raphson;
+mod poincare_iter;
+mod poincare_section;
 mod radius;
+mod symmetric_megno;
+mod twist;
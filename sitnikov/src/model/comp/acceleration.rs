@@ -1,4 +1,6 @@
-//! Provides the [`acceleration`](Model#method.acceleration) method
+//! Provides the [`acceleration`](Model#method.acceleration) method, plus
+//! the methods for profiling the number of its calls (these are available
+//! with the `profiling` feature, so the calls aren't counted otherwise)
 
 use anyhow::{Context, Result};
 use numeric_literals::replace_float_literals;
@@ -10,11 +12,28 @@ impl<F: Float> Model<F> {
     /// Compute the acceleration (second derivative)
     #[replace_float_literals(F::from(literal).unwrap())]
     pub(in super::super) fn acceleration(&self, t: F, z: F) -> Result<F> {
+        #[cfg(feature = "profiling")]
+        self.acceleration_calls.increment();
         let r = self
             .radius(t)
             .with_context(|| "Couldn't compute the radius")?;
         Ok(-z / (r.powi(2) + z.powi(2)).powf(1.5))
     }
+
+    /// Get the number of calls of the [`acceleration`](Model#method.acceleration)
+    /// method since the model was created or the counter was reset
+    #[cfg(feature = "profiling")]
+    #[allow(dead_code)]
+    pub fn acceleration_call_count(&self) -> usize {
+        self.acceleration_calls.get()
+    }
+
+    /// Reset the counter of calls of the [`acceleration`](Model#method.acceleration) method
+    #[cfg(feature = "profiling")]
+    #[allow(dead_code)]
+    pub fn reset_acceleration_call_count(&self) {
+        self.acceleration_calls.reset();
+    }
 }
 
 #[test]
@@ -37,3 +56,40 @@ fn test_acceleration() -> Result<()> {
     }
     Ok(())
 }
+
+#[test]
+#[cfg(feature = "profiling")]
+fn test_acceleration_call_count() -> Result<()> {
+    use anyhow::anyhow;
+
+    // Initialize a test model
+    let mut model = Model::<f64>::test();
+    model.e = 0.6;
+    model.n = 100;
+
+    // Compute the initial acceleration
+    let z_0 = 1.;
+    let a_0 = model.acceleration(model.t_0, z_0)?;
+    model.x_0 = vec![z_0, 0., a_0];
+
+    // Integrate the model (the 4th-order Yoshida method
+    // computes the accelerations thrice per iteration)
+    model.integrate()?;
+
+    // Compare to the expected number of calls
+    let count = model.acceleration_call_count();
+    let expected = 3 * model.n + 1;
+    if count != expected {
+        return Err(anyhow!(
+            "The number of calls of the acceleration function is incorrect: {expected} vs. {count}"
+        ));
+    }
+
+    // Reset the counter
+    model.reset_acceleration_call_count();
+    if model.acceleration_call_count() != 0 {
+        return Err(anyhow!("The counter wasn't reset"));
+    }
+
+    Ok(())
+}
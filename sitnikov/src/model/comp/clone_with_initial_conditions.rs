@@ -0,0 +1,62 @@
+//! Provides the [`clone_with_initial_conditions`](Model#method.clone_with_initial_conditions) method
+
+use anyhow::{Context, Result};
+
+use super::super::Model;
+use crate::Float;
+
+impl<F: Float> Model<F> {
+    /// Clone the model with other initial values of position
+    /// and velocity, recomputing the initial acceleration
+    ///
+    /// Arguments:
+    /// * `z_0` --- Initial value of position;
+    /// * `z_v_0` --- Initial value of velocity.
+    #[allow(dead_code)]
+    pub fn clone_with_initial_conditions(&self, z_0: F, z_v_0: F) -> Result<Self> {
+        let mut model = self.clone();
+        // Compute the initial acceleration
+        let a_0 = model
+            .acceleration(model.t_0, z_0)
+            .with_context(|| "Couldn't compute the initial acceleration")?;
+        // Set the vector of initial values
+        model.x_0 = vec![z_0, z_v_0, a_0];
+        Ok(model)
+    }
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_clone_with_initial_conditions() -> Result<()> {
+    use anyhow::anyhow;
+
+    // Initialize a test model
+    let mut model = Model::<f64>::test();
+    model.e = 0.2;
+    model.t_0 = 1.;
+    let a_0 = model.acceleration(model.t_0, 1.)?;
+    model.x_0 = vec![1., 0., a_0];
+
+    // Clone it with other initial values
+    let clone = model.clone_with_initial_conditions(0.5, 0.1)?;
+
+    // Check the initial values of the clone
+    let x_0 = vec![0.5, 0.1, model.acceleration(model.t_0, 0.5)?];
+    if clone.x_0 != x_0 {
+        return Err(anyhow!(
+            "The initial values of the clone are incorrect: {:?} vs. {:?}",
+            x_0,
+            clone.x_0
+        ));
+    }
+
+    // Check that the original model is unchanged
+    if model.x_0 != vec![1., 0., a_0] {
+        return Err(anyhow!(
+            "The initial values of the original model changed: {:?}",
+            model.x_0
+        ));
+    }
+
+    Ok(())
+}
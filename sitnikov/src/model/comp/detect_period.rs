@@ -0,0 +1,143 @@
+//! Provides the [`detect_period`](Model#method.detect_period) method
+
+use anyhow::{anyhow, Result};
+use integrators::ResultExt;
+use numeric_literals::replace_float_literals;
+
+use super::super::Model;
+use crate::Float;
+
+impl<F: Float> Model<F> {
+    /// Estimate the period of the integrated trajectory from
+    /// the autocorrelation of the position of the third body
+    ///
+    /// The period is the lag of the first secondary peak of the
+    /// autocorrelation function. If this peak is not strong enough
+    /// (or there is no such peak at all), `None` is returned
+    ///
+    /// An error is returned if the trajectory is too short or
+    /// constant (the autocorrelation is undefined then)
+    #[allow(dead_code)]
+    #[replace_float_literals(F::from(literal).unwrap())]
+    pub fn detect_period(&self) -> Result<Option<F>> {
+        // Get the position vector
+        let z = if self.compute_megnos {
+            self.results.m.result(0)
+        } else {
+            self.results.x.result(0)
+        };
+        let l = z.len();
+        if l < 4 {
+            return Err(anyhow!(
+                "The trajectory is too short to compute the autocorrelation: {} states",
+                l
+            ));
+        }
+        // Compute the deviations from the mean
+        let mean = z.iter().sum::<F>() / F::from(l).unwrap();
+        let d: Vec<F> = z.iter().map(|&z| z - mean).collect();
+        // Compute the variance (unnormalized)
+        let var = d.iter().fold(0., |acc, &d| acc + d.powi(2));
+        if var == 0. {
+            return Err(anyhow!(
+                "The position is constant, so the autocorrelation is undefined"
+            ));
+        }
+        // Define the autocorrelation function
+        let r = |k: usize| {
+            d.iter()
+                .zip(d[k..].iter())
+                .fold(0., |acc, (&a, &b)| acc + a * b)
+                / var
+        };
+        // Find the first secondary peak
+        let mut crossed = false;
+        let (mut r_prev, mut r_curr) = (1., r(1));
+        for k in 1..l / 2 {
+            let r_next = r(k + 1);
+            // The central peak ends where the function becomes negative
+            if r_curr < 0. {
+                crossed = true;
+            }
+            // The first local maximum after that is the secondary peak
+            if crossed && r_prev < r_curr && r_curr >= r_next {
+                return Ok(if r_curr > 0.5 {
+                    Some(F::from(k).unwrap() * self.h)
+                } else {
+                    None
+                });
+            }
+            r_prev = r_curr;
+            r_curr = r_next;
+        }
+        Ok(None)
+    }
+}
+
+#[test]
+#[allow(clippy::cast_precision_loss)]
+fn test_detect_period_sinusoid() -> Result<()> {
+    // Initialize a test model
+    let mut model = Model::<f64>::test();
+    model.h = 1e-2;
+
+    // Put a sinusoid in the results
+    let period = 2.345;
+    model.results.x = integrators::Result::<f64>::from_fn(1, 5000, |_, j| {
+        f64::sin(2. * std::f64::consts::PI * j as f64 * model.h / period)
+    });
+
+    // Detect the period
+    let detected = model
+        .detect_period()?
+        .ok_or_else(|| anyhow!("The period wasn't detected"))?;
+
+    // Compare to the known result
+    if (detected - period).abs() > model.h {
+        return Err(anyhow!(
+            "The detected period is incorrect: {period} vs. {detected}"
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_detect_period_white_noise() -> Result<()> {
+    use rand::prelude::*;
+    use rand_distr::StandardNormal;
+    use rand_xoshiro::Xoshiro256PlusPlus;
+
+    // Initialize a test model
+    let mut model = Model::<f64>::test();
+
+    // Put white noise in the results
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(1);
+    model.results.x =
+        integrators::Result::<f64>::from_fn(1, 5000, |_, _| rng.sample(StandardNormal));
+
+    // Try to detect the period
+    if let Some(detected) = model.detect_period()? {
+        return Err(anyhow!("A period was detected in white noise: {detected}"));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_detect_period_degenerate() -> Result<()> {
+    // Initialize a test model
+    let mut model = Model::<f64>::test();
+
+    // Check that an empty and a constant trajectory are rejected
+    model.results.x = integrators::Result::<f64>::new(1, 0);
+    if model.detect_period().is_ok() {
+        return Err(anyhow!("The period of an empty trajectory was computed"));
+    }
+    model.results.x = integrators::Result::<f64>::from_fn(1, 100, |_, _| 1.);
+    if model.detect_period().is_ok() {
+        return Err(anyhow!("The period of a constant trajectory was computed"));
+    }
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 //! Provides the [`eccentric_anomaly`](Model#method.eccentric_anomaly) method
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use numeric_literals::replace_float_literals;
 
 use super::super::Model;
@@ -9,18 +9,41 @@ use crate::Float;
 
 impl<F: Float> Model<F> {
     /// Compute the eccentric anomaly from the eccentricity and the mean anomaly
+    ///
+    /// If the orbit of the primaries is unbound (`e > 1`), the hyperbolic
+    /// anomaly is computed instead (from $ e \sinh H - H = M $). The
+    /// parabolic case (`e = 1`) is not supported
     #[replace_float_literals(F::from(literal).unwrap())]
     pub(super) fn eccentric_anomaly(&self, m: F) -> Result<F> {
-        // Define the non-linear equation
-        let fun = |x| x - self.e * F::sin(x) - m;
-        // Define its derivative
-        let der = |x| 1. - self.e * F::cos(x);
         // Compute the solution
         if self.e == 0. {
             Ok(m)
+        } else if self.e < 1. {
+            // Define the non-linear equation
+            let fun = |x| x - self.e * F::sin(x) - m;
+            // Define its derivative
+            let der = |x| 1. - self.e * F::cos(x);
+            // Define the initial value (on the side of the root)
+            let initial = if self.e <= 0.8 {
+                m
+            } else if m < 0. {
+                -F::PI()
+            } else {
+                F::PI()
+            };
+            // Use the Newton–Raphson method as a root-finding algorithm
+            newton_raphson(fun, der, initial).with_context(|| "Couldn't find the root")
+        } else if self.e == 1. {
+            Err(anyhow!("The parabolic case is not supported"))
         } else {
-            // Define the initial value
-            let initial = if self.e > 0.8 { F::PI() } else { m };
+            // Define the non-linear equation
+            let fun = |x| self.e * F::sinh(x) - x - m;
+            // Define its derivative
+            let der = |x| self.e * F::cosh(x) - 1.;
+            // Define the initial value (see J. M. A. Danby, 1988; the
+            // function is convex on that side of the origin, so the
+            // iterations approach the root monotonically)
+            let initial = F::signum(m) * F::ln(2. * m.abs() / self.e + 1.8);
             // Use the Newton–Raphson method as a root-finding algorithm
             newton_raphson(fun, der, initial).with_context(|| "Couldn't find the root")
         }
@@ -115,3 +138,63 @@ fn test_elliptic_case_big_e() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_elliptic_case_big_e_negative_m() -> Result<()> {
+    use anyhow::anyhow;
+
+    // Initialize a test model
+    let mut model = Model::<f64>::test();
+
+    // Compare the eccentric anomalies to the known results (the mean
+    // anomalies are in `[-π, π]`, as passed from the radius, and the
+    // ones close to zero are the hardest for the Newton–Raphson method)
+    for (e, m, e_a_0) in [
+        (0.9, -0.01, -0.098_564_377_520_977_06),
+        (0.95, -3., -3.068_949_919_222_049),
+        (0.99, 0.001, 0.088_548_596_330_181_96),
+        (0.99, -2.5, -2.816_346_563_655_77),
+    ] {
+        model.e = e;
+        let e_a = model.eccentric_anomaly(m)?;
+        if (e_a - e_a_0).abs() >= 1e-14 {
+            return Err(anyhow!(
+                "The value of the eccentric anomaly is incorrect (e = {e}, M = {m}): {e_a_0} vs. {e_a}"
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_hyperbolic_case() -> Result<()> {
+    use anyhow::anyhow;
+
+    // Initialize a test model
+    let mut model = Model::<f64>::test();
+
+    // Compare the hyperbolic anomalies to the known
+    // results (including a negative mean anomaly)
+    for (e, m, h_a_0) in [
+        (2., 1., 0.814_096_796_302_133_2),
+        (1.5, 10., 2.843_947_202_416_640_3),
+        (2., -1., -0.814_096_796_302_133_2),
+    ] {
+        model.e = e;
+        let h_a = model.eccentric_anomaly(m)?;
+        if (h_a - h_a_0).abs() >= 1e-14 {
+            return Err(anyhow!(
+                "The value of the hyperbolic anomaly is incorrect (e = {e}, M = {m}): {h_a_0} vs. {h_a}"
+            ));
+        }
+    }
+
+    // The parabolic case should be rejected
+    model.e = 1.;
+    if model.eccentric_anomaly(1.).is_ok() {
+        return Err(anyhow!("The parabolic case is not rejected"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+//! Provides the [`energy`](Model#method.energy) and
+//! [`energies`](Model#method.energies) methods
+
+use anyhow::{Context, Result};
+use integrators::ResultExt;
+use numeric_literals::replace_float_literals;
+
+use super::super::Model;
+use crate::Float;
+
+impl<F: Float> Model<F> {
+    /// Compute the energy of the third body (per unit mass; it's
+    /// conserved only in the circular case):
+    ///
+    /// $ E = \dot{z}^2 / 2 - 1 / \sqrt{r^2(t) + z^2} $
+    ///
+    /// Arguments:
+    /// * `t` --- Time moment;
+    /// * `z` --- Position;
+    /// * `z_v` --- Velocity.
+    #[replace_float_literals(F::from(literal).unwrap())]
+    pub(crate) fn energy(&self, t: F, z: F, z_v: F) -> Result<F> {
+        let r = self
+            .radius(t)
+            .with_context(|| "Couldn't compute the radius")?;
+        Ok(z_v.powi(2) / 2. - 1. / (r.powi(2) + z.powi(2)).sqrt())
+    }
+
+    /// Compute the energies of the third body along the integrated
+    /// trajectory (on the same time grid as the positions and the
+    /// velocities in the results)
+    pub(crate) fn energies(&self) -> Result<Vec<F>> {
+        // Get the position and velocity vectors, plus the index
+        // of the iteration the first of their states is at
+        let (z, z_v, i_0) = if self.compute_megnos {
+            (self.results.m.result(0), self.results.m.result(2), self.i_m)
+        } else {
+            (self.results.x.result(0), self.results.x.result(1), 0)
+        };
+        z.iter()
+            .zip(z_v.iter())
+            .enumerate()
+            .map(|(i, (&z, &z_v))| {
+                let t = self.t_0 + F::from(i_0 + i).unwrap() * self.h;
+                self.energy(t, z, z_v)
+                    .with_context(|| format!("Couldn't compute the energy at t = {t}"))
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test_energies() -> Result<()> {
+    use anyhow::anyhow;
+
+    // Initialize a test model in the circular case
+    // (where the energy of the third body is conserved)
+    let mut model = Model::<f64>::test();
+    model.e = 0.;
+    model.n = 10_000;
+    let a_0 = model.acceleration(model.t_0, 1.)?;
+    model.x_0 = vec![1., 0., a_0];
+
+    // Compute the energies along the trajectory
+    model.integrate()?;
+    let energies = model.energies()?;
+    let e_0 = model.energy(model.t_0, 1., 0.)?;
+
+    // Check the length and the conservation
+    if energies.len() != model.results.x.result(0).len() {
+        return Err(anyhow!(
+            "The number of energies doesn't match the number of positions: {} vs. {}",
+            model.results.x.result(0).len(),
+            energies.len()
+        ));
+    }
+    if let Some(e) = energies.iter().find(|&&e| (e - e_0).abs() >= 1e-8) {
+        return Err(anyhow!("The energy is not conserved: {} vs. {}", e_0, e));
+    }
+
+    // Check that the time grid matches when MEGNOs are computed
+    model.e = 0.5;
+    model.i_m = 100;
+    model.compute_megnos = true;
+    model.integrate()?;
+    let energies = model.energies()?;
+    let (t, x) = (model.t_0 + 100. * model.h, model.results.m.state(0));
+    if (energies[0] - model.energy(t, x[0], x[2])?).abs() > 0. {
+        return Err(anyhow!(
+            "The energy at the start of the MEGNO integration is incorrect"
+        ));
+    }
+
+    Ok(())
+}
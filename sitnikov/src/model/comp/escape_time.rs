@@ -0,0 +1,139 @@
+//! Provides the [`escape_time`](Model#method.escape_time) method
+
+use anyhow::{anyhow, Context, Result};
+use integrators::{bisection, hermite, ResultExt, SymplecticIntegrator};
+use numeric_literals::replace_float_literals;
+
+use super::super::Model;
+use crate::Float;
+
+impl<F: Float> Model<F> {
+    /// Integrate the equations of motion until the third body escapes,
+    /// return the time moment of the escape (the first moment the absolute
+    /// value of the position exceeds the cutoff) or `None` if the body
+    /// stays within the cutoff for the whole integration
+    ///
+    /// The equations of motion are integrated by the chosen method (which
+    /// must be a symplectic one), and the integration stops as soon as the
+    /// escape is detected. The moment of the escape is refined between the
+    /// bracketing steps by bisecting the cubic Hermite interpolant of the
+    /// position. The trajectory is not stored, and MEGNOs are not computed
+    /// by this method
+    ///
+    /// Arguments:
+    /// * `z_cutoff` --- Cutoff of the absolute value of the position.
+    #[allow(dead_code)]
+    #[replace_float_literals(F::from(literal).unwrap())]
+    pub fn escape_time(&self, z_cutoff: F) -> Result<Option<F>> {
+        if self.compute_megnos {
+            return Err(anyhow!(
+                "The escape time can't be computed together with MEGNOs"
+            ));
+        }
+        // Check if the body is already out of the cutoff
+        if self.x_0[0].abs() > z_cutoff {
+            return Ok(Some(self.t_0));
+        }
+        // Get the symplectic integrator of the equations of motion
+        let method = self
+            .symplectic_method()
+            .with_context(|| "The escape time can be computed only with a symplectic method")?;
+        // Integrate until the body crosses the cutoff
+        let (result, n) = SymplecticIntegrator::integrate_while(
+            self,
+            &self.x_0,
+            self.t_0,
+            self.h,
+            self.n,
+            method,
+            |_, _, x| x[0].abs() > z_cutoff,
+        )
+        .with_context(|| "Couldn't integrate the equations of motion")?;
+        // Check if the body escaped
+        if n == 0 || result.state(n)[0].abs() <= z_cutoff {
+            return Ok(None);
+        }
+        let (prev, next) = (result.state(n - 1), result.state(n));
+        // Refine the moment of the crossing by bisection
+        let s = bisection(
+            |s| {
+                let state = hermite(
+                    &prev[0..2],
+                    &prev[1..3],
+                    &next[0..2],
+                    &next[1..3],
+                    self.h,
+                    s,
+                );
+                Ok(state[0].abs() - z_cutoff)
+            },
+            0.,
+            1.,
+        )
+        .with_context(|| "Couldn't refine the moment of the escape")?;
+        Ok(Some(result.times()[n - 1] + s * self.h))
+    }
+}
+
+#[test]
+fn test_escape_time() -> Result<()> {
+    use super::super::Method;
+
+    // Initialize a test model in the circular case
+    // (where the energy of the third body is conserved)
+    let mut model = Model::<f64>::test();
+    model.e = 0.;
+    model.h = 1e-2;
+    model.n = 10_000;
+
+    // Check that a bound orbit doesn't escape
+    let bound = model.clone_with_initial_conditions(1., 0.)?;
+    if let Some(t) = bound.escape_time(10.)? {
+        return Err(anyhow!("The bound orbit escaped at t = {}", t));
+    }
+
+    // Compute the escape time of an unbound orbit
+    let (z_v_0, z_cutoff) = (2., 10.);
+    let unbound = model.clone_with_initial_conditions(0., z_v_0)?;
+    let t = unbound
+        .escape_time(z_cutoff)?
+        .ok_or_else(|| anyhow!("The unbound orbit didn't escape"))?;
+
+    // Compute the escape time from the energy integral,
+    // `t = ∫ dz / sqrt(2 (E + 1 / sqrt(1 + z^2)))`,
+    // using Simpson's rule
+    let energy = z_v_0.powi(2) / 2. - 1.;
+    let v = |z: f64| (2. * (energy + 1. / (1. + z.powi(2)).sqrt())).sqrt();
+    let m = 10_000;
+    let dz = z_cutoff / f64::from(m);
+    let t_0 = (0..m)
+        .map(|i| {
+            let z = f64::from(i) * dz;
+            dz / 6. * (1. / v(z) + 4. / v(z + dz / 2.) + 1. / v(z + dz))
+        })
+        .sum::<f64>();
+    if (t - t_0).abs() >= 1e-6 {
+        return Err(anyhow!("The escape time is incorrect: {} vs. {}", t_0, t));
+    }
+
+    // Check that the chosen method is used
+    let mut unbound = unbound;
+    unbound.method = Method::Yoshida6;
+    let t_6 = unbound
+        .escape_time(z_cutoff)?
+        .ok_or_else(|| anyhow!("The unbound orbit didn't escape"))?;
+    #[allow(clippy::float_cmp)]
+    if t_6 == t || (t_6 - t_0).abs() >= 1e-6 {
+        return Err(anyhow!(
+            "The escape time with the 6th-order method is incorrect: {} vs. {}",
+            t_0,
+            t_6
+        ));
+    }
+    unbound.method = Method::Rk4;
+    if unbound.escape_time(z_cutoff).is_ok() {
+        return Err(anyhow!("The non-symplectic method was accepted"));
+    }
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 //! Provides the [`integrate`](Model#method.integrate) method
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use integrators::{
     GeneralIntegrator, GeneralIntegrators, ResultExt, SymplecticIntegrator, SymplecticIntegrators,
 };
@@ -9,18 +9,37 @@ use rand::prelude::*;
 use rand_distr::Normal;
 use rand_xoshiro::Xoshiro256PlusPlus;
 
-use super::super::Model;
+use std::time::{Duration, Instant};
+
+use super::super::{MegnoDefinition, Method, Model, VariationMode};
 use crate::{Float, FloatMax};
 
 /// Get a small variation to the passed value
-fn variate<F: Float>(x: F, rng: &mut impl rand::Rng) -> Result<F> {
+fn variate<F: Float>(x: F, sd: F, rng: &mut impl rand::Rng) -> Result<F> {
     // Construct a normal distribution with the passed value as mean
-    let normal: Normal<FloatMax> = Normal::new(x.to_f64().unwrap(), 1e-1)
-        .with_context(|| "Couldn't construct a normal distribution for {x}")?;
+    let normal: Normal<FloatMax> = Normal::new(x.to_f64().unwrap(), sd.to_f64().unwrap())
+        .with_context(|| format!("Couldn't construct a normal distribution for {x}"))?;
     // Sample a number from this distribution
     Ok(F::from(normal.sample(rng)).unwrap())
 }
 
+/// The equations of motion of the third body as a general system
+/// (the state is the position and the velocity)
+struct Motion<'a, F: Float> {
+    /// Model
+    model: &'a Model<F>,
+}
+
+impl<F: Float> GeneralIntegrator<F> for Motion<'_, F> {
+    fn update(&self, t: F, x: &[F]) -> Result<Vec<F>> {
+        let a = self
+            .model
+            .acceleration(t, x[0])
+            .with_context(|| "Couldn't compute the acceleration")?;
+        Ok(vec![x[1], a])
+    }
+}
+
 impl<F: Float> SymplecticIntegrator<F> for Model<F> {
     // We integrate the equations of motion for one or two trajectories
     fn accelerations(&self, t: F, x: &[F]) -> Result<Vec<F>> {
@@ -42,8 +61,93 @@ impl<F: Float> SymplecticIntegrator<F> for Model<F> {
 
 impl<F: Float> GeneralIntegrator<F> for Model<F> {
     // We integrate the equations of motions and MEGNO equations
-    #[replace_float_literals(F::from(literal).unwrap())]
     fn update(&self, t: F, x: &[F]) -> Result<Vec<F>> {
+        self.megno_equations(t, self.megno_time(t), x)
+    }
+}
+
+impl<F: Float> Model<F> {
+    /// Displace (variate) the position and the velocity
+    /// according to the variation mode
+    ///
+    /// Arguments:
+    /// * `z` --- Position;
+    /// * `z_v` --- Velocity;
+    /// * `rng` --- Random number generator.
+    pub(super) fn displace(&self, z: F, z_v: F, rng: &mut impl rand::Rng) -> Result<(F, F)> {
+        match self.variation {
+            VariationMode::Random { sd } => {
+                let z_tilda =
+                    variate(z, sd, rng).with_context(|| "Couldn't variate the position")?;
+                let z_v_tilda =
+                    variate(z_v, sd, rng).with_context(|| "Couldn't variate the velocity")?;
+                Ok((z_tilda, z_v_tilda))
+            }
+            VariationMode::Fixed {
+                direction: (d_z, d_z_v),
+                magnitude,
+            } => {
+                // Normalize the direction
+                let norm = (d_z.powi(2) + d_z_v.powi(2)).sqrt();
+                if norm.is_zero() || !norm.is_finite() {
+                    return Err(anyhow!(
+                        "The direction of the displacement can't be normalized: {:?}",
+                        (d_z, d_z_v)
+                    ));
+                }
+                Ok((z + magnitude * d_z / norm, z_v + magnitude * d_z_v / norm))
+            }
+        }
+    }
+
+    /// Get the symplectic integrator of the equations of motion
+    pub(super) fn symplectic_method(&self) -> Result<SymplecticIntegrators> {
+        match self.method {
+            Method::Leapfrog => Ok(SymplecticIntegrators::Leapfrog),
+            Method::Yoshida4 => Ok(SymplecticIntegrators::Yoshida4th),
+            Method::Yoshida6 => Ok(SymplecticIntegrators::Yoshida6th),
+            Method::Rk4 => Err(anyhow!("The method {:?} is not symplectic", self.method)),
+        }
+    }
+
+    /// Integrate the equations of motion of the third body using the chosen
+    /// method (the state doesn't include the acceleration in the general case,
+    /// so it's dropped from the vector of initial values if it's there)
+    ///
+    /// Arguments:
+    /// * `x` --- Vector of initial values;
+    /// * `t_0` --- Initial value of time;
+    /// * `n` --- Number of iterations.
+    pub(super) fn integrate_motion(
+        &self,
+        x: &[F],
+        t_0: F,
+        n: usize,
+    ) -> Result<integrators::Solution<F>> {
+        if self.method == Method::Rk4 {
+            GeneralIntegrator::integrate(
+                &Motion { model: self },
+                &x[..2],
+                t_0,
+                self.h,
+                n,
+                GeneralIntegrators::RungeKutta4th,
+            )
+        } else {
+            let method = self.symplectic_method()?;
+            SymplecticIntegrator::integrate(self, x, t_0, self.h, n, method)
+        }
+    }
+
+    /// Compute the right-hand side of the equations of motions
+    /// of both trajectories and the MEGNO equations
+    ///
+    /// Arguments:
+    /// * `t` --- Time moment;
+    /// * `t_m` --- Time used in the MEGNO equations;
+    /// * `x` --- State of the system.
+    #[replace_float_literals(F::from(literal).unwrap())]
+    pub(super) fn megno_equations(&self, t: F, t_m: F, x: &[F]) -> Result<Vec<F>> {
         // Compute the accelerations
         let a_1 = self
             .acceleration(t, x[0])
@@ -71,52 +175,148 @@ impl<F: Float> GeneralIntegrator<F> for Model<F> {
             // exponential rate of divergence of nearby orbits". However, there
             // is a singular point at `t - t_0 = 0`. Since the properties at
             // t -> +Inf are the same for `t`, substitution of`t_0` is omitted
-            delta_dot_pr / delta_norm_sq * t,
-            2. * x[4] / t,
+            // by default (in the original definition, the limit at the
+            // singular point is used instead)
+            delta_dot_pr / delta_norm_sq * t_m,
+            if t_m.is_zero() { 0. } else { 2. * x[4] / t_m },
         ])
     }
-}
 
-impl<F: Float> Model<F> {
-    /// Integrate the equations of motion and
-    /// (optionally) compute MEGNOs
+    /// Get the time used in the MEGNO equations
+    ///
+    /// Arguments:
+    /// * `t` --- Time moment.
+    fn megno_time(&self, t: F) -> F {
+        match self.megno_definition {
+            MegnoDefinition::Hinse => t,
+            MegnoDefinition::Original => t - (self.t_0 + F::from(self.i_m).unwrap() * self.h),
+        }
+    }
+
+    /// Integrate a system using the passed integration routine, return the
+    /// result and the wall-clock durations of the blocks of iterations
+    ///
+    /// If timing is enabled, the integration is performed in blocks
+    /// of `timing_block` iterations, each one starting from the last
+    /// state of the previous one. Otherwise, it's done in one go,
+    /// and the vector of durations is empty
+    ///
+    /// Arguments:
+    /// * `x` --- Vector of initial values;
+    /// * `t_0` --- Initial value of time;
+    /// * `n` --- Number of iterations;
+    /// * `integrate` --- Integration routine (takes the same arguments).
+    fn integrate_timed(
+        &self,
+        x: &[F],
+        t_0: F,
+        n: usize,
+        integrate: impl Fn(&[F], F, usize) -> Result<integrators::Solution<F>>,
+    ) -> Result<(integrators::Result<F>, Vec<Duration>)> {
+        // If timing is disabled, integrate in one go
+        let block = match self.timing_block {
+            Some(block) => block,
+            None => return Ok((integrate(x, t_0, n)?.into_states(), Vec::new())),
+        };
+        // Prepare a result matrix
+        let mut result = integrators::Result::<F>::new(x.len(), n + 1);
+        result.set_state_from_slice(0, x);
+        let mut timings = Vec::with_capacity((n + block - 1) / block);
+        // Integrate block by block
+        let mut i = 0;
+        while i < n {
+            // Compute the number of iterations in this block
+            let m = usize::min(block, n - i);
+            // Integrate the block
+            let start = Instant::now();
+            let block_result =
+                integrate(&result.state(i), t_0 + F::from(i).unwrap() * self.h, m)
+                    .with_context(|| format!("Couldn't integrate the block starting at {i}"))?;
+            timings.push(start.elapsed());
+            // Put the new states in the result
+            for j in 1..=m {
+                result.set_state(i + j, block_result.state(j));
+            }
+            i += m;
+        }
+        Ok((result, timings))
+    }
+
+    /// Integrate the equations of motion and (optionally) compute MEGNOs
+    /// or the Poincaré section, estimate the Lyapunov exponent and
+    /// integrate the equations of motion in the fictitious time
+    #[allow(clippy::too_many_lines)]
     #[replace_float_literals(F::from(literal).unwrap())]
     pub(crate) fn integrate(&mut self) -> Result<()> {
+        // Reset the timings
+        self.results.timings.clear();
+        self.results.higher_order = false;
+        // Estimate the Lyapunov exponent if that's requested
+        self.results.lyapunov.clear();
+        if self.compute_lyapunov {
+            self.results.lyapunov = self
+                .lyapunov()
+                .with_context(|| "Couldn't estimate the Lyapunov exponent")?;
+        }
+        // Integrate in the fictitious time if that's requested
+        self.results.s = integrators::Result::<F>::new(0, 0);
+        if self.compute_regularized {
+            self.integrate_regularized()
+                .with_context(|| "Couldn't integrate in the fictitious time")?;
+        }
         // If a user wants to compute MEGNOs
         if self.compute_megnos {
+            // If the error of the reference trajectory is too big, compute
+            // MEGNOs with the higher-order method (unless it's already used)
+            if let Some(tolerance) = self.megno_tolerance {
+                if self.method != Method::Yoshida6 {
+                    let error = self.reference_error().with_context(|| {
+                        "Couldn't estimate the error of the reference trajectory"
+                    })?;
+                    if error > tolerance {
+                        return self
+                            .integrate_higher_order()
+                            .with_context(|| "Couldn't integrate with the higher-order method");
+                    }
+                }
+            }
+            // Get the symplectic integrator of the equations of motion
+            let method = self
+                .symplectic_method()
+                .with_context(|| "MEGNOs can be computed only with a symplectic method")?;
             // Prepare a random number generator
-            let mut rng = Xoshiro256PlusPlus::seed_from_u64(1);
+            let mut rng = Xoshiro256PlusPlus::seed_from_u64(self.seed);
             // Variate (displace) the initial values
-            let z_0_tilda = variate(self.x_0[0], &mut rng)
-                .with_context(|| "Couldn't variate the initial value of position")?;
-            let z_v_0_tilda = variate(self.x_0[1], &mut rng)
-                .with_context(|| "Couldn't variate the initial value of velocity")?;
+            let (z_0_tilda, z_v_0_tilda) = self
+                .displace(self.x_0[0], self.x_0[1], &mut rng)
+                .with_context(|| "Couldn't variate the initial values")?;
             // Compute the initial acceleration for the displaced value of position
             let a_0_tilda = self.acceleration(self.t_0, z_0_tilda).with_context(|| {
                 "Couldn't compute the initial acceleration with displaced initial position"
             })?;
             // Integrate the equations of motion
-            // using the 4th-order Yoshida method
+            // using the symplectic method
             // (`i_m` iterations)
             //
             // This is because we'd like to avoid the singular
             // point at `t = 0` when computing MEGNOs later
-            self.results.x = SymplecticIntegrator::integrate(
-                self,
-                &[
-                    self.x_0[0],
-                    z_0_tilda,
-                    self.x_0[1],
-                    z_v_0_tilda,
-                    self.x_0[2],
-                    a_0_tilda,
-                ],
-                self.t_0,
-                self.h,
-                self.i_m,
-                SymplecticIntegrators::Yoshida4th,
-            )
-            .with_context(|| "Couldn't integrate the equations of motion")?;
+            let (x, timings) = self
+                .integrate_timed(
+                    &[
+                        self.x_0[0],
+                        z_0_tilda,
+                        self.x_0[1],
+                        z_v_0_tilda,
+                        self.x_0[2],
+                        a_0_tilda,
+                    ],
+                    self.t_0,
+                    self.i_m,
+                    |x, t_0, n| SymplecticIntegrator::integrate(self, x, t_0, self.h, n, method),
+                )
+                .with_context(|| "Couldn't integrate the equations of motion")?;
+            self.results.x = x;
+            self.results.timings.extend(timings);
             // Get the `i_m`-th state of the system of the equation of motions
             let s = self.results.x.state(self.i_m);
             // Compute the time moment
@@ -126,38 +326,304 @@ impl<F: Float> Model<F> {
             // Compute the integrals in the MEGNO equations
             // using the 4th-order Runge-Kutta method
             // (`n` - `i_m` iterations)
-            self.results.m = GeneralIntegrator::integrate(
-                self,
-                &[s[0], s[1], s[2], s[3], 0., 0.],
-                t_0,
-                self.h,
-                n_m,
-                GeneralIntegrators::RungeKutta4th,
-            )
-            .with_context(|| "Couldn't integrate the MEGNO equations")?;
+            let (m, timings) = self
+                .integrate_timed(&[s[0], s[1], s[2], s[3], 0., 0.], t_0, n_m, |x, t_0, n| {
+                    GeneralIntegrator::integrate(
+                        self,
+                        x,
+                        t_0,
+                        self.h,
+                        n,
+                        GeneralIntegrators::RungeKutta4th,
+                    )
+                })
+                .with_context(|| "Couldn't integrate the MEGNO equations")?;
+            self.results.m = m;
+            self.results.timings.extend(timings);
             // Compute the MEGNOs
             for i in 0..=n_m {
                 // Compute the time moment
-                let t = t_0 + F::from(i + self.i_m).unwrap() * self.h;
+                let t = match self.megno_definition {
+                    MegnoDefinition::Hinse => t_0 + F::from(i + self.i_m).unwrap() * self.h,
+                    MegnoDefinition::Original => F::from(i).unwrap() * self.h,
+                };
+                // Both integrals vanish at the singular point
+                if t.is_zero() {
+                    continue;
+                }
                 // Compute the MEGNO (see the note about `t` above)
                 self.results.m[(4, i)] = 2. * self.results.m[(4, i)] / t;
                 // Compute the mean MEGNO (see the note about `t` above)
                 self.results.m[(5, i)] = self.results.m[(5, i)] / t;
             }
+            // If a user wants symmetric MEGNOs, average
+            // them with the ones computed backward in time
+            if self.symmetric_megno {
+                self.symmetrize_megnos(t_0, n_m)
+                    .with_context(|| "Couldn't compute the symmetric MEGNOs")?;
+            }
             // Otherwise,
         } else {
-            // Integrate the equations of motion
-            // using the 4th-order Yoshida method
-            self.results.x = SymplecticIntegrator::integrate(
-                self,
-                &self.x_0,
-                self.t_0,
-                self.h,
-                self.n,
-                SymplecticIntegrators::Yoshida4th,
-            )
-            .with_context(|| "Couldn't integrate the equations of motion")?;
+            // Integrate the equations of motion using the chosen method (the
+            // state doesn't include the acceleration in the general case)
+            let x_0 = if self.method == Method::Rk4 {
+                &self.x_0[..2]
+            } else {
+                &self.x_0[..]
+            };
+            let (x, timings) = self
+                .integrate_timed(x_0, self.t_0, self.n, |x, t_0, n| {
+                    self.integrate_motion(x, t_0, n)
+                })
+                .with_context(|| "Couldn't integrate the equations of motion")?;
+            self.results.x = x;
+            self.results.timings = timings;
+            // Compute the Poincaré section if that's requested
+            self.results.poincare.clear();
+            if self.compute_poincare {
+                self.results.poincare = self
+                    .poincare_section()
+                    .with_context(|| "Couldn't compute the Poincaré section")?;
+            }
         }
         Ok(())
     }
 }
+
+#[test]
+fn test_timings() -> Result<()> {
+    // Initialize a test model
+    let mut model = Model::<f64>::test();
+    model.e = 0.6;
+    model.n = 1050;
+    let a_0 = model.acceleration(model.t_0, 1.)?;
+    model.x_0 = vec![1., 0., a_0];
+
+    // Integrate without timing
+    Model::integrate(&mut model)?;
+    let x = model.results.x.clone();
+    if !model.results.timings.is_empty() {
+        return Err(anyhow!("The timings were recorded while disabled"));
+    }
+
+    // Integrate with timing
+    model.timing_block = Some(100);
+    Model::integrate(&mut model)?;
+
+    // Check the timings
+    let timings = &model.results.timings;
+    if timings.len() != 11 {
+        return Err(anyhow!(
+            "The number of timed blocks is incorrect: 11 vs. {}",
+            timings.len()
+        ));
+    }
+    if timings.iter().sum::<Duration>().is_zero() {
+        return Err(anyhow!("The total duration is not positive"));
+    }
+
+    // Compare the trajectories
+    if (x - &model.results.x).amax() >= 1e-12 {
+        return Err(anyhow!(
+            "The trajectory integrated in blocks differs from the one integrated in one go"
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_megno_definitions() -> Result<()> {
+    // Initialize a test model with a regular orbit
+    let mut model = Model::<f64>::test();
+    model.e = 0.4;
+    model.i_m = 100;
+    model.compute_megnos = true;
+    let a_0 = model.acceleration(model.t_0, 1.5)?;
+    model.x_0 = vec![1.5, 0., a_0];
+
+    // Compute the mean MEGNOs using both definitions
+    Model::integrate(&mut model)?;
+    let hinse = model.results.m.result(5);
+    model.megno_definition = MegnoDefinition::Original;
+    Model::integrate(&mut model)?;
+    let original = model.results.m.result(5);
+
+    // Check the convergence
+    for (name, m) in [("Hinse", &hinse), ("original", &original)] {
+        let last = m[m.len() - 1];
+        if (last - 2.).abs() >= 0.2 {
+            return Err(anyhow!(
+                "The mean MEGNO ({} definition) doesn't converge to 2: {}",
+                name,
+                last
+            ));
+        }
+    }
+
+    // Check the transients
+    if (hinse[100] - original[100]).abs() < 0.1 {
+        return Err(anyhow!(
+            "The transients are not distinguishable: {} vs. {}",
+            hinse[100],
+            original[100]
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_fixed_variation() -> Result<()> {
+    // Compute the mean MEGNOs of a regular orbit using the passed seed
+    let mean_megnos = |seed: u64| -> Result<Vec<f64>> {
+        // Initialize a test model
+        let mut model = Model::<f64>::test();
+        model.e = 0.2;
+        model.n = 40000;
+        model.i_m = 100;
+        model.compute_megnos = true;
+        model.variation = VariationMode::Fixed {
+            direction: (1., 0.),
+            magnitude: 1e-8,
+        };
+        model.seed = seed;
+        let a_0 = model.acceleration(model.t_0, 0.5)?;
+        model.x_0 = vec![0.5, 0., a_0];
+        // Integrate
+        Model::integrate(&mut model)?;
+        Ok(model.results.m.result(5))
+    };
+
+    // Check that the results don't depend on the seed
+    let m = mean_megnos(1)?;
+    if m != mean_megnos(2)? {
+        return Err(anyhow!("The mean MEGNOs depend on the seed"));
+    }
+
+    // Check the convergence
+    let last = m[m.len() - 1];
+    if (last - 2.).abs() >= 0.1 {
+        return Err(anyhow!("The mean MEGNO doesn't converge to 2: {}", last));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_implicit_midpoint_energy() -> Result<()> {
+    // Initialize a test model in the circular case
+    // (where the energy of the third body is conserved)
+    let mut model = Model::<f64>::test();
+    model.e = 0.;
+    let motion = Motion { model: &model };
+    let energy = |x: &[f64]| x[1].powi(2) / 2. - 1. / (1. + x[0].powi(2)).sqrt();
+
+    // Integrate over many periods with a coarse time step,
+    // compute the maximum deviations of the energy
+    let x_0 = [1., 0.];
+    let e_0 = energy(&x_0);
+    let deviation = |integrator| -> Result<f64> {
+        let result = motion.integrate(&x_0, 0., 0.2, 5000, integrator)?;
+        Ok((0..result.ncols())
+            .map(|i| (energy(&result.state(i)) - e_0).abs())
+            .fold(0., f64::max))
+    };
+    let deviation_midpoint = deviation(GeneralIntegrators::ImplicitMidpoint)?;
+    let deviation_rk2 = deviation(GeneralIntegrators::RungeKutta2nd)?;
+    if deviation_midpoint >= 1e-2 || 10. * deviation_midpoint >= deviation_rk2 {
+        return Err(anyhow!(
+            "The energy is not conserved better by the implicit midpoint rule: {} vs. {}",
+            deviation_midpoint,
+            deviation_rk2
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_stormer_verlet() -> Result<()> {
+    // Initialize a test model
+    let mut model = Model::<f64>::test();
+    model.e = 0.5;
+    let x_0 = [1., 0., model.acceleration(model.t_0, 1.)?];
+    let (h, n) = (1e-2, 10000);
+
+    // Integrate using the Störmer-Verlet and leapfrog methods
+    let integrate =
+        |integrator| SymplecticIntegrator::integrate(&model, &x_0, model.t_0, h, n, integrator);
+    let result = integrate(SymplecticIntegrators::StormerVerlet)?;
+    let result_leapfrog = integrate(SymplecticIntegrators::Leapfrog)?;
+
+    // Compare the positions
+    let (z, z_leapfrog) = (result.result(0), result_leapfrog.result(0));
+    if z.iter()
+        .zip(z_leapfrog.iter())
+        .any(|(&z, &z_leapfrog)| (z - z_leapfrog).abs() >= 1e-8)
+    {
+        return Err(anyhow!(
+            "The positions differ from the ones computed by the leapfrog method"
+        ));
+    }
+
+    // Recover the velocities by central differences and compare them
+    let v_leapfrog = result_leapfrog.result(1);
+    if (1..n).any(|i| ((z[i + 1] - z[i - 1]) / (2. * h) - v_leapfrog[i]).abs() >= 1e-4) {
+        return Err(anyhow!(
+            "The recovered velocities differ from the ones computed by the leapfrog method"
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_methods() -> Result<()> {
+    // Initialize a test model
+    let mut model = Model::<f64>::test();
+    model.e = 0.5;
+    model.n = 4000;
+    let a_0 = model.acceleration(model.t_0, 1.)?;
+    model.x_0 = vec![1., 0., a_0];
+
+    // Integrate using each method, collect the positions and the velocities
+    let mut results = Vec::new();
+    for method in [Method::Yoshida4, Method::Leapfrog, Method::Rk4] {
+        model.method = method;
+        Model::integrate(&mut model)?;
+        results.push((model.results.x.result(0), model.results.x.result(1)));
+    }
+
+    // Compare the trajectories with the one computed by the Yoshida method
+    let (z, z_v) = &results[0];
+    for (method, tolerance, (other_z, other_z_v)) in [
+        (Method::Leapfrog, 1e-2, &results[1]),
+        (Method::Rk4, 1e-5, &results[2]),
+    ] {
+        let deviation = z
+            .iter()
+            .zip(other_z)
+            .chain(z_v.iter().zip(other_z_v))
+            .map(|(a, b)| (a - b).abs())
+            .fold(0., f64::max);
+        if deviation >= tolerance {
+            return Err(anyhow!(
+                "The trajectory computed by the {:?} method deviates too much: {}",
+                method,
+                deviation
+            ));
+        }
+    }
+
+    // Check that the non-symplectic method is rejected when computing MEGNOs
+    model.i_m = 100;
+    model.compute_megnos = true;
+    if Model::integrate(&mut model).is_ok() {
+        return Err(anyhow!(
+            "The non-symplectic method is accepted when computing MEGNOs"
+        ));
+    }
+
+    Ok(())
+}
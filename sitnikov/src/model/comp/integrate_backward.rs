@@ -0,0 +1,88 @@
+//! Provides the [`integrate_backward`](Model#method.integrate_backward) method
+
+use anyhow::{Context, Result};
+use integrators::{SymplecticIntegrator, SymplecticIntegrators};
+
+use super::super::Model;
+use crate::Float;
+
+impl<F: Float> Model<F> {
+    /// Integrate the equations of motion backward in time
+    ///
+    /// The trajectory is stored in the forward-time order, so the last
+    /// state is the one passed, and the first one is the earliest.
+    /// MEGNOs are not computed by this method
+    ///
+    /// Arguments:
+    /// * `from_state` --- State to start from (position, velocity, acceleration);
+    /// * `from_time` --- Time moment to start from;
+    /// * `steps` --- Number of iterations.
+    #[allow(dead_code)]
+    pub fn integrate_backward(
+        &mut self,
+        from_state: &[F],
+        from_time: F,
+        steps: usize,
+    ) -> Result<()> {
+        // Integrate the equations of motion
+        // using the 4th-order Yoshida method
+        // with a negative time step
+        let result = SymplecticIntegrator::integrate(
+            self,
+            from_state,
+            from_time,
+            -self.h,
+            steps,
+            SymplecticIntegrators::Yoshida4th,
+        )
+        .with_context(|| "Couldn't integrate the equations of motion backward")?;
+        // Reverse the order of the states
+        self.results.x = integrators::Result::<F>::from_fn(result.nrows(), steps + 1, |i, j| {
+            result[(i, steps - j)]
+        });
+        Ok(())
+    }
+}
+
+#[test]
+#[allow(clippy::cast_precision_loss)]
+fn test_integrate_backward() -> Result<()> {
+    use anyhow::anyhow;
+    use integrators::ResultExt;
+
+    // Initialize a test model
+    let mut model = Model::<f64>::test();
+    model.e = 0.6;
+    model.n = 1000;
+    let a_0 = model.acceleration(model.t_0, 1.)?;
+    model.x_0 = vec![1., 0., a_0];
+
+    // Integrate forward
+    Model::integrate(&mut model)?;
+    let x = model.results.x.state(model.n);
+    let t = model.t_0 + model.n as f64 * model.h;
+
+    // Integrate backward
+    model.integrate_backward(&x, t, model.n)?;
+
+    // Check the storage order
+    if model.results.x.state(model.n) != x {
+        return Err(anyhow!(
+            "The last state is not the one integration started from"
+        ));
+    }
+
+    // Compare to the initial values
+    let x = model.results.x.state(0);
+    if x.iter()
+        .zip(model.x_0.iter())
+        .any(|(&x, &x_0)| (x - x_0).abs() >= 10. * model.h.powi(4))
+    {
+        return Err(anyhow!(
+            "The integration doesn't return to the initial values: {:?} vs {x:?}",
+            model.x_0
+        ));
+    }
+
+    Ok(())
+}
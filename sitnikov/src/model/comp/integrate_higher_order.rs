@@ -0,0 +1,118 @@
+//! Provides the [`reference_error`](Model#method.reference_error)
+//! and [`integrate_higher_order`](Model#method.integrate_higher_order) methods
+
+use anyhow::{Context, Result};
+use integrators::{SymplecticIntegrator, SymplecticIntegrators};
+
+use super::super::{Method, Model};
+use crate::Float;
+
+impl<F: Float> Model<F> {
+    /// Estimate the error of the reference trajectory by comparing
+    /// the position at the end of the first tenth of the iterations
+    /// with the one computed by the 6th-order Yoshida method
+    pub(super) fn reference_error(&self) -> Result<F> {
+        // Get the number of iterations in the subsample
+        let n = usize::max(self.n / 10, 1);
+        // Prepare the vector of initial values (the accelerations are
+        // computed for two trajectories in this mode, so the reference
+        // trajectory is used twice)
+        let x_0 = [
+            self.x_0[0],
+            self.x_0[0],
+            self.x_0[1],
+            self.x_0[1],
+            self.x_0[2],
+            self.x_0[2],
+        ];
+        // Integrate the equations of motion with the
+        // current method and with the higher-order one
+        let method = self.symplectic_method()?;
+        let result = SymplecticIntegrator::integrate(self, &x_0, self.t_0, self.h, n, method)
+            .with_context(|| "Couldn't integrate with the current method")?;
+        let result_higher_order = SymplecticIntegrator::integrate(
+            self,
+            &x_0,
+            self.t_0,
+            self.h,
+            n,
+            SymplecticIntegrators::Yoshida6th,
+        )
+        .with_context(|| "Couldn't integrate with the higher-order method")?;
+        Ok((result[(0, n)] - result_higher_order[(0, n)]).abs())
+    }
+
+    /// Integrate the equations of motion and compute MEGNOs
+    /// using the 6th-order Yoshida method
+    pub(super) fn integrate_higher_order(&mut self) -> Result<()> {
+        // Prepare a model with the higher-order method
+        let mut model = self.clone();
+        model.method = Method::Yoshida6;
+        model.megno_tolerance = None;
+        model.compute_lyapunov = false;
+        model.compute_regularized = false;
+        // Integrate it
+        Model::integrate(&mut model)?;
+        self.results.x = model.results.x;
+        self.results.m = model.results.m;
+        self.results.timings = model.results.timings;
+        self.results.higher_order = true;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_integrate_higher_order() -> Result<()> {
+    use anyhow::anyhow;
+    use integrators::ResultExt;
+
+    // Integrate the model with the passed initial
+    // position, return the model and the mean MEGNOs
+    let integrate = |e: f64, z_0: f64, tolerance: Option<f64>| -> Result<(Model<f64>, Vec<f64>)> {
+        let mut model = Model::<f64>::test();
+        model.e = e;
+        model.n = 40000;
+        model.i_m = 100;
+        model.compute_megnos = true;
+        model.megno_tolerance = tolerance;
+        let a_0 = model.acceleration(model.t_0, z_0)?;
+        model.x_0 = vec![z_0, 0., a_0];
+        Model::integrate(&mut model)?;
+        let m = model.results.m.result(5);
+        Ok((model, m))
+    };
+
+    // Check that a regular orbit doesn't trigger the fallback
+    let (model, _) = integrate(0.4, 1.5, Some(1e-7))?;
+    if model.results.higher_order {
+        return Err(anyhow!("The regular orbit triggered the fallback"));
+    }
+
+    // Check that a chaotic orbit triggers the fallback
+    let (model, higher_order) = integrate(0.3, 1.2, Some(1e-7))?;
+    if !model.results.higher_order {
+        return Err(anyhow!("The chaotic orbit didn't trigger the fallback"));
+    }
+    if !model
+        .display(1)
+        .starts_with("MEGNOs were computed with the method Yoshida6")
+    {
+        return Err(anyhow!("The fallback isn't reported"));
+    }
+
+    // Compare the final mean MEGNOs computed by the two methods
+    let (_, original) = integrate(0.3, 1.2, None)?;
+    let (last, last_higher_order) = (
+        original[original.len() - 1],
+        higher_order[higher_order.len() - 1],
+    );
+    if (last - last_higher_order).abs() >= 0.05 * last.abs() {
+        return Err(anyhow!(
+            "The mean MEGNOs are inconsistent: {} vs. {}",
+            last,
+            last_higher_order
+        ));
+    }
+
+    Ok(())
+}
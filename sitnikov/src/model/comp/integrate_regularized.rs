@@ -0,0 +1,156 @@
+//! Provides the [`integrate_regularized`](Model#method.integrate_regularized) method
+
+use anyhow::{anyhow, Context, Result};
+use integrators::{GeneralIntegrator, GeneralIntegrators, ResultExt};
+
+use super::super::Model;
+use crate::Float;
+
+/// The equations of motion in the fictitious time `s`, introduced
+/// by the Sundman transformation `dt / ds = r^2 + z^2`
+struct Regularized<'a, F: Float> {
+    /// Model of the Sitnikov problem
+    model: &'a Model<F>,
+}
+
+impl<F: Float> GeneralIntegrator<F> for Regularized<'_, F> {
+    // The state is time, position and velocity
+    fn update(&self, _s: F, x: &[F]) -> Result<Vec<F>> {
+        let (t, z, z_v) = (x[0], x[1], x[2]);
+        // Compute the derivative of time with respect to the fictitious time
+        let radius = self
+            .model
+            .radius(t)
+            .with_context(|| "Couldn't compute the radius")?;
+        let factor = radius.powi(2) + z.powi(2);
+        // Compute the acceleration
+        let acceleration = self
+            .model
+            .acceleration(t, z)
+            .with_context(|| "Couldn't compute the acceleration")?;
+        Ok(vec![factor, factor * z_v, factor * acceleration])
+    }
+}
+
+impl<F: Float> Model<F> {
+    /// Integrate the equations of motion in the fictitious time
+    /// with a constant step (see [`Regularized`]) until the time
+    /// reaches the end of the regular integration interval
+    ///
+    /// The step in the fictitious time is the time step of the model (they
+    /// match at the unit distance from the primaries, where `dt / ds = 1`),
+    /// so the steps in time shrink near the close approaches of the third
+    /// body to the primaries and grow far from them. The last step is
+    /// shortened so the trajectory ends exactly at the final time moment.
+    /// MEGNOs are not computed
+    pub(super) fn integrate_regularized(&mut self) -> Result<()> {
+        if self.h <= F::zero() {
+            return Err(anyhow!("The time step must be positive"));
+        }
+        let ds = self.h;
+        let system = Regularized { model: self };
+        // Compute the final time moment
+        let t_end = self.t_0 + F::from(self.n).unwrap() * self.h;
+        // Make a step in the fictitious time
+        let step = |x: &[F], s: F, ds: F| -> Result<Vec<F>> {
+            Ok(system
+                .integrate(x, s, ds, 1, GeneralIntegrators::RungeKutta4th)
+                .with_context(|| format!("Couldn't make a step at s = {s}"))?
+                .state(1))
+        };
+        // Integrate
+        let mut states = vec![vec![self.t_0, self.x_0[0], self.x_0[1]]];
+        let mut s = F::zero();
+        while states[states.len() - 1][0] < t_end {
+            let x = &states[states.len() - 1];
+            let mut next = step(x, s, ds)?;
+            // If the step goes past the final time moment, shorten it
+            // (the time is monotonic in the fictitious time, so a few
+            // secant iterations are enough)
+            if next[0] > t_end {
+                let mut ds_last = ds;
+                for _ in 0..4 {
+                    ds_last = ds_last * (t_end - x[0]) / (next[0] - x[0]);
+                    next = step(x, s, ds_last)?;
+                }
+                next[0] = t_end;
+            }
+            states.push(next);
+            s = s + ds;
+        }
+        // Save the trajectory
+        let mut result = integrators::Result::<F>::new(3, states.len());
+        for (i, x) in states.into_iter().enumerate() {
+            result.set_state(i, x);
+        }
+        self.results.s = result;
+        Ok(())
+    }
+}
+
+#[test]
+#[allow(clippy::cast_precision_loss)]
+fn test_integrate_regularized() -> Result<()> {
+    use integrators::{SymplecticIntegrator, SymplecticIntegrators};
+
+    // Initialize a test model with a high eccentricity and the third
+    // body crossing the plane of the primaries at their pericenter
+    // (the distance to them is `1 - e = 0.1` at this moment)
+    let mut model = Model::<f64>::test();
+    model.e = 0.9;
+    let (z_0, z_v_0) = (0., 1.);
+    let a_0 = model.acceleration(model.t_0, z_0)?;
+    model.x_0 = vec![z_0, z_v_0, a_0];
+    model.n = 400;
+    model.h = 4. * std::f64::consts::PI / model.n as f64;
+    let t_end = model.t_0 + model.n as f64 * model.h;
+
+    // Integrate the regular equations of motion with the passed
+    // number of steps, return the final position and velocity
+    let integrate = |model: &Model<f64>, n: usize| -> Result<(f64, f64)> {
+        let result = SymplecticIntegrator::integrate(
+            model,
+            &model.x_0,
+            model.t_0,
+            (t_end - model.t_0) / n as f64,
+            n,
+            SymplecticIntegrators::Yoshida4th,
+        )?;
+        Ok((result[(0, n)], result[(1, n)]))
+    };
+
+    // Compute the reference state with a tiny time step
+    let (z_ref, z_v_ref) = integrate(&model, 200_000)?;
+
+    // Integrate the regularized equations
+    model.integrate_regularized()?;
+    let steps = model.results.s.ncols() - 1;
+    let t = model.results.s[(0, steps)];
+    if (t - t_end).abs() >= 1e-12 {
+        return Err(anyhow!(
+            "The regularized integration didn't end at the final time moment: {} vs. {}",
+            t_end,
+            t
+        ));
+    }
+    let error = f64::max(
+        (model.results.s[(1, steps)] - z_ref).abs(),
+        (model.results.s[(2, steps)] - z_v_ref).abs(),
+    );
+
+    // Compare with the regular integration with four times as many steps
+    let (z, z_v) = integrate(&model, 4 * steps)?;
+    let error_regular = f64::max((z - z_ref).abs(), (z_v - z_v_ref).abs());
+    if error >= error_regular {
+        return Err(anyhow!(
+            "The regularized integration ({} steps) is not more accurate \
+            than the regular one ({} steps): {} vs. {}",
+            steps,
+            4 * steps,
+            error,
+            error_regular
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,162 @@
+//! Provides the [`lyapunov`](Model#method.lyapunov) method
+
+use anyhow::{anyhow, Context, Result};
+use integrators::{GeneralIntegrator, GeneralIntegrators, ResultExt};
+use numeric_literals::replace_float_literals;
+
+use super::super::Model;
+use crate::Float;
+
+/// The equations of motion of the third body together with the
+/// variational equations (the state is the position, the velocity
+/// and the components of the tangent vector)
+struct Tangent<'a, F: Float> {
+    /// Model
+    model: &'a Model<F>,
+}
+
+impl<F: Float> GeneralIntegrator<F> for Tangent<'_, F> {
+    #[replace_float_literals(F::from(literal).unwrap())]
+    fn update(&self, t: F, x: &[F]) -> Result<Vec<F>> {
+        let a = self
+            .model
+            .acceleration(t, x[0])
+            .with_context(|| "Couldn't compute the acceleration")?;
+        // Compute the derivative of the acceleration with respect to the position
+        let r = self
+            .model
+            .radius(t)
+            .with_context(|| "Couldn't compute the radius")?;
+        let rho_sq = r.powi(2) + x[0].powi(2);
+        let da = (2. * x[0].powi(2) - r.powi(2)) / rho_sq.powf(2.5);
+        Ok(vec![x[1], a, x[3], da * x[2]])
+    }
+}
+
+impl<F: Float> Model<F> {
+    /// Estimate the maximal Lyapunov exponent of the trajectory
+    /// in each time moment (finite-time exponents):
+    ///
+    /// $ \lambda = \frac{1}{\Delta t} \ln \frac{|\delta(\Delta t)|}{|\delta(0)|} $,
+    ///
+    /// where $ \delta $ is the tangent vector evolved by the variational
+    /// equations along the trajectory (by the 4th-order Runge-Kutta method)
+    /// and $ \Delta t $ is the time elapsed from the initial moment
+    ///
+    /// The tangent vector is rescaled to the unit norm every `lyapunov_interval`
+    /// iterations, and the logarithms of its norms before the rescaling are
+    /// accumulated (see G. Benettin et al., 1980), so it never overflows
+    #[replace_float_literals(F::from(literal).unwrap())]
+    pub(crate) fn lyapunov(&self) -> Result<Vec<F>> {
+        if self.lyapunov_interval == 0 {
+            return Err(anyhow!(
+                "The number of iterations between the renormalizations must be positive"
+            ));
+        }
+        let tangent = Tangent { model: self };
+        // Start with a unit tangent vector off both axes
+        let mut x = vec![
+            self.x_0[0],
+            self.x_0[1],
+            F::FRAC_1_SQRT_2(),
+            F::FRAC_1_SQRT_2(),
+        ];
+        // Prepare the sum of the logarithms of the norms
+        let mut sum = 0.;
+        let mut lyapunov = Vec::with_capacity(self.n + 1);
+        lyapunov.push(0.);
+        // Integrate block by block
+        let mut i = 0;
+        while i < self.n {
+            // Compute the number of iterations in this block
+            let m = usize::min(self.lyapunov_interval, self.n - i);
+            // Integrate the block
+            let result = GeneralIntegrator::integrate(
+                &tangent,
+                &x,
+                self.t_0 + F::from(i).unwrap() * self.h,
+                self.h,
+                m,
+                GeneralIntegrators::RungeKutta4th,
+            )
+            .with_context(|| format!("Couldn't integrate the block starting at {i}"))?;
+            // Compute the exponents in the moments of the block
+            let mut ln_norm = 0.;
+            for j in 1..=m {
+                let s = result.state(j);
+                ln_norm = (s[2].powi(2) + s[3].powi(2)).sqrt().ln();
+                lyapunov.push((sum + ln_norm) / (F::from(i + j).unwrap() * self.h));
+            }
+            // Renormalize the tangent vector
+            x = result.state(m);
+            let norm = ln_norm.exp();
+            if norm.is_zero() || !norm.is_finite() {
+                return Err(anyhow!(
+                    "The tangent vector can't be renormalized at {}: {:?}",
+                    i + m,
+                    (x[2], x[3])
+                ));
+            }
+            x[2] = x[2] / norm;
+            x[3] = x[3] / norm;
+            sum = sum + ln_norm;
+            i += m;
+        }
+        Ok(lyapunov)
+    }
+}
+
+#[test]
+fn test_lyapunov() -> Result<()> {
+    // Initialize a test model with a regular orbit
+    let mut model = Model::<f64>::test();
+    model.e = 0.;
+    model.n = 20_000;
+    let a_0 = model.acceleration(model.t_0, 1.)?;
+    model.x_0 = vec![1., 0., a_0];
+
+    // Estimate the exponents with renormalizations of different frequency
+    model.lyapunov_interval = 1;
+    let every = model.lyapunov()?;
+    model.lyapunov_interval = 10;
+    let regular = model.lyapunov()?;
+    model.lyapunov_interval = model.n;
+    let naive = model.lyapunov()?;
+
+    // The variational equations are linear, so the renormalization
+    // shouldn't change the estimates (up to the rounding errors,
+    // since the time moments of the blocks are computed anew)
+    if every.len() != model.n + 1 || regular.len() != model.n + 1 {
+        return Err(anyhow!(
+            "The number of estimates is incorrect: {} vs. {}",
+            model.n + 1,
+            regular.len()
+        ));
+    }
+    let diff = |a: &[f64], b: &[f64]| {
+        a.iter()
+            .zip(b)
+            .fold(0., |d, (a, b)| f64::max(d, (a - b).abs()))
+    };
+    if diff(&every, &regular) >= 1e-6 || diff(&naive, &regular) >= 1e-6 {
+        return Err(anyhow!(
+            "The estimates depend on the renormalization: {} and {}",
+            diff(&every, &regular),
+            diff(&naive, &regular)
+        ));
+    }
+
+    // The exponent of a regular orbit should tend to zero (the
+    // tangent vector grows linearly, so it decays as `ln(t) / t`)
+    #[allow(clippy::cast_precision_loss)]
+    let t = model.n as f64 * model.h;
+    let last = regular[model.n];
+    if last.abs() >= 2. * t.ln() / t {
+        return Err(anyhow!(
+            "The exponent of a regular orbit doesn't vanish: {}",
+            last
+        ));
+    }
+
+    Ok(())
+}
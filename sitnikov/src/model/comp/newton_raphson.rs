@@ -29,7 +29,8 @@ pub(super) fn newton_raphson<F: Float>(
             // Compute the next point
             let x_2 = x_1 - f / d;
             // Check if the last two points are close enough
-            if (x_1 - x_2).abs() < F::epsilon() * 10. {
+            // (relative to the magnitude of the root)
+            if (x_1 - x_2).abs() < F::epsilon() * (10. + x_2.abs()) {
                 return Ok(x_2);
             }
             // If not, continue
@@ -63,3 +64,38 @@ fn test_find_roots() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_find_large_root() -> Result<()> {
+    use anyhow::Context;
+
+    // Define the Kepler equation with a large mean anomaly (the iterations
+    // end up oscillating between the neighbouring floating-point
+    // numbers, which are further apart than the absolute tolerance)
+    let m = 5e5;
+    let f = |x: f64| x - 0.5 * x.sin() - m;
+    let d = |x: f64| 1. - 0.5 * x.cos();
+
+    // Find the root
+    let x = newton_raphson(f, d, m).with_context(|| "Couldn't find the root")?;
+
+    // Check the residual
+    if f(x).abs() >= f64::EPSILON * 10. * m {
+        return Err(anyhow!("The root is incorrect: the residual is {}", f(x)));
+    }
+
+    // Check that the same iterations don't converge
+    // with the absolute criterion on this input
+    let mut x_1 = m;
+    for _ in 0..MAX_ITER {
+        let x_2 = x_1 - f(x_1) / d(x_1);
+        if (x_1 - x_2).abs() < f64::EPSILON * 10. {
+            return Err(anyhow!(
+                "The iterations converged with the absolute criterion"
+            ));
+        }
+        x_1 = x_2;
+    }
+
+    Ok(())
+}
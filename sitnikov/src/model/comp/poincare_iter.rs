@@ -0,0 +1,106 @@
+//! Provides the [`poincare_iter`](Model#method.poincare_iter) method
+
+use anyhow::{anyhow, Context, Result};
+use integrators::ResultExt;
+use numeric_literals::replace_float_literals;
+
+use super::super::Model;
+use crate::Float;
+
+impl<F: Float> Model<F> {
+    /// Integrate the equations of motion and lazily yield the points of the
+    /// Poincaré map (the position and the velocity of the third body at the
+    /// moments the primaries pass the pericenter)
+    ///
+    /// The trajectory is not stored: as the iterator is advanced, the system
+    /// is integrated by the chosen method only up to the step containing the
+    /// next crossing, so at most a period of it is held in memory. The points
+    /// are interpolated the same way as in [`poincare_section`](Model#method.poincare_section),
+    /// so they match the section of the integrated trajectory. MEGNOs are
+    /// not computed by this method
+    #[allow(dead_code)]
+    #[replace_float_literals(F::from(literal).unwrap())]
+    pub fn poincare_iter(&mut self) -> impl Iterator<Item = Result<(F, F)>> + '_ {
+        let mut crossing = self.first_crossing();
+        // Prepare the states at the ends of the last step
+        let mut prev = self.x_0.clone();
+        let mut x = self.x_0.clone();
+        let mut i = 0;
+        let mut failed = false;
+        std::iter::from_fn(move || {
+            if failed {
+                return None;
+            }
+            if self.compute_megnos {
+                failed = true;
+                return Some(Err(anyhow!(
+                    "The Poincaré map can't be computed together with MEGNOs"
+                )));
+            }
+            // Find the step during which the primaries pass the pericenter
+            let step = self.crossing_step(crossing, self.n)?;
+            // Integrate up to the end of this step (unless it's already
+            // done, which happens if the time step exceeds the period)
+            if step >= i {
+                let t = self.t_0 + F::from(i).unwrap() * self.h;
+                let result = match self.integrate_motion(&x, t, step + 1 - i) {
+                    Ok(result) => result,
+                    Err(e) => {
+                        failed = true;
+                        return Some(Err(e).with_context(|| {
+                            format!("Couldn't integrate the equations of motion from t = {t}")
+                        }));
+                    }
+                };
+                prev = result.state(step - i);
+                x = result.state(step + 1 - i);
+                i = step + 1;
+            }
+            // Interpolate the state at the moment of crossing
+            let point = self.crossing_point(crossing, step, &prev, &x);
+            failed = point.is_err();
+            crossing = crossing + 2. * F::PI();
+            Some(point)
+        })
+    }
+}
+
+#[test]
+fn test_poincare_iter() -> Result<()> {
+    use super::super::Method;
+
+    // Initialize a test model with a time step that
+    // doesn't divide the period of the primaries evenly
+    let mut model = Model::<f64>::test();
+    model.e = 0.6;
+    model.h = 0.03;
+    model.n = 3000;
+    let a_0 = model.acceleration(model.t_0, 1.)?;
+    model.x_0 = vec![1., 0., a_0];
+
+    // Compare the points of the map with the section of the integrated
+    // trajectory (for the symplectic method and for the one without the
+    // acceleration in the state; the time moments of the integrated chunks
+    // are rounded differently, so the points match up to a small error)
+    for method in [Method::Yoshida4, Method::Rk4] {
+        model.method = method;
+        let points = model.poincare_iter().collect::<Result<Vec<_>>>()?;
+        Model::integrate(&mut model)?;
+        let section = model.poincare_section()?;
+        if points.len() != section.len()
+            || points
+                .iter()
+                .zip(&section)
+                .any(|(p, s)| (p.0 - s.0).abs() >= 1e-9 || (p.1 - s.1).abs() >= 1e-9)
+        {
+            return Err(anyhow!(
+                "The points of the map don't match the section ({:?}): {:?} vs. {:?}",
+                method,
+                section,
+                points
+            ));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,165 @@
+//! Provides the [`poincare_section`](Model#method.poincare_section) method
+
+use anyhow::{anyhow, Context, Result};
+use integrators::{hermite, ResultExt};
+use numeric_literals::replace_float_literals;
+
+use super::super::Model;
+use crate::Float;
+
+#[replace_float_literals(F::from(literal).unwrap())]
+impl<F: Float> Model<F> {
+    /// Get the first moment the primaries pass the
+    /// pericenter (not earlier than the initial moment)
+    pub(super) fn first_crossing(&self) -> F {
+        let period = 2. * F::PI();
+        self.tau + ((self.t_0 - self.tau) / period).ceil() * period
+    }
+
+    /// Find the step (out of the first `n` ones) during which the primaries
+    /// pass the pericenter at the passed moment, or `None` if it's after the
+    /// last step (the crossing at the end of the last step belongs to it)
+    ///
+    /// Arguments:
+    /// * `crossing` --- Moment of passing the pericenter;
+    /// * `n` --- Number of steps.
+    pub(super) fn crossing_step(&self, crossing: F, n: usize) -> Option<usize> {
+        let t_end = self.t_0 + F::from(n).unwrap() * self.h;
+        if n == 0 || crossing > t_end {
+            return None;
+        }
+        Some(
+            ((crossing - self.t_0) / self.h)
+                .floor()
+                .to_usize()
+                .unwrap()
+                .min(n - 1),
+        )
+    }
+
+    /// Interpolate the point of the section (the position and the velocity
+    /// at the moment of crossing) between the states at the ends of the step
+    /// using cubic Hermite polynomials
+    ///
+    /// Arguments:
+    /// * `crossing` --- Moment of passing the pericenter;
+    /// * `step` --- Index of the step;
+    /// * `x_1` --- State at the start of the step;
+    /// * `x_2` --- State at the end of the step.
+    pub(super) fn crossing_point(
+        &self,
+        crossing: F,
+        step: usize,
+        x_1: &[F],
+        x_2: &[F],
+    ) -> Result<(F, F)> {
+        // Get the derivatives of the position and the velocity (the
+        // acceleration is a part of the state only in the symplectic
+        // case, so it's computed otherwise)
+        let derivatives = |i: usize, state: &[F]| -> Result<[F; 2]> {
+            if state.len() == 3 {
+                return Ok([state[1], state[2]]);
+            }
+            let t = self.t_0 + F::from(i).unwrap() * self.h;
+            let a = self
+                .acceleration(t, state[0])
+                .with_context(|| format!("Couldn't compute the acceleration at t = {t}"))?;
+            Ok([state[1], a])
+        };
+        let (d_1, d_2) = (derivatives(step, x_1)?, derivatives(step + 1, x_2)?);
+        let fraction = (crossing - (self.t_0 + F::from(step).unwrap() * self.h)) / self.h;
+        let state = hermite(&x_1[0..2], &d_1, &x_2[0..2], &d_2, self.h, fraction);
+        Ok((state[0], state[1]))
+    }
+
+    /// Collect the points of the Poincaré section (the position and the
+    /// velocity of the third body at the moments the primaries pass the
+    /// pericenter) from the integrated trajectory
+    ///
+    /// The states at the moments of crossing are interpolated between the
+    /// nearest steps using cubic Hermite polynomials, so the time step doesn't
+    /// have to divide the period evenly. The points follow the trajectory
+    /// of the chosen integration method
+    pub fn poincare_section(&self) -> Result<Vec<(F, F)>> {
+        let x = &self.results.x;
+        if x.ncols() < 2 {
+            return Err(anyhow!(
+                "The equations of motion must be integrated for at least one step"
+            ));
+        }
+        let n = x.ncols() - 1;
+        // Interpolate the states at the moments of crossing
+        let mut crossing = self.first_crossing();
+        let mut points = Vec::new();
+        while let Some(step) = self.crossing_step(crossing, n) {
+            points.push(self.crossing_point(crossing, step, &x.state(step), &x.state(step + 1))?);
+            crossing = crossing + 2. * F::PI();
+        }
+        Ok(points)
+    }
+}
+
+#[test]
+fn test_poincare_section() -> Result<()> {
+    use super::super::Method;
+    use std::f64::consts::PI;
+
+    // Initialize a test model with a time step that
+    // doesn't divide the period of the primaries evenly
+    let mut model = Model::<f64>::test();
+    model.e = 0.;
+    model.h = 0.03;
+    model.n = 10_000;
+    let a_0 = model.acceleration(model.t_0, 1.)?;
+    model.x_0 = vec![1., 0., a_0];
+
+    // Compute the section
+    Model::integrate(&mut model)?;
+    let points = model.poincare_section()?;
+
+    // Check the number of points (including the initial one)
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    let expected = (model.n as f64 * model.h / (2. * PI)).floor() as usize + 1;
+    if points.len() != expected {
+        return Err(anyhow!(
+            "The number of points is incorrect: {} vs. {}",
+            expected,
+            points.len()
+        ));
+    }
+
+    // The energy is conserved in the circular case, so it
+    // should be the same at every point of the section
+    let energy = |(z, z_v): (f64, f64)| z_v.powi(2) / 2. - 1. / (1. + z.powi(2)).sqrt();
+    let e_0 = energy(points[0]);
+    if let Some(&point) = points.iter().find(|&&p| (energy(p) - e_0).abs() >= 1e-6) {
+        return Err(anyhow!(
+            "The point {:?} is off the energy level: {} vs. {}",
+            point,
+            e_0,
+            energy(point)
+        ));
+    }
+
+    // Check that the section follows the chosen method (the state
+    // of the 4th-order Runge-Kutta method lacks the acceleration)
+    model.method = Method::Rk4;
+    Model::integrate(&mut model)?;
+    let points_rk4 = model.poincare_section()?;
+    if points_rk4.len() != points.len()
+        || points
+            .iter()
+            .zip(&points_rk4)
+            .any(|(p, p_rk4)| (p.0 - p_rk4.0).abs() >= 1e-4 || (p.1 - p_rk4.1).abs() >= 1e-4)
+    {
+        return Err(anyhow!(
+            "The section computed by the 4th-order Runge-Kutta method is different"
+        ));
+    }
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 //! Provides the [`radius`](Model#method.radius) method
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use numeric_literals::replace_float_literals;
 
 use super::super::Model;
@@ -10,18 +10,36 @@ use crate::Float;
 impl<F: Float> Model<F> {
     /// Compute the radius (distance from the focus to either
     /// of the primary bodies) from the eccentricity and time
+    ///
+    /// If the orbit of the primaries is unbound (`e > 1`), the absolute
+    /// value of the semi-major axis is the unit of length instead, and the
+    /// motion is not periodic (the primaries pass the pericenter once). The
+    /// parabolic case (`e = 1`) is not supported: the radius vanishes at
+    /// the pericenter in these units
     pub(super) fn radius(&self, t: F) -> Result<F> {
-        let e_a = self
-            .eccentric_anomaly(t % (2. * F::PI()) - self.tau)
-            .with_context(|| "Couldn't compute the eccentric anomaly")?;
-        Ok(1. - self.e * F::cos(e_a))
+        if self.e == 1. {
+            return Err(anyhow!("The parabolic case is not supported"));
+        }
+        if self.e < 1. {
+            // Reduce the mean anomaly to `[-π, π]`, so the
+            // root-finding doesn't lose precision near `2π`
+            let m = t - self.tau;
+            let m = m - 2. * F::PI() * (m / (2. * F::PI())).round();
+            let e_a = self
+                .eccentric_anomaly(m)
+                .with_context(|| "Couldn't compute the eccentric anomaly")?;
+            Ok(1. - self.e * F::cos(e_a))
+        } else {
+            let h_a = self
+                .eccentric_anomaly(t - self.tau)
+                .with_context(|| "Couldn't compute the hyperbolic anomaly")?;
+            Ok(self.e * F::cosh(h_a) - 1.)
+        }
     }
 }
 
 #[test]
 fn test_radius() -> Result<()> {
-    use anyhow::anyhow;
-
     // Initialize a test model
     let mut model = Model::<f64>::test();
     model.e = 0.6;
@@ -39,3 +57,55 @@ fn test_radius() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_radius_reduced() -> Result<()> {
+    // Initialize a test model
+    let mut model = Model::<f64>::test();
+    model.e = 0.9;
+
+    // Compute the radius shortly before the second pericenter
+    // passage (the mean anomaly is reduced to `-0.01`)
+    let r = model.radius(4. * std::f64::consts::PI - 0.01)?;
+
+    // Compare to the known result
+    let r_0 = 0.104_368_183_328_482_97;
+    if (r - r_0).abs() >= 1e-14 {
+        return Err(anyhow!(
+            "The value of the radius is incorrect: {r_0} vs. {r}"
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_radius_hyperbolic() -> Result<()> {
+    // Initialize a test model
+    let mut model = Model::<f64>::test();
+    model.e = 1.2;
+
+    // Compute the radius
+    let r = model.radius(std::f64::consts::FRAC_PI_2)?;
+
+    // Compare to the known result
+    let r_0 = 2.520_701_851_060_545_4;
+    if (r - r_0).abs() >= 1e-14 {
+        return Err(anyhow!(
+            "The value of the radius is incorrect: {r_0} vs. {r}"
+        ));
+    }
+
+    // The primaries should move away after the pericenter
+    if model.radius(100.)? <= r {
+        return Err(anyhow!("The primaries don't move away"));
+    }
+
+    // The parabolic case should be rejected
+    model.e = 1.;
+    if model.radius(std::f64::consts::FRAC_PI_2).is_ok() {
+        return Err(anyhow!("The parabolic case is not rejected"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,115 @@
+//! Provides the [`symmetrize_megnos`](Model#method.symmetrize_megnos) method
+
+use anyhow::{Context, Result};
+use integrators::{GeneralIntegrator, GeneralIntegrators, ResultExt};
+use numeric_literals::replace_float_literals;
+use rand::prelude::*;
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+use super::super::Model;
+use crate::Float;
+
+/// The equations of motion of both trajectories and the MEGNO equations
+/// integrated backward in time (the time in the MEGNO equations is the
+/// time elapsed from the starting point)
+struct Backward<'a, F: Float> {
+    /// Model
+    model: &'a Model<F>,
+    /// Time moment the integration starts from
+    t_end: F,
+}
+
+impl<F: Float> GeneralIntegrator<F> for Backward<'_, F> {
+    fn update(&self, t: F, x: &[F]) -> Result<Vec<F>> {
+        self.model.megno_equations(t, self.t_end - t, x)
+    }
+}
+
+impl<F: Float> Model<F> {
+    /// Compute MEGNOs backward in time from the end of the forward integration
+    /// and average them with the forward ones (aligned in time)
+    ///
+    /// Arguments:
+    /// * `t_0` --- Time moment the forward MEGNO integration started at;
+    /// * `n_m` --- Number of iterations of the forward MEGNO integration.
+    #[replace_float_literals(F::from(literal).unwrap())]
+    pub(super) fn symmetrize_megnos(&mut self, t_0: F, n_m: usize) -> Result<()> {
+        // Get the last state of the reference trajectory
+        let s = self.results.m.state(n_m);
+        // Variate (displace) it, so the backward integration
+        // doesn't just retrace the divergence of the forward one
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(self.seed);
+        let (z_tilda, z_v_tilda) = self
+            .displace(s[0], s[2], &mut rng)
+            .with_context(|| "Couldn't variate the last state")?;
+        // Compute the time moment
+        let t_end = t_0 + F::from(n_m).unwrap() * self.h;
+        // Integrate the MEGNO equations backward in time,
+        // measuring time from the end of the forward integration
+        let backward = Backward { model: self, t_end };
+        let m = backward
+            .integrate(
+                &[s[0], z_tilda, s[2], z_v_tilda, 0., 0.],
+                t_end,
+                -self.h,
+                n_m,
+                GeneralIntegrators::RungeKutta4th,
+            )
+            .with_context(|| "Couldn't integrate the MEGNO equations backward")?;
+        // Average the MEGNOs
+        for i in 0..=n_m {
+            // Get the index of the same time moment in the backward
+            // integration and the time elapsed from its start
+            let j = n_m - i;
+            let tau = F::from(j).unwrap() * self.h;
+            // Both integrals vanish at the singular point
+            let (megno, mean_megno) = if tau.is_zero() {
+                (0., 0.)
+            } else {
+                // The time step is negative, so the integral
+                // in the mean MEGNO has the opposite sign
+                (2. * m[(4, j)] / tau, -m[(5, j)] / tau)
+            };
+            self.results.m[(4, i)] = (self.results.m[(4, i)] + megno) / 2.;
+            self.results.m[(5, i)] = (self.results.m[(5, i)] + mean_megno) / 2.;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_symmetric_megno() -> Result<()> {
+    use anyhow::anyhow;
+
+    // Compute the mean MEGNOs of a regular orbit,
+    // return their maximum overshoot above 2
+    let overshoot = |symmetric_megno: bool| -> Result<f64> {
+        // Initialize a test model
+        let mut model = Model::<f64>::test();
+        model.e = 0.4;
+        model.i_m = 100;
+        model.compute_megnos = true;
+        model.symmetric_megno = symmetric_megno;
+        let a_0 = model.acceleration(model.t_0, 1.5)?;
+        model.x_0 = vec![1.5, 0., a_0];
+        // Integrate
+        Model::integrate(&mut model)?;
+        let m = model.results.m.result(5);
+        if m.iter().any(|x| !x.is_finite()) {
+            return Err(anyhow!("Some of the mean MEGNOs are not finite"));
+        }
+        Ok(m.iter().fold(0., |acc, &x| f64::max(acc, x - 2.)))
+    };
+
+    // Compare the overshoots
+    let (forward, symmetric) = (overshoot(false)?, overshoot(true)?);
+    if symmetric >= forward {
+        return Err(anyhow!(
+            "The overshoot of the symmetric MEGNOs is not smaller: {} vs. {}",
+            forward,
+            symmetric
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,128 @@
+//! Provides the [`rotation_number`](Model#method.rotation_number)
+//! and [`twist`](Model#method.twist) methods
+
+use anyhow::{anyhow, Context, Result};
+use numeric_literals::replace_float_literals;
+
+use super::super::Model;
+use crate::Float;
+
+impl<F: Float> Model<F> {
+    /// Compute the rotation number of the orbit starting from rest at the passed
+    /// position, i.e. the number of turns the third body makes around the origin
+    /// of the phase plane per one period of the primaries
+    ///
+    /// The equations of motion are integrated by the chosen method. The angle
+    /// in the phase plane is tracked at every step, so the result is not
+    /// limited to the interval `[0, 1)`
+    ///
+    /// Arguments:
+    /// * `z_0` --- Initial value of position (the action of the orbit).
+    #[replace_float_literals(F::from(literal).unwrap())]
+    fn rotation_number(&self, z_0: F) -> Result<F> {
+        // Compute the initial acceleration
+        let a_0 = self
+            .acceleration(self.t_0, z_0)
+            .with_context(|| "Couldn't compute the initial acceleration")?;
+        // Integrate the equations of motion
+        let result = self
+            .integrate_motion(&[z_0, 0., a_0], self.t_0, self.n)
+            .with_context(|| "Couldn't integrate the equations of motion")?;
+        // Accumulate the angle the phase point sweeps (clockwise)
+        let mut angle = 0.;
+        let mut theta_prev = F::atan2(-result[(1, 0)], result[(0, 0)]);
+        for j in 1..=self.n {
+            let theta = F::atan2(-result[(1, j)], result[(0, j)]);
+            // Unwrap the difference into `(-π, π]`
+            let mut diff = theta - theta_prev;
+            if diff > F::PI() {
+                diff = diff - 2. * F::PI();
+            } else if diff <= -F::PI() {
+                diff = diff + 2. * F::PI();
+            }
+            angle = angle + diff;
+            theta_prev = theta;
+        }
+        // Normalize by the number of periods of the primaries
+        let periods = F::from(self.n).unwrap() * self.h / (2. * F::PI());
+        Ok(angle / (2. * F::PI()) / periods)
+    }
+
+    /// Compute the twist of the Poincaré map: the derivatives of the rotation
+    /// number with respect to the action, computed by finite differences
+    /// between the consecutive orbits
+    ///
+    /// The orbits start from rest, so the initial positions serve as their
+    /// actions. MEGNOs must be disabled
+    ///
+    /// Arguments:
+    /// * `actions` --- Initial values of position (in the ascending order).
+    #[allow(dead_code)]
+    pub fn twist(&self, actions: &[F]) -> Result<Vec<F>> {
+        if self.compute_megnos {
+            return Err(anyhow!("The twist can't be computed together with MEGNOs"));
+        }
+        if actions.len() < 2 {
+            return Err(anyhow!(
+                "At least two actions are required to compute the twist"
+            ));
+        }
+        // Compute the rotation numbers
+        let rotation_numbers = actions
+            .iter()
+            .map(|&z_0| {
+                self.rotation_number(z_0).with_context(|| {
+                    format!("Couldn't compute the rotation number for the action {z_0}")
+                })
+            })
+            .collect::<Result<Vec<F>>>()?;
+        // Compute the finite differences
+        Ok(actions
+            .windows(2)
+            .zip(rotation_numbers.windows(2))
+            .map(|(a, r)| (r[1] - r[0]) / (a[1] - a[0]))
+            .collect())
+    }
+}
+
+#[test]
+fn test_twist() -> Result<()> {
+    use super::super::Method;
+
+    // Initialize a test model in the near-circular regime
+    let mut model = Model::<f64>::test();
+    model.e = 0.01;
+    model.n = 40000;
+
+    // Check the rotation number of a small orbit (the frequency
+    // of small oscillations in the circular case is 1)
+    let rotation_number = model.rotation_number(0.01)?;
+    if (rotation_number - 1.).abs() >= 1e-2 {
+        return Err(anyhow!(
+            "The rotation number of the small orbit is incorrect: 1 vs. {}",
+            rotation_number
+        ));
+    }
+
+    // Check that the chosen method is used
+    model.method = Method::Leapfrog;
+    let rotation_number_leapfrog = model.rotation_number(0.01)?;
+    #[allow(clippy::float_cmp)]
+    if rotation_number_leapfrog == rotation_number || (rotation_number_leapfrog - 1.).abs() >= 1e-2
+    {
+        return Err(anyhow!(
+            "The rotation number computed by the leapfrog method is incorrect: {} vs. {}",
+            rotation_number,
+            rotation_number_leapfrog
+        ));
+    }
+    model.method = Method::Yoshida4;
+
+    // Compute the twist
+    let twist = model.twist(&[0.2, 0.4, 0.6])?;
+    if twist.len() != 2 || twist.iter().any(|x| !x.is_finite() || x.abs() < 1e-3) {
+        return Err(anyhow!("The twist is not finite and non-zero: {:?}", twist));
+    }
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
 //! Provides IO methods
 
+mod display;
 mod from;
 mod write;
@@ -0,0 +1,65 @@
+//! Provides the [`display`](Model#method.display) method
+
+use integrators::ResultExt;
+
+use super::super::{Method, Model};
+use crate::Float;
+
+impl<F: Float> Model<F> {
+    /// Format the first `max_cols` states of the integrated trajectory (or
+    /// of both trajectories and MEGNOs, if they're computed) as a table
+    ///
+    /// If MEGNOs are computed, the table is preceded by the method used for
+    /// them (which is the 6th-order Yoshida method if the error of the
+    /// reference trajectory exceeded the tolerance)
+    ///
+    /// Arguments:
+    /// * `max_cols` --- Maximum number of states.
+    pub fn display(&self, max_cols: usize) -> String {
+        if self.compute_megnos {
+            let t_0 = self.t_0 + F::from(self.i_m).unwrap() * self.h;
+            let method = if self.results.higher_order {
+                format!(
+                    "{:?} (the error of the reference trajectory exceeded the tolerance)",
+                    Method::Yoshida6
+                )
+            } else {
+                format!("{:?}", self.method)
+            };
+            format!(
+                "MEGNOs were computed with the method {method}\n{}",
+                self.results.m.display_trajectory(t_0, self.h, max_cols)
+            )
+        } else {
+            self.results
+                .x
+                .display_trajectory(self.t_0, self.h, max_cols)
+        }
+    }
+}
+
+#[test]
+fn test_display() -> anyhow::Result<()> {
+    // Initialize a test model
+    let mut model = Model::<f64>::test();
+    model.n = 100;
+    let a_0 = model.acceleration(model.t_0, 1.)?;
+    model.x_0 = vec![1., 0., a_0];
+
+    // Integrate the model and format the first states
+    model.integrate()?;
+    let table = model.display(3);
+
+    // Check the header, the initial state and the truncation
+    let lines: Vec<&str> = table.lines().collect();
+    if lines.len() != 5
+        || !lines[0].trim_start().starts_with('t')
+        || !lines[1].contains("0.000000")
+        || !lines[1].contains("1.000000")
+        || lines[4] != "... (98 more)"
+    {
+        return Err(anyhow::anyhow!("The table is incorrect:\n{table}"));
+    }
+
+    Ok(())
+}
@@ -1,9 +1,13 @@
 //! Provides the [`from`](Model#method.from) method
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use numeric_literals::replace_float_literals;
 
-use super::super::{Model, Results};
+#[cfg(feature = "profiling")]
+use super::super::Counter;
+#[cfg(test)]
+use super::super::{MegnoDefinition, Method, OutputFormat};
+use super::super::{Model, Results, VariationMode};
 use crate::cli::Args;
 use crate::Float;
 
@@ -30,11 +34,45 @@ impl<F: Float> Model<F> {
                 .round()
                 .to_usize()
                 .unwrap(),
+            method: args.method,
             // Skip the first quarter of the period
             i_m: (1. / args.h).round().to_usize().unwrap(),
             compute_megnos: args.compute_megnos,
+            megno_definition: args.megno_definition,
+            megno_tolerance: args.megno_tolerance,
+            symmetric_megno: args.symmetric_megno,
+            compute_poincare: args.compute_poincare,
+            compute_lyapunov: args.compute_lyapunov,
+            lyapunov_interval: args.lyapunov_interval,
+            compute_regularized: args.compute_regularized,
+            variation: match (args.fixed_variation, args.megno_variation) {
+                (Some(magnitude), _) => VariationMode::Fixed {
+                    direction: (1., 0.),
+                    magnitude,
+                },
+                (None, Some(sd)) => VariationMode::Random { sd },
+                (None, None) => VariationMode::default(),
+            },
+            seed: args.seed,
+            output_format: args.output_format,
+            output_f32: args.output_f32,
+            create_dirs: args.create_dirs,
+            output_energy: args.output_energy,
+            timing_block: args.timing_block,
             results: Results::new(),
+            #[cfg(feature = "profiling")]
+            acceleration_calls: Counter::default(),
         };
+        // Make sure there is something left to integrate
+        // after skipping the iterations before computing MEGNOs
+        if model.compute_megnos && model.i_m >= model.n {
+            return Err(anyhow!(
+                "The number of iterations to skip before computing MEGNOs ({}) \
+                must be smaller than the total number of iterations ({})",
+                model.i_m,
+                model.n
+            ));
+        }
         // Compute the initial acceleration
         let a_0 = model
             .acceleration(t_0, args.z_0)
@@ -44,3 +82,44 @@ impl<F: Float> Model<F> {
         Ok(model)
     }
 }
+
+#[test]
+fn test_from_megno_skip() -> Result<()> {
+    // Prepare the arguments with zero periods,
+    // bypassing the command-line validators
+    let args = Args::<f64> {
+        output: std::env::temp_dir(),
+        compute_megnos: true,
+        megno_definition: MegnoDefinition::Hinse,
+        megno_tolerance: None,
+        symmetric_megno: false,
+        compute_poincare: false,
+        compute_lyapunov: false,
+        lyapunov_interval: 10,
+        compute_regularized: false,
+        megno_variation: None,
+        fixed_variation: None,
+        seed: 1,
+        output_format: OutputFormat::Bin,
+        output_f32: false,
+        create_dirs: false,
+        output_energy: false,
+        e: 0.,
+        tau: 0.,
+        z_0: 1.,
+        z_v_0: 0.,
+        h: 1e-2,
+        p: 0,
+        method: Method::Yoshida4,
+        timing_block: None,
+        verbose: false,
+    };
+    // Make sure the model is rejected with a descriptive error
+    match Model::from(&args) {
+        Ok(_) => Err(anyhow!(
+            "The model was created with the skip not smaller than the number of iterations"
+        )),
+        Err(e) if e.to_string().contains("MEGNOs") => Ok(()),
+        Err(e) => Err(anyhow!("The error is not descriptive: {}", e)),
+    }
+}
@@ -1,47 +1,434 @@
 //! Provides the [`write`](Model#method.write) method
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use bincode::Options;
 use integrators::ResultExt;
+use serde_json::{Map, Value};
 
 use std::fs::File;
-use std::io::BufWriter;
+use std::io::{BufWriter, Write};
 use std::path::Path;
 
-use super::super::Model;
+use super::super::{Model, OutputFormat};
 use crate::Float;
 
+/// A table of the output vectors (the columns are of the same length)
+struct Table<'a, F: Float> {
+    /// Name of the table
+    name: &'a str,
+    /// Names and values of the columns
+    columns: Vec<(&'a str, Vec<F>)>,
+    /// Write the columns into separate files in the binary format
+    /// (otherwise, the rows are written one after another)?
+    split: bool,
+}
+
+impl<F: Float> Table<'_, F> {
+    /// Get the number of rows
+    fn rows(&self) -> usize {
+        self.columns.first().map_or(0, |(_, values)| values.len())
+    }
+}
+
+/// Encoder of the output tables
+trait Encoder<F: Float> {
+    /// Write the table into the output directory
+    ///
+    /// Arguments:
+    /// * `table` --- Table;
+    /// * `output` --- Output directory.
+    fn write(&self, table: &Table<F>, output: &Path) -> Result<()>;
+}
+
+/// Native-endian `bincode` encoder (a vector per file)
+struct Bincode {
+    /// Convert the values to [`f32`] first?
+    single: bool,
+}
+
+impl<F: Float> Encoder<F> for Bincode {
+    fn write(&self, table: &Table<F>, output: &Path) -> Result<()> {
+        if table.split {
+            for (name, values) in &table.columns {
+                serialize_into(values, &output.join(format!("{name}.bin")), self.single)
+                    .with_context(|| format!("Couldn't serialize the `{name}` vector"))?;
+            }
+        } else {
+            let values: Vec<F> = (0..table.rows())
+                .flat_map(|i| table.columns.iter().map(move |(_, values)| values[i]))
+                .collect();
+            serialize_into(
+                &values,
+                &output.join(format!("{}.bin", table.name)),
+                self.single,
+            )
+            .with_context(|| format!("Couldn't serialize the `{}` vector", table.name))?;
+        }
+        Ok(())
+    }
+}
+
+/// CSV encoder (a header, then a line per row)
+struct Csv {
+    /// Convert the values to [`f32`] first?
+    single: bool,
+}
+
+impl<F: Float> Encoder<F> for Csv {
+    fn write(&self, table: &Table<F>, output: &Path) -> Result<()> {
+        let path = output.join(format!("{}.csv", table.name));
+        let file =
+            File::create(&path).with_context(|| "Couldn't open a file in write-only mode")?;
+        let mut writer = BufWriter::new(file);
+        let header: Vec<&str> = table.columns.iter().map(|(name, _)| *name).collect();
+        writeln!(writer, "{}", header.join(","))
+            .and_then(|()| {
+                (0..table.rows()).try_for_each(|i| {
+                    let row: Vec<String> = table
+                        .columns
+                        .iter()
+                        .map(|(_, values)| {
+                            if self.single {
+                                values[i].to_f32().unwrap().to_string()
+                            } else {
+                                values[i].to_string()
+                            }
+                        })
+                        .collect();
+                    writeln!(writer, "{}", row.join(","))
+                })
+            })
+            .and_then(|()| writer.flush())
+            .with_context(|| format!("Couldn't write the table to file {}", path.display()))?;
+        Ok(())
+    }
+}
+
+/// JSON encoder (an array of records)
+struct Json {
+    /// Convert the values to [`f32`] first?
+    single: bool,
+}
+
+impl<F: Float> Encoder<F> for Json {
+    fn write(&self, table: &Table<F>, output: &Path) -> Result<()> {
+        let path = output.join(format!("{}.json", table.name));
+        let records: Vec<Map<String, Value>> = (0..table.rows())
+            .map(|i| {
+                table
+                    .columns
+                    .iter()
+                    .map(|(name, values)| {
+                        let value = if self.single {
+                            Value::from(values[i].to_f32().unwrap())
+                        } else {
+                            Value::from(values[i].to_f64().unwrap())
+                        };
+                        ((*name).to_string(), value)
+                    })
+                    .collect()
+            })
+            .collect();
+        let file =
+            File::create(&path).with_context(|| "Couldn't open a file in write-only mode")?;
+        serde_json::to_writer(BufWriter::new(file), &records)
+            .with_context(|| format!("Couldn't serialize the table for file {}", path.display()))?;
+        Ok(())
+    }
+}
+
 /// Serialize the vector into the file
-fn serialize_into<F: Float>(vec: &[F], path: &Path) -> Result<()> {
+///
+/// If `single` is `true`, the values are converted to [`f32`] first
+fn serialize_into<F: Float>(vec: &[F], path: &Path, single: bool) -> Result<()> {
     let file = File::create(path).with_context(|| "Couldn't open a file in write-only mode")?;
     let mut writer = BufWriter::new(file);
 
-    bincode::DefaultOptions::new()
+    let options = bincode::DefaultOptions::new()
         .with_native_endian()
-        .with_fixint_encoding()
-        .serialize_into(&mut writer, vec)
-        .with_context(|| format!("Couldn't serialize the vector for file {:?}", path))?;
+        .with_fixint_encoding();
+    if single {
+        let vec: Vec<f32> = vec.iter().map(|x| x.to_f32().unwrap()).collect();
+        options.serialize_into(&mut writer, &vec)
+    } else {
+        options.serialize_into(&mut writer, vec)
+    }
+    .with_context(|| format!("Couldn't serialize the vector for file {:?}", path))?;
     Ok(())
 }
 
 impl<F: Float> Model<F> {
     /// Serialize the result vectors and write them to files in the output directory
+    ///
+    /// The trajectory (the time moments, the positions, the velocities and, if
+    /// computed, MEGNOs and energies) is a table with a row per step. In the
+    /// binary format, each of its columns goes into a separate file, while the
+    /// rows of the other tables (the Poincaré section, the estimates of the
+    /// Lyapunov exponent on the whole time grid, the trajectory integrated in
+    /// the fictitious time and the timings) are written one after another
+    /// into a single file
+    ///
+    /// If the directory is missing, it's created only if that's enabled
     pub fn write(&self, output: &Path) -> Result<()> {
-        if self.compute_megnos {
-            serialize_into(&self.results.m.result(0), &output.join("z.bin"))
-                .with_context(|| "Couldn't serialize the position vector")?;
-            serialize_into(&self.results.m.result(2), &output.join("z_v.bin"))
-                .with_context(|| "Couldn't serialize the velocity vector")?;
-            serialize_into(&self.results.m.result(4), &output.join("megno.bin"))
-                .with_context(|| "Couldn't serialize the MEGNOs vector")?;
-            serialize_into(&self.results.m.result(5), &output.join("mean_megno.bin"))
-                .with_context(|| "Couldn't serialize the MEGNOs vector")?;
+        if self.create_dirs {
+            std::fs::create_dir_all(output).with_context(|| {
+                format!("Couldn't create the output directory {}", output.display())
+            })?;
+        } else if !output.is_dir() {
+            return Err(anyhow!(
+                "The output directory {} doesn't exist",
+                output.display()
+            ));
+        }
+        let single = self.output_f32;
+        let encoder: Box<dyn Encoder<F>> = match self.output_format {
+            OutputFormat::Bin => Box::new(Bincode { single }),
+            OutputFormat::Csv => Box::new(Csv { single }),
+            OutputFormat::Json => Box::new(Json { single }),
+        };
+        // Prepare the trajectory
+        let mut columns = if self.compute_megnos {
+            vec![
+                ("z", self.results.m.result(0)),
+                ("z_v", self.results.m.result(2)),
+                ("megno", self.results.m.result(4)),
+                ("mean_megno", self.results.m.result(5)),
+            ]
         } else {
-            serialize_into(&self.results.x.result(0), &output.join("z.bin"))
-                .with_context(|| "Couldn't serialize the position vector")?;
-            serialize_into(&self.results.x.result(1), &output.join("z_v.bin"))
-                .with_context(|| "Couldn't serialize the velocity vector")?;
+            vec![
+                ("z", self.results.x.result(0)),
+                ("z_v", self.results.x.result(1)),
+            ]
+        };
+        let i_0 = if self.compute_megnos { self.i_m } else { 0 };
+        let t = (0..columns[0].1.len())
+            .map(|i| self.t_0 + F::from(i_0 + i).unwrap() * self.h)
+            .collect();
+        columns.insert(0, ("t", t));
+        if self.output_energy {
+            let energies = self
+                .energies()
+                .with_context(|| "Couldn't compute the energies")?;
+            columns.push(("energy", energies));
+        }
+        let mut tables = vec![Table {
+            name: "trajectory",
+            columns,
+            split: true,
+        }];
+        if self.compute_poincare {
+            let (z, z_v) = self.results.poincare.iter().copied().unzip();
+            tables.push(Table {
+                name: "poincare",
+                columns: vec![("z", z), ("z_v", z_v)],
+                split: false,
+            });
+        }
+        if self.compute_lyapunov {
+            tables.push(Table {
+                name: "lyapunov",
+                columns: vec![("lyapunov", self.results.lyapunov.clone())],
+                split: false,
+            });
+        }
+        if self.compute_regularized {
+            tables.push(Table {
+                name: "regularized",
+                columns: vec![
+                    ("t", self.results.s.result(0)),
+                    ("z", self.results.s.result(1)),
+                    ("z_v", self.results.s.result(2)),
+                ],
+                split: false,
+            });
+        }
+        if self.timing_block.is_some() {
+            let timings = self
+                .results
+                .timings
+                .iter()
+                .map(|d| F::from(d.as_secs_f64()).unwrap())
+                .collect();
+            tables.push(Table {
+                name: "timings",
+                columns: vec![("timing", timings)],
+                split: false,
+            });
+        }
+        // Write the tables
+        for table in &tables {
+            encoder
+                .write(table, output)
+                .with_context(|| format!("Couldn't write the `{}` table", table.name))?;
         }
         Ok(())
     }
 }
+
+#[test]
+fn test_output_f32() -> Result<()> {
+    // Initialize a test model
+    let mut model = Model::<f64>::test();
+    model.n = 1000;
+    let a_0 = model.acceleration(model.t_0, 1.)?;
+    model.x_0 = vec![1., 0., a_0];
+
+    // Integrate the model
+    model.integrate()?;
+
+    // Write the results in both precisions
+    let output = std::env::temp_dir().join("sitnikov-test-output-f32");
+    let (output_f64, output_f32) = (output.join("f64"), output.join("f32"));
+    std::fs::create_dir_all(&output_f64)?;
+    std::fs::create_dir_all(&output_f32)?;
+    model.write(&output_f64)?;
+    model.output_f32 = true;
+    model.write(&output_f32)?;
+
+    // Read the position vectors back
+    let options = bincode::DefaultOptions::new()
+        .with_native_endian()
+        .with_fixint_encoding();
+    let bytes_f64 = std::fs::read(output_f64.join("z.bin"))?;
+    let bytes_f32 = std::fs::read(output_f32.join("z.bin"))?;
+    let z_f64: Vec<f64> = options.deserialize(&bytes_f64)?;
+    let z_f32: Vec<f32> = options.deserialize(&bytes_f32)?;
+    std::fs::remove_dir_all(&output)?;
+
+    // Compare the values
+    if z_f64.len() != z_f32.len()
+        || z_f64
+            .iter()
+            .zip(z_f32.iter())
+            .any(|(&a, &b)| (a - f64::from(b)).abs() > a.abs() * f64::from(f32::EPSILON))
+    {
+        return Err(anyhow!(
+            "The single-precision results don't match the double-precision ones"
+        ));
+    }
+
+    // Compare the sizes of the data (without the length prefixes)
+    let prefix = std::mem::size_of::<u64>();
+    if 2 * (bytes_f32.len() - prefix) != bytes_f64.len() - prefix {
+        return Err(anyhow!(
+            "The single-precision file is not half the size: {} vs. {}",
+            bytes_f32.len(),
+            bytes_f64.len()
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_create_dirs() -> Result<()> {
+    // Initialize a test model
+    let mut model = Model::<f64>::test();
+    model.n = 10;
+    let a_0 = model.acceleration(model.t_0, 1.)?;
+    model.x_0 = vec![1., 0., a_0];
+
+    // Integrate the model
+    model.integrate()?;
+
+    // Prepare a nested path which doesn't exist yet
+    let output = std::env::temp_dir().join("sitnikov-test-create-dirs");
+    let nested = output.join("a").join("b");
+    if output.exists() {
+        std::fs::remove_dir_all(&output)?;
+    }
+
+    // Check that writing fails without creating directories
+    if model.write(&nested).is_ok() || output.exists() {
+        return Err(anyhow!(
+            "Writing into a missing directory didn't fail cleanly"
+        ));
+    }
+
+    // Check that writing succeeds with creating directories
+    model.create_dirs = true;
+    model.write(&nested)?;
+    let written = nested.join("z.bin").is_file();
+    std::fs::remove_dir_all(&output)?;
+    if !written {
+        return Err(anyhow!("The results weren't written"));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_formats() -> Result<()> {
+    // Initialize a test model
+    let mut model = Model::<f64>::test();
+    model.n = 100;
+    model.output_energy = true;
+    model.compute_regularized = true;
+    let a_0 = model.acceleration(model.t_0, 1.)?;
+    model.x_0 = vec![1., 0., a_0];
+
+    // Integrate the model
+    model.integrate()?;
+    let z = model.results.x.result(0);
+
+    // Write the results as CSV and JSON
+    let output = std::env::temp_dir().join("sitnikov-test-formats");
+    std::fs::create_dir_all(&output)?;
+    model.output_format = OutputFormat::Csv;
+    model.write(&output)?;
+    model.output_format = OutputFormat::Json;
+    model.write(&output)?;
+    let csv = std::fs::read_to_string(output.join("trajectory.csv"))?;
+    let json: Vec<Map<String, Value>> =
+        serde_json::from_slice(&std::fs::read(output.join("trajectory.json"))?)?;
+    let regularized = std::fs::read_to_string(output.join("regularized.csv"))?;
+    std::fs::remove_dir_all(&output)?;
+
+    // Check the CSV table
+    let lines: Vec<&str> = csv.lines().collect();
+    if lines.len() != z.len() + 1 || lines[0] != "t,z,z_v,energy" {
+        return Err(anyhow!(
+            "The CSV table is malformed: {} lines, the header is {:?}",
+            lines.len(),
+            lines[0]
+        ));
+    }
+    for (i, line) in lines[1..].iter().enumerate() {
+        let row = line
+            .split(',')
+            .map(str::parse)
+            .collect::<std::result::Result<Vec<f64>, _>>()?;
+        #[allow(clippy::cast_precision_loss, clippy::float_cmp)]
+        if row.len() != 4 || row[0] != i as f64 * model.h || row[1] != z[i] {
+            return Err(anyhow!("The CSV row {} is incorrect: {:?}", i, row));
+        }
+    }
+
+    // Check the table of the trajectory integrated in the fictitious time
+    #[allow(clippy::cast_precision_loss)]
+    let t_end = model.n as f64 * model.h;
+    let last = regularized
+        .lines()
+        .last()
+        .and_then(|line| line.split(',').next())
+        .map(str::parse::<f64>)
+        .transpose()?;
+    if !regularized.starts_with("t,z,z_v\n") || last.map_or(true, |t| (t - t_end).abs() > 1e-12) {
+        return Err(anyhow!(
+            "The table of the regularized trajectory is malformed (ends at {:?})",
+            last
+        ));
+    }
+
+    // Check the JSON records (the parser of `serde_json`
+    // doesn't round-trip the floats exactly by default)
+    if json.len() != z.len()
+        || json.iter().zip(&z).any(|(record, &z)| {
+            record.len() != 4 || record["z"].as_f64().map_or(true, |v| (v - z).abs() > 1e-15)
+        })
+    {
+        return Err(anyhow!("The JSON records are incorrect"));
+    }
+
+    Ok(())
+}